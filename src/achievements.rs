@@ -0,0 +1,251 @@
+use ggez::graphics::{Canvas, Color, DrawMode, DrawParam, Mesh, Rect, Text, TextFragment};
+use ggez::{Context, GameResult};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use tracing::{error, warn};
+
+use crate::config;
+use crate::export;
+use crate::locale::{tr, Message};
+
+/// Generations between two looks for spaceships and guns.
+pub const SAMPLE_INTERVAL: u64 = 30;
+// A gun is recognized by the glider count rising over this many samples in a row.
+const GUN_SAMPLES: usize = 4;
+const CROWDED_POPULATION: usize = 5000;
+const MARATHON_GENERATIONS: u64 = 10_000;
+// How long the banner announcing a new discovery stays up.
+const BANNER_DURATION: Duration = Duration::from_secs(4);
+const MARGIN: f32 = 8.0;
+const PADDING: f32 = 8.0;
+const LOCKED_COLOR: Color = Color::new(0.5, 0.5, 0.5, 1.0);
+const UNLOCKED_COLOR: Color = Color::new(1.0, 0.85, 0.2, 1.0);
+
+/// Notable things that can happen while exploring.
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Achievement {
+    FirstGlider,
+    FirstLwss,
+    GunBuilt,
+    Period15,
+    Period30,
+    Crowded,
+    Marathon,
+}
+
+// Every achievement, in the order they are listed in the panel.
+const ALL: [Achievement; 7] = [
+    Achievement::FirstGlider,
+    Achievement::FirstLwss,
+    Achievement::GunBuilt,
+    Achievement::Period15,
+    Achievement::Period30,
+    Achievement::Crowded,
+    Achievement::Marathon,
+];
+
+/// One entry of the discovery log.
+#[derive(Serialize, Deserialize)]
+struct Discovery {
+    achievement: Achievement,
+    /// Seconds since the Unix epoch.
+    unlocked_at: u64,
+    generation: u64,
+}
+
+/// The persistent discovery log and what is needed to notice new discoveries.
+pub struct Achievements {
+    log: Vec<Discovery>,
+    pub visible: bool,
+    banner: Option<(Achievement, Instant)>,
+    /// Glider counts of the most recent samples, newest last.
+    glider_samples: VecDeque<usize>,
+}
+
+impl Achievement {
+    fn message(self) -> Message {
+        match self {
+            Achievement::FirstGlider => Message::AchievementFirstGlider,
+            Achievement::FirstLwss => Message::AchievementFirstLwss,
+            Achievement::GunBuilt => Message::AchievementGunBuilt,
+            Achievement::Period15 => Message::AchievementPeriod15,
+            Achievement::Period30 => Message::AchievementPeriod30,
+            Achievement::Crowded => Message::AchievementCrowded,
+            Achievement::Marathon => Message::AchievementMarathon,
+        }
+    }
+}
+
+impl Achievements {
+    /// Read the discovery log, starting an empty one if there is none.
+    pub fn load() -> Achievements {
+        let log = match log_path().map(fs::read_to_string) {
+            Some(Ok(text)) => serde_json::from_str(&text).unwrap_or_else(|e| {
+                warn!("ignoring the discovery log: {}", e);
+                Vec::new()
+            }),
+            Some(Err(e)) if e.kind() != io::ErrorKind::NotFound => {
+                error!("cannot read the discovery log: {}", e);
+                Vec::new()
+            }
+            _ => Vec::new(),
+        };
+        Achievements {
+            log,
+            visible: false,
+            banner: None,
+            glider_samples: VecDeque::with_capacity(GUN_SAMPLES),
+        }
+    }
+
+    /// Check the population, the generation count and the period of a new generation.
+    pub fn observe_generation(
+        &mut self,
+        population: usize,
+        generation: u64,
+        period: Option<usize>,
+    ) {
+        if population > CROWDED_POPULATION {
+            self.unlock(Achievement::Crowded, generation);
+        }
+        if generation >= MARATHON_GENERATIONS {
+            self.unlock(Achievement::Marathon, generation);
+        }
+        match period {
+            Some(15) => self.unlock(Achievement::Period15, generation),
+            Some(30) => self.unlock(Achievement::Period30, generation),
+            _ => (),
+        }
+    }
+
+    /// Check the spaceships counted every `SAMPLE_INTERVAL` generations.
+    pub fn observe_ships(&mut self, gliders: usize, lwss: usize, generation: u64) {
+        if gliders > 0 {
+            self.unlock(Achievement::FirstGlider, generation);
+        }
+        if lwss > 0 {
+            self.unlock(Achievement::FirstLwss, generation);
+        }
+        if self.glider_samples.len() == GUN_SAMPLES {
+            self.glider_samples.pop_front();
+        }
+        self.glider_samples.push_back(gliders);
+        // A steady stream of new gliders means something keeps producing them
+        let rising = self
+            .glider_samples
+            .iter()
+            .zip(self.glider_samples.iter().skip(1))
+            .all(|(older, newer)| newer > older);
+        if self.glider_samples.len() == GUN_SAMPLES && rising {
+            self.unlock(Achievement::GunBuilt, generation);
+        }
+    }
+
+    /// Forget the samples taken from a grid that has just been replaced.
+    pub fn reset(&mut self) {
+        self.glider_samples.clear();
+    }
+
+    fn unlock(&mut self, achievement: Achievement, generation: u64) {
+        if self.log.iter().any(|d| d.achievement == achievement) {
+            return;
+        }
+        self.log.push(Discovery {
+            achievement,
+            unlocked_at: export::unix_time(),
+            generation,
+        });
+        self.banner = Some((achievement, Instant::now()));
+        if let Err(e) = self.save() {
+            error!("cannot write the discovery log: {}", e);
+        }
+    }
+
+    fn save(&self) -> io::Result<()> {
+        let Some(path) = log_path() else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(&self.log)?)
+    }
+
+    /// Draw the banner of a fresh discovery and, if visible, the discovery log.
+    pub fn draw(&self, ctx: &mut Context, canvas: &mut Canvas) -> GameResult {
+        let (window_width, window_height) = ctx.gfx.drawable_size();
+        if let Some((achievement, at)) = self.banner {
+            if at.elapsed() < BANNER_DURATION {
+                let text = Text::new(
+                    TextFragment::new(format!(
+                        "{}: {}",
+                        tr(Message::AchievementUnlocked),
+                        tr(achievement.message())
+                    ))
+                    .color(UNLOCKED_COLOR),
+                );
+                let size = text.measure(ctx)?;
+                let left = (window_width - size.x) / 2.0 - PADDING;
+                draw_panel(ctx, canvas, &text, [left, MARGIN])?;
+            }
+        }
+        if !self.visible {
+            return Ok(());
+        }
+
+        let mut text = Text::new(format!(
+            "{} ({}/{})",
+            tr(Message::AchievementsTitle),
+            self.log.len(),
+            ALL.len()
+        ));
+        for achievement in ALL {
+            let line = match self.log.iter().find(|d| d.achievement == achievement) {
+                Some(discovery) => TextFragment::new(format!(
+                    "\n* {} ({} {})",
+                    tr(achievement.message()),
+                    tr(Message::AchievementGeneration),
+                    discovery.generation
+                ))
+                .color(UNLOCKED_COLOR),
+                None => TextFragment::new(format!("\n  {}", tr(achievement.message())))
+                    .color(LOCKED_COLOR),
+            };
+            text.add(line);
+        }
+        let size = text.measure(ctx)?;
+        let corner = [
+            (window_width - size.x) / 2.0 - PADDING,
+            (window_height - size.y) / 2.0 - PADDING,
+        ];
+        draw_panel(ctx, canvas, &text, corner)
+    }
+}
+
+/// Draw text on a dark backdrop with its top left corner at `corner`.
+fn draw_panel(ctx: &mut Context, canvas: &mut Canvas, text: &Text, corner: [f32; 2]) -> GameResult {
+    let size = text.measure(ctx)?;
+    let panel = Rect::new(
+        corner[0],
+        corner[1],
+        size.x + 2.0 * PADDING,
+        size.y + 2.0 * PADDING,
+    );
+    let backdrop =
+        Mesh::new_rectangle(ctx, DrawMode::fill(), panel, Color::new(0.0, 0.0, 0.0, 0.8))?;
+    canvas.draw(&backdrop, DrawParam::default());
+    canvas.draw(
+        text,
+        DrawParam::default().dest([panel.x + PADDING, panel.y + PADDING]),
+    );
+    Ok(())
+}
+
+fn log_path() -> Option<PathBuf> {
+    config::project_dirs().map(|dirs| dirs.data_dir().join("discoveries.json"))
+}
@@ -0,0 +1,181 @@
+use ggez::audio::{SoundData, SoundSource, Source};
+use ggez::graphics::{Canvas, Color, DrawMode, DrawParam, Mesh, Rect};
+use ggez::{Context, GameResult};
+use std::fmt;
+use std::time::{Duration, Instant};
+
+// How long the window flashes when the alarm goes off.
+const FLASH_DURATION: Duration = Duration::from_millis(1500);
+const FLASH_COLOR: Color = Color::new(1.0, 0.2, 0.1, 0.45);
+// The beep: a short tone, loud enough to hear from across the room.
+const SAMPLE_RATE: u32 = 44_100;
+const BEEP_FREQUENCY: f32 = 880.0;
+const BEEP_DURATION: f32 = 0.4;
+const BEEP_VOLUME: f32 = 0.5;
+
+/// Population limits that, once crossed, pause the simulation with a beep
+/// and a flash, for unattended slow runs.
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub struct Limits {
+    /// Go off when the population rises above this.
+    pub above: Option<usize>,
+    /// Go off when the population drops below this.
+    pub below: Option<usize>,
+}
+
+impl Limits {
+    /// Parse limits such as `>10000 <50`, either or both, with `,` or `_`
+    /// allowed between the digits; an empty text sets none.
+    pub fn parse(text: &str) -> Result<Limits, String> {
+        let mut limits = Limits::default();
+        for term in text.split_whitespace() {
+            let (limit, number) = if let Some(number) = term.strip_prefix('>') {
+                (&mut limits.above, number)
+            } else if let Some(number) = term.strip_prefix('<') {
+                (&mut limits.below, number)
+            } else {
+                return Err(format!("`{}` is neither >N nor <N", term));
+            };
+            let digits: String = number.chars().filter(|&c| c != ',' && c != '_').collect();
+            let number = digits
+                .parse()
+                .map_err(|_| format!("`{}` is not a number of cells", number))?;
+            *limit = Some(number);
+        }
+        Ok(limits)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.above.is_none() && self.below.is_none()
+    }
+}
+
+impl fmt::Display for Limits {
+    /// Write the limits the way `parse` reads them.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let terms: Vec<String> = [(">", self.above), ("<", self.below)]
+            .into_iter()
+            .filter_map(|(sign, limit)| limit.map(|limit| format!("{}{}", sign, limit)))
+            .collect();
+        f.write_str(&terms.join(" "))
+    }
+}
+
+/// A limit the population has crossed.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Crossing {
+    Above(usize),
+    Below(usize),
+}
+
+/// Watches the population for crossing the limits, going off once per
+/// crossing: not again until the population has come back from it.
+pub struct Alarm {
+    pub limits: Limits,
+    /// The limit the population is past, if any.
+    crossed: Option<Crossing>,
+    flash_started: Option<Instant>,
+    /// Whether the alarm went off since the beep was last played.
+    beep_due: bool,
+    beep: Option<SoundData>,
+}
+
+impl Alarm {
+    pub fn new(limits: Limits) -> Alarm {
+        Alarm {
+            limits,
+            crossed: None,
+            flash_started: None,
+            beep_due: false,
+            beep: None,
+        }
+    }
+
+    /// Watch for other limits from now on.
+    pub fn set_limits(&mut self, limits: Limits) {
+        self.limits = limits;
+        self.crossed = None;
+    }
+
+    /// Look at the population of a new generation and tell whether it crossed
+    /// a limit, setting off the beep and flash if so.
+    pub fn observe(&mut self, population: usize) -> Option<Crossing> {
+        let crossing = match self.limits {
+            Limits {
+                above: Some(above), ..
+            } if population > above => Some(Crossing::Above(above)),
+            Limits {
+                below: Some(below), ..
+            } if population < below => Some(Crossing::Below(below)),
+            _ => None,
+        };
+        let previous = std::mem::replace(&mut self.crossed, crossing);
+        let crossing = crossing.filter(|&crossing| Some(crossing) != previous)?;
+        self.flash_started = Some(Instant::now());
+        self.beep_due = true;
+        Some(crossing)
+    }
+
+    /// Play the beep if the alarm went off since the last call.
+    pub fn play_due(&mut self, ctx: &mut Context) -> GameResult {
+        if !std::mem::take(&mut self.beep_due) {
+            return Ok(());
+        }
+        let data = self
+            .beep
+            .get_or_insert_with(|| SoundData::from_bytes(&beep_wav()));
+        let mut source = Source::from_data(ctx, data.clone())?;
+        source.play_detached(ctx)
+    }
+
+    /// Tint the whole window, fading out, while the alarm flashes.
+    pub fn draw_flash(&mut self, ctx: &mut Context, canvas: &mut Canvas) -> GameResult {
+        let Some(started) = self.flash_started else {
+            return Ok(());
+        };
+        let elapsed = started.elapsed();
+        if elapsed >= FLASH_DURATION {
+            self.flash_started = None;
+            return Ok(());
+        }
+        let fade = 1.0 - elapsed.as_secs_f32() / FLASH_DURATION.as_secs_f32();
+        let (window_width, window_height) = ctx.gfx.drawable_size();
+        let mut color = FLASH_COLOR;
+        color.a *= fade;
+        let tint = Mesh::new_rectangle(
+            ctx,
+            DrawMode::fill(),
+            Rect::new(0.0, 0.0, window_width, window_height),
+            color,
+        )?;
+        canvas.draw(&tint, DrawParam::default());
+        Ok(())
+    }
+}
+
+/// A short sine tone as a 16-bit mono WAV file, fading in and out to avoid clicks.
+fn beep_wav() -> Vec<u8> {
+    let count = (SAMPLE_RATE as f32 * BEEP_DURATION) as u32;
+    let data_size = count * 2;
+    let mut wav = Vec::with_capacity(44 + data_size as usize);
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data_size).to_le_bytes());
+    wav.extend_from_slice(b"WAVEfmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes());
+    // PCM, one channel
+    wav.extend_from_slice(&1u16.to_le_bytes());
+    wav.extend_from_slice(&1u16.to_le_bytes());
+    wav.extend_from_slice(&SAMPLE_RATE.to_le_bytes());
+    wav.extend_from_slice(&(SAMPLE_RATE * 2).to_le_bytes());
+    wav.extend_from_slice(&2u16.to_le_bytes());
+    wav.extend_from_slice(&16u16.to_le_bytes());
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&data_size.to_le_bytes());
+    for i in 0..count {
+        let t = i as f32 / SAMPLE_RATE as f32;
+        let envelope = (t / 0.02).min((BEEP_DURATION - t) / 0.02).min(1.0);
+        let sample = (t * BEEP_FREQUENCY * std::f32::consts::TAU).sin() * envelope * BEEP_VOLUME;
+        wav.extend_from_slice(&((sample * i16::MAX as f32) as i16).to_le_bytes());
+    }
+    wav
+}
@@ -0,0 +1,88 @@
+use ggez::graphics::{Canvas, Color, DrawMode, DrawParam, Mesh, MeshBuilder, Text};
+use ggez::{Context, GameResult};
+use serde::{Deserialize, Serialize};
+
+use crate::render::CellSize;
+
+const MARKER_COLOR: Color = Color::new(1.0, 0.4, 0.8, 1.0);
+
+/// A piece of text attached to a cell, e.g. "eater here".
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Label {
+    pub x: usize,
+    pub y: usize,
+    pub text: String,
+}
+
+/// Labels documenting the construction on the grid.
+pub struct Annotations {
+    labels: Vec<Label>,
+}
+
+impl Annotations {
+    pub fn new() -> Annotations {
+        Annotations { labels: Vec::new() }
+    }
+
+    pub fn labels(&self) -> &[Label] {
+        &self.labels
+    }
+
+    /// Replace every label, e.g. with the ones of a loaded state.
+    pub fn replace(&mut self, labels: Vec<Label>) {
+        self.labels = labels;
+    }
+
+    pub fn clear(&mut self) {
+        self.labels.clear();
+    }
+
+    /// Text of the label on a cell.
+    pub fn get(&self, x: usize, y: usize) -> Option<&str> {
+        self.labels
+            .iter()
+            .find(|label| label.x == x && label.y == y)
+            .map(|label| label.text.as_str())
+    }
+
+    /// Attach text to a cell, replacing its label; empty text removes the label.
+    pub fn set(&mut self, x: usize, y: usize, text: String) {
+        self.labels.retain(|label| label.x != x || label.y != y);
+        if !text.is_empty() {
+            self.labels.push(Label { x, y, text });
+        }
+    }
+
+    /// Move every label along with the cells after a resize, dropping those that fell off.
+    pub fn shift(&mut self, (dx, dy): (isize, isize), width: usize, height: usize) {
+        self.labels.retain_mut(|label| {
+            let (x, y) = (label.x as isize + dx, label.y as isize + dy);
+            let inside = (0..width as isize).contains(&x) && (0..height as isize).contains(&y);
+            (label.x, label.y) = (x.max(0) as usize, y.max(0) as usize);
+            inside
+        });
+    }
+
+    /// Mark every labelled cell and write its text next to it.
+    pub fn draw(&self, ctx: &mut Context, canvas: &mut Canvas, cell: CellSize) -> GameResult {
+        if self.labels.is_empty() {
+            return Ok(());
+        }
+        let mut markers = MeshBuilder::new();
+        for label in &self.labels {
+            let center = cell.point(label.x as f32 + 0.5, label.y as f32 + 0.5);
+            let radius = cell.width.max(cell.height) * 0.7;
+            markers.circle(DrawMode::stroke(2.0), center, radius, 0.5, MARKER_COLOR)?;
+        }
+        canvas.draw(&Mesh::from_data(ctx, markers.build()), DrawParam::default());
+        for label in &self.labels {
+            canvas.draw(
+                &Text::new(label.text.as_str()),
+                DrawParam::default()
+                    .dest(cell.point(label.x as f32 + 1.5, label.y as f32 - 0.5))
+                    .color(MARKER_COLOR),
+            );
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,59 @@
+use ggez::glam::Vec2;
+use ggez::graphics::{Canvas, Color, DrawParam, Image};
+use ggez::{Context, GameError, GameResult};
+use std::fs;
+use std::path::Path;
+
+use crate::render::CellSize;
+
+/// Opacity used when none is given on the command line.
+pub const DEFAULT_OPACITY: f32 = 0.25;
+// Amount the opacity changes per key press.
+const OPACITY_STEP: f32 = 0.05;
+
+/// An image drawn faintly behind the grid, e.g. for tracing shapes with cells.
+pub struct Background {
+    image: Image,
+    opacity: f32,
+}
+
+impl Background {
+    /// Load an image from anywhere on disk, not only from the resource directory.
+    pub fn load(ctx: &mut Context, path: &Path, opacity: f32) -> GameResult<Background> {
+        let bytes = fs::read(path).map_err(|e| {
+            GameError::ResourceLoadError(format!("cannot read {}: {}", path.display(), e))
+        })?;
+        Ok(Background {
+            image: Image::from_bytes(ctx, &bytes)?,
+            opacity: opacity.clamp(0.0, 1.0),
+        })
+    }
+
+    pub fn opacity(&self) -> f32 {
+        self.opacity
+    }
+
+    /// Make the image more visible.
+    pub fn increase_opacity(&mut self) {
+        self.opacity = (self.opacity + OPACITY_STEP).min(1.0);
+    }
+
+    /// Make the image less visible.
+    pub fn decrease_opacity(&mut self) {
+        self.opacity = (self.opacity - OPACITY_STEP).max(0.0);
+    }
+
+    /// Draw the image stretched over a grid of `width` by `height` cells.
+    pub fn draw(&self, canvas: &mut Canvas, width: usize, height: usize, cell: CellSize) {
+        let scale = Vec2::new(
+            width as f32 * cell.width / self.image.width() as f32,
+            height as f32 * cell.height / self.image.height() as f32,
+        );
+        canvas.draw(
+            &self.image,
+            DrawParam::default()
+                .scale(scale)
+                .color(Color::new(1.0, 1.0, 1.0, self.opacity)),
+        );
+    }
+}
@@ -0,0 +1,140 @@
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use crate::cli::BatchOptions;
+use crate::game::RULE;
+use crate::pattern::Pattern;
+use crate::period::{PeriodDetector, MAX_PERIOD};
+use crate::universe::Universe;
+
+// Dead cells added around each pattern when no universe size is given.
+const DEFAULT_MARGIN: usize = 64;
+// Name of the summary written next to the final states.
+const SUMMARY_FILE: &str = "summary.csv";
+
+/// What happened to one pattern.
+struct Outcome {
+    universe_width: usize,
+    universe_height: usize,
+    initial_population: usize,
+    final_population: usize,
+    final_width: usize,
+    final_height: usize,
+    /// Period of the final state, if it repeats within the last generations.
+    period: Option<usize>,
+}
+
+/// Run every pattern file in a directory and write the final states and a summary CSV.
+pub fn run(options: &BatchOptions) -> io::Result<()> {
+    let mut paths: Vec<PathBuf> = fs::read_dir(&options.input)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| is_pattern_file(path))
+        .collect();
+    paths.sort();
+    fs::create_dir_all(&options.output)?;
+
+    let mut summary = BufWriter::new(File::create(options.output.join(SUMMARY_FILE))?);
+    writeln!(
+        summary,
+        "file,universe_width,universe_height,generations,initial_population,\
+         final_population,final_width,final_height,period,error"
+    )?;
+    for path in &paths {
+        let file = path.file_name().unwrap_or_default().to_string_lossy();
+        match run_pattern(path, options) {
+            Ok(outcome) => {
+                println!(
+                    "{}: {} -> {} cells",
+                    file, outcome.initial_population, outcome.final_population
+                );
+                writeln!(
+                    summary,
+                    "{},{},{},{},{},{},{},{},{},",
+                    csv_field(&file),
+                    outcome.universe_width,
+                    outcome.universe_height,
+                    options.generations,
+                    outcome.initial_population,
+                    outcome.final_population,
+                    outcome.final_width,
+                    outcome.final_height,
+                    outcome.period.map(|p| p.to_string()).unwrap_or_default(),
+                )?;
+            }
+            Err(e) => {
+                eprintln!("{}: {}", file, e);
+                writeln!(summary, "{},,,,,,,,,{}", csv_field(&file), csv_field(&e))?;
+            }
+        }
+    }
+    summary.flush()?;
+    println!(
+        "{} patterns, summary in {}",
+        paths.len(),
+        options.output.join(SUMMARY_FILE).display()
+    );
+    Ok(())
+}
+
+/// Simulate one pattern and write its final state as RLE.
+fn run_pattern(path: &Path, options: &BatchOptions) -> Result<Outcome, String> {
+    let text = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let pattern = Pattern::parse(&text)?;
+
+    // The universe always has room for the pattern, however small the requested size
+    let (width, height) = options.size.unwrap_or((
+        pattern.width + 2 * DEFAULT_MARGIN,
+        pattern.height + 2 * DEFAULT_MARGIN,
+    ));
+    let mut universe = Universe::new(width.max(pattern.width), height.max(pattern.height));
+    let (left, top) = universe.centered(&pattern);
+    universe.place(&pattern, left, top);
+    let initial_population = universe.population();
+
+    let mut period = PeriodDetector::new();
+    let watch_from = options.generations.saturating_sub(MAX_PERIOD as u64);
+    let mut final_period = None;
+    for generation in 0..options.generations {
+        universe.step();
+        if generation >= watch_from {
+            final_period = period.observe(universe.grid());
+        }
+    }
+
+    let mut result = universe.to_pattern();
+    result.name = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned());
+    let out = options
+        .output
+        .join(path.file_stem().unwrap_or_default())
+        .with_extension("rle");
+    fs::write(&out, result.to_rle(RULE)).map_err(|e| e.to_string())?;
+
+    Ok(Outcome {
+        universe_width: universe.width(),
+        universe_height: universe.height(),
+        initial_population,
+        final_population: result.cells.len(),
+        final_width: result.width,
+        final_height: result.height,
+        period: final_period,
+    })
+}
+
+fn is_pattern_file(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(|e| e.to_str()),
+        Some("rle" | "cells" | "lif" | "life")
+    )
+}
+
+/// Quote a CSV field if it contains a separator or a quote.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
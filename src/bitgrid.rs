@@ -0,0 +1,336 @@
+use crate::rule::Rule;
+use crate::universe::Region;
+
+const BITS: usize = u64::BITS as usize;
+
+/// Cells packed 64 to a word, each row starting a new word, so that a
+/// generation is worked out for 64 cells at once with bitwise arithmetic.
+///
+/// Bit `k` of word `i` of a row is the cell in column `64 * i + k`; the bits
+/// past the last column are always 0.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    width: usize,
+    height: usize,
+    words_per_row: usize,
+    words: Vec<u64>,
+}
+
+/// How the cells past the edges are found.
+#[derive(Clone, Copy)]
+pub struct Edges {
+    pub toroidal: bool,
+    /// Columns the rows across the top and bottom edges of a torus are shifted by.
+    pub wrap_shift: usize,
+}
+
+impl BitGrid {
+    pub fn new(width: usize, height: usize) -> BitGrid {
+        let words_per_row = width.div_ceil(BITS);
+        BitGrid {
+            width,
+            height,
+            words_per_row,
+            words: vec![0; words_per_row * height],
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Become a copy of another grid, reusing the words already allocated.
+    pub fn copy_from(&mut self, other: &BitGrid) {
+        (self.width, self.height, self.words_per_row) =
+            (other.width, other.height, other.words_per_row);
+        self.words.clone_from(&other.words);
+    }
+
+    /// Pack rows of cells of any size, keeping only what fits.
+    pub fn from_rows(cells: &[Vec<bool>], width: usize, height: usize) -> BitGrid {
+        let mut grid = BitGrid::new(width, height);
+        for (y, row) in cells.iter().enumerate().take(height) {
+            for (x, _) in row
+                .iter()
+                .enumerate()
+                .take(width)
+                .filter(|&(_, &alive)| alive)
+            {
+                grid.set(x, y, true);
+            }
+        }
+        grid
+    }
+
+    /// Unpack into rows of cells.
+    pub fn to_rows(&self) -> Vec<Vec<bool>> {
+        (0..self.height)
+            .map(|y| (0..self.width).map(|x| self.get(x, y)).collect())
+            .collect()
+    }
+
+    /// Set the cells of the regions, e.g. to mask the frozen ones.
+    pub fn from_regions(regions: &[Region], width: usize, height: usize) -> BitGrid {
+        let mut grid = BitGrid::new(width, height);
+        for region in regions {
+            for y in region.top..(region.top + region.height).min(height) {
+                for x in region.left..(region.left + region.width).min(width) {
+                    grid.set(x, y, true);
+                }
+            }
+        }
+        grid
+    }
+
+    /// Whether a cell inside the grid is alive.
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        self.words[y * self.words_per_row + x / BITS] >> (x % BITS) & 1 == 1
+    }
+
+    /// Set the state of a cell inside the grid.
+    pub fn set(&mut self, x: usize, y: usize, alive: bool) {
+        let word = &mut self.words[y * self.words_per_row + x / BITS];
+        if alive {
+            *word |= 1 << (x % BITS);
+        } else {
+            *word &= !(1 << (x % BITS));
+        }
+    }
+
+    /// Kill every cell.
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    pub fn population(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    /// Every live cell `(x, y)`, row by row from the top.
+    pub fn live_cells(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.words
+            .iter()
+            .enumerate()
+            .flat_map(move |(index, &word)| {
+                let (y, left) = (
+                    index / self.words_per_row,
+                    index % self.words_per_row * BITS,
+                );
+                let mut bits = word;
+                std::iter::from_fn(move || {
+                    (bits != 0).then(|| {
+                        let bit = bits.trailing_zeros() as usize;
+                        // Clear the lowest set bit
+                        bits &= bits - 1;
+                        (left + bit, y)
+                    })
+                })
+            })
+    }
+
+    /// Smallest rectangle `(left, top, right, bottom)` containing every live cell.
+    pub fn bounding_box(&self) -> Option<(usize, usize, usize, usize)> {
+        let mut bounds: Option<(usize, usize, usize, usize)> = None;
+        for y in 0..self.height {
+            let row = self.row(y);
+            let (Some(first), Some(last)) = (
+                row.iter().position(|&word| word != 0),
+                row.iter().rposition(|&word| word != 0),
+            ) else {
+                continue;
+            };
+            let left = first * BITS + row[first].trailing_zeros() as usize;
+            let right = last * BITS + (BITS - 1 - row[last].leading_zeros() as usize);
+            bounds = Some(match bounds {
+                None => (left, y, right, y),
+                Some((l, t, r, _)) => (l.min(left), t, r.max(right), y),
+            });
+        }
+        bounds
+    }
+
+    /// The cells that differ from those of a grid of the same size.
+    pub fn flips(&self, other: &BitGrid) -> BitGrid {
+        BitGrid {
+            words: self
+                .words
+                .iter()
+                .zip(&other.words)
+                .map(|(a, b)| a ^ b)
+                .collect(),
+            ..*self
+        }
+    }
+
+    /// Lengths of the alternating runs of dead and live cells, row by row,
+    /// starting with dead ones and leaving out the last run if it is dead.
+    pub fn runs(&self) -> Vec<u64> {
+        let mut runs = Vec::new();
+        let (mut alive, mut run) = (false, 0u64);
+        for y in 0..self.height {
+            let row = self.row(y);
+            let mut x = 0;
+            while x < self.width {
+                let word = row[x / BITS] >> (x % BITS);
+                // Cells of the row left in this word
+                let left = (BITS - x % BITS).min(self.width - x);
+                let same = if alive { !word } else { word }.trailing_zeros() as usize;
+                let same = same.min(left);
+                run += same as u64;
+                x += same;
+                if same < left {
+                    runs.push(run);
+                    (alive, run) = (!alive, 0);
+                }
+            }
+        }
+        if alive {
+            runs.push(run);
+        }
+        runs
+    }
+
+    /// Bytes held by the words.
+    pub fn memory_usage(&self) -> usize {
+        self.words.capacity() * std::mem::size_of::<u64>()
+    }
+
+    fn row(&self, y: usize) -> &[u64] {
+        &self.words[y * self.words_per_row..(y + 1) * self.words_per_row]
+    }
+
+    /// Work out the next generation into `next`, a grid of the same size,
+    /// leaving the cells set in `frozen` as they are.
+    pub fn step(&self, next: &mut BitGrid, rule: Rule, edges: Edges, frozen: Option<&BitGrid>) {
+        let (width, height, words) = (self.width, self.height, self.words_per_row);
+        if width == 0 || height == 0 {
+            return;
+        }
+        let births: Vec<u32> = (0..=8).filter(|&n| rule.born(n as usize)).collect();
+        let survivals: Vec<u32> = (0..=8).filter(|&n| rule.survives(n as usize)).collect();
+        let last_bits = match width % BITS {
+            0 => u64::MAX,
+            bits => (1 << bits) - 1,
+        };
+        let zeros = vec![0; words];
+        // The rows across the top and bottom edges, on a twisted torus shifted along them
+        let (above_top, below_bottom) = if edges.toroidal {
+            let shift = edges.wrap_shift % width;
+            (
+                self.rotated(height - 1, shift),
+                self.rotated(0, width - shift),
+            )
+        } else {
+            (zeros.clone(), zeros.clone())
+        };
+        for y in 0..height {
+            let up = match y {
+                0 => &above_top[..],
+                _ => self.row(y - 1),
+            };
+            let middle = self.row(y);
+            let down = match y + 1 {
+                below if below == height => &below_bottom[..],
+                below => self.row(below),
+            };
+            let rows = [up, middle, down];
+            let into = &mut next.words[y * words..(y + 1) * words];
+            for (i, word) in into.iter_mut().enumerate() {
+                // The eight neighbors of the 64 cells, added up bit by bit
+                let mut sum = [0u64; 4];
+                for (r, row) in rows.iter().enumerate() {
+                    let neighbors = [
+                        west(row, i, width, edges.toroidal),
+                        row[i],
+                        east(row, i, width, edges.toroidal),
+                    ];
+                    for (c, &neighbor) in neighbors.iter().enumerate() {
+                        if (r, c) != (1, 1) {
+                            add(&mut sum, neighbor);
+                        }
+                    }
+                }
+                let cells = middle[i];
+                let mut alive = cells & counts(&sum, &survivals) | !cells & counts(&sum, &births);
+                if let Some(frozen) = frozen {
+                    let held = frozen.row(y)[i];
+                    alive = alive & !held | cells & held;
+                }
+                *word = if i + 1 == words {
+                    alive & last_bits
+                } else {
+                    alive
+                };
+            }
+        }
+    }
+
+    /// The words of a row with every cell moved `shift` columns to the right,
+    /// those past the right edge coming back in on the left.
+    fn rotated(&self, y: usize, shift: usize) -> Vec<u64> {
+        let row = self.row(y);
+        let mut rotated = vec![0; self.words_per_row];
+        for x in (0..self.width).filter(|&x| row[x / BITS] >> (x % BITS) & 1 == 1) {
+            let to = (x + shift) % self.width;
+            rotated[to / BITS] |= 1 << (to % BITS);
+        }
+        rotated
+    }
+}
+
+/// Word `i` of a row with every cell's neighbor to the west in its place.
+fn west(row: &[u64], i: usize, width: usize, toroidal: bool) -> u64 {
+    let carry = if i > 0 {
+        row[i - 1] >> (BITS - 1)
+    } else if toroidal {
+        // The last column is west of the first
+        row[(width - 1) / BITS] >> ((width - 1) % BITS) & 1
+    } else {
+        0
+    };
+    row[i] << 1 | carry
+}
+
+/// Word `i` of a row with every cell's neighbor to the east in its place.
+fn east(row: &[u64], i: usize, width: usize, toroidal: bool) -> u64 {
+    let last = row.len() - 1;
+    if i < last {
+        return row[i] >> 1 | row[i + 1] << (BITS - 1);
+    }
+    // The first column is east of the last; the bits past it are 0
+    let carry = if toroidal { row[0] & 1 } else { 0 };
+    row[i] >> 1 | carry << ((width - 1) % BITS)
+}
+
+/// Add one neighbor to each of 64 sums held as 4 bit planes, lowest first.
+fn add(sum: &mut [u64; 4], neighbor: u64) {
+    let mut carry = neighbor;
+    for plane in &mut sum[..3] {
+        let next = *plane & carry;
+        *plane ^= carry;
+        carry = next;
+    }
+    // 8 is the most neighbors there are, so the top plane never carries
+    sum[3] |= carry;
+}
+
+/// The bits whose sum is one of `counts`.
+fn counts(sum: &[u64; 4], counts: &[u32]) -> u64 {
+    counts
+        .iter()
+        .map(|&n| {
+            sum.iter()
+                .enumerate()
+                .fold(u64::MAX, |matching, (bit, &plane)| {
+                    matching & if n >> bit & 1 == 1 { plane } else { !plane }
+                })
+        })
+        .fold(0, |any, matching| any | matching)
+}
@@ -0,0 +1,124 @@
+use ggez::graphics::{Canvas, Color, DrawMode, DrawParam, Mesh, MeshBuilder, Rect};
+use ggez::{Context, GameResult};
+use serde::Deserialize;
+use tracing::warn;
+
+// Defaults: a slate frame, and the outside shaded a little lighter than the
+// black universe while dimming whatever is drawn there.
+const FRAME_COLOR: Color = Color::new(0.35, 0.35, 0.45, 1.0);
+const OUTSIDE_COLOR: Color = Color::new(0.12, 0.12, 0.16, 0.7);
+const FRAME_WIDTH: f32 = 2.0;
+
+/// The `[border]` table of the configuration.
+#[derive(Deserialize)]
+#[serde(default)]
+pub struct BorderConfig {
+    /// Width of the frame in unzoomed pixels; 0 leaves it out.
+    pub width: f32,
+    /// Colors as `#rrggbb` or `#rrggbbaa`.
+    pub color: Option<String>,
+    pub outside: Option<String>,
+}
+
+impl Default for BorderConfig {
+    fn default() -> BorderConfig {
+        BorderConfig {
+            width: FRAME_WIDTH,
+            color: None,
+            outside: None,
+        }
+    }
+}
+
+/// A frame around the universe and a shade over everything beyond it, so the
+/// edges stay unambiguous when the view is zoomed out or panned past them.
+pub struct Border {
+    width: f32,
+    color: Color,
+    outside: Color,
+}
+
+impl Border {
+    pub fn new(config: &BorderConfig) -> Border {
+        let color = |text: &Option<String>, default| match text {
+            Some(text) => parse_color(text).unwrap_or_else(|| {
+                warn!(
+                    "ignoring the border color `{}`, it is not like #rrggbb",
+                    text
+                );
+                default
+            }),
+            None => default,
+        };
+        Border {
+            width: config.width.max(0.0),
+            color: color(&config.color, FRAME_COLOR),
+            outside: color(&config.outside, OUTSIDE_COLOR),
+        }
+    }
+
+    /// Shade the part of `view` outside `grid` and frame `grid`, both in the
+    /// screen coordinates of the canvas.
+    pub fn draw(
+        &self,
+        ctx: &mut Context,
+        canvas: &mut Canvas,
+        view: Rect,
+        grid: Rect,
+    ) -> GameResult {
+        let mut builder = MeshBuilder::new();
+        let mut empty = true;
+        let (view_right, view_bottom) = (view.x + view.w, view.y + view.h);
+        let (grid_right, grid_bottom) = (grid.x + grid.w, grid.y + grid.h);
+        let top = grid.y.clamp(view.y, view_bottom);
+        let bottom = grid_bottom.clamp(view.y, view_bottom);
+        let left = grid.x.clamp(view.x, view_right);
+        let right = grid_right.clamp(view.x, view_right);
+        let outside = [
+            Rect::new(view.x, view.y, view.w, top - view.y),
+            Rect::new(view.x, bottom, view.w, view_bottom - bottom),
+            Rect::new(view.x, top, left - view.x, bottom - top),
+            Rect::new(right, top, view_right - right, bottom - top),
+        ];
+        for rect in outside
+            .into_iter()
+            .filter(|rect| rect.w > 0.0 && rect.h > 0.0)
+        {
+            builder.rectangle(DrawMode::fill(), rect, self.outside)?;
+            empty = false;
+        }
+        if self.width > 0.0 {
+            // Keep the frame outside the grid, so that it never covers edge cells
+            let half = self.width / 2.0;
+            let frame = Rect::new(
+                grid.x - half,
+                grid.y - half,
+                grid.w + self.width,
+                grid.h + self.width,
+            );
+            builder.rectangle(DrawMode::stroke(self.width), frame, self.color)?;
+            empty = false;
+        }
+        // A mesh without any vertices cannot be built
+        if !empty {
+            canvas.draw(&Mesh::from_data(ctx, builder.build()), DrawParam::default());
+        }
+        Ok(())
+    }
+}
+
+/// Parse a color written as `#rrggbb` or `#rrggbbaa`.
+fn parse_color(text: &str) -> Option<Color> {
+    let hex = text.strip_prefix('#')?;
+    if !(hex.len() == 6 || hex.len() == 8) || !hex.is_ascii() {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    let alpha = if hex.len() == 8 { channel(6)? } else { 255 };
+    Some(Color::from_rgba(
+        channel(0)?,
+        channel(2)?,
+        channel(4)?,
+        alpha,
+    ))
+}
@@ -0,0 +1,113 @@
+use ggez::graphics::{Canvas, Color, DrawMode, DrawParam, Mesh, MeshBuilder};
+use ggez::{Context, GameResult};
+
+use crate::locale::{tr, Message};
+use crate::render::CellSize;
+
+const FOOTPRINT_COLOR: Color = Color::new(0.4, 0.8, 1.0, 0.35);
+// The brushes to cycle through, from the single cell up.
+const BRUSHES: [Brush; 5] = [
+    Brush::SINGLE,
+    Brush {
+        size: 3,
+        shape: Shape::Square,
+    },
+    Brush {
+        size: 5,
+        shape: Shape::Square,
+    },
+    Brush {
+        size: 3,
+        shape: Shape::Circle,
+    },
+    Brush {
+        size: 5,
+        shape: Shape::Circle,
+    },
+];
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Shape {
+    Square,
+    Circle,
+}
+
+/// What painting with the mouse brings to life or kills around the cursor.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Brush {
+    /// Cells across, always odd so that the brush has a center.
+    pub size: usize,
+    pub shape: Shape,
+}
+
+impl Brush {
+    pub const SINGLE: Brush = Brush {
+        size: 1,
+        shape: Shape::Square,
+    };
+
+    /// The next brush, back to the single cell after the largest circle.
+    pub fn next(self) -> Brush {
+        let index = BRUSHES.iter().position(|&brush| brush == self).unwrap_or(0);
+        BRUSHES[(index + 1) % BRUSHES.len()]
+    }
+
+    pub fn previous(self) -> Brush {
+        let index = BRUSHES.iter().position(|&brush| brush == self).unwrap_or(0);
+        BRUSHES[(index + BRUSHES.len() - 1) % BRUSHES.len()]
+    }
+
+    /// The size and shape, e.g. `5×5 circle`.
+    pub fn describe(self) -> String {
+        let shape = match self.shape {
+            Shape::Square => Message::BrushSquare,
+            Shape::Circle => Message::BrushCircle,
+        };
+        format!("{}×{} {}", self.size, self.size, tr(shape))
+    }
+
+    /// The cells the brush covers centered on a cell, leaving out those past
+    /// the edges of a universe of the given size.
+    pub fn footprint(
+        self,
+        (x, y): (usize, usize),
+        width: usize,
+        height: usize,
+    ) -> impl Iterator<Item = (usize, usize)> {
+        let radius = (self.size / 2) as isize;
+        // A little over the radius so that the edges are round rather than pointed
+        let reach = (radius as f32 + 0.3).powi(2);
+        let shape = self.shape;
+        (-radius..=radius)
+            .flat_map(move |dy| (-radius..=radius).map(move |dx| (dx, dy)))
+            .filter(move |&(dx, dy)| {
+                shape == Shape::Square || ((dx * dx + dy * dy) as f32) <= reach
+            })
+            .filter_map(move |(dx, dy)| {
+                let (cell_x, cell_y) = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+                (cell_x < width && cell_y < height).then_some((cell_x, cell_y))
+            })
+    }
+
+    /// Shade the cells the brush would paint centered on a cell.
+    pub fn draw_footprint(
+        self,
+        ctx: &mut Context,
+        canvas: &mut Canvas,
+        cell: CellSize,
+        center: (usize, usize),
+        (width, height): (usize, usize),
+    ) -> GameResult {
+        let mut builder = MeshBuilder::new();
+        for (x, y) in self.footprint(center, width, height) {
+            builder.rectangle(
+                DrawMode::fill(),
+                cell.rect(x as f32, y as f32, 1.0, 1.0),
+                FOOTPRINT_COLOR,
+            )?;
+        }
+        let mesh = Mesh::from_data(ctx, builder.build());
+        canvas.draw(&mesh, DrawParam::default());
+        Ok(())
+    }
+}
@@ -0,0 +1,77 @@
+use ggez::graphics::Rect;
+use serde::{Deserialize, Serialize};
+
+// Limits of zooming, as screen pixels per unzoomed pixel.
+const MIN_ZOOM: f32 = 0.25;
+const MAX_ZOOM: f32 = 16.0;
+// Share of the way to the followed point covered each frame, so that the view
+// glides along instead of jumping with every generation.
+const FOLLOW_EASING: f32 = 0.15;
+
+/// The part of the grid that is on screen, zoomed and panned.
+///
+/// Positions are in unzoomed pixels, where the whole grid fits the window at a
+/// zoom of 1.
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub struct Camera {
+    zoom: f32,
+    /// Unzoomed position of the top left corner of the window.
+    x: f32,
+    y: f32,
+}
+
+impl Default for Camera {
+    fn default() -> Camera {
+        Camera {
+            zoom: 1.0,
+            x: 0.0,
+            y: 0.0,
+        }
+    }
+}
+
+impl Camera {
+    /// The unzoomed rectangle shown in a window of the given size, to be used as
+    /// the screen coordinates of the canvas.
+    pub fn view(&self, window_width: f32, window_height: f32) -> Rect {
+        Rect::new(
+            self.x,
+            self.y,
+            window_width / self.zoom,
+            window_height / self.zoom,
+        )
+    }
+
+    /// The unzoomed position under a point of the window.
+    pub fn world_at(&self, x: f32, y: f32) -> (f32, f32) {
+        (self.x + x / self.zoom, self.y + y / self.zoom)
+    }
+
+    /// Move the view along with a drag of `dx` by `dy` window pixels.
+    pub fn pan(&mut self, dx: f32, dy: f32) {
+        self.x -= dx / self.zoom;
+        self.y -= dy / self.zoom;
+    }
+
+    /// Zoom by `factor`, keeping the point under `(x, y)` in the window in place.
+    pub fn zoom_at(&mut self, factor: f32, x: f32, y: f32) {
+        let (world_x, world_y) = self.world_at(x, y);
+        self.zoom = (self.zoom * factor).clamp(MIN_ZOOM, MAX_ZOOM);
+        self.x = world_x - x / self.zoom;
+        self.y = world_y - y / self.zoom;
+    }
+
+    /// Move part of the way towards having the unzoomed point `(x, y)` in the
+    /// middle of a window of the given size.
+    pub fn follow(&mut self, x: f32, y: f32, window_width: f32, window_height: f32) {
+        let target_x = x - window_width / 2.0 / self.zoom;
+        let target_y = y - window_height / 2.0 / self.zoom;
+        self.x += (target_x - self.x) * FOLLOW_EASING;
+        self.y += (target_y - self.y) * FOLLOW_EASING;
+    }
+
+    /// Show the whole grid again, unzoomed.
+    pub fn reset(&mut self) {
+        *self = Camera::default();
+    }
+}
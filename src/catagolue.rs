@@ -0,0 +1,122 @@
+use rand::seq::SliceRandom;
+use sha2::{Digest, Sha256};
+
+use crate::pattern::Pattern;
+use crate::rule::Rule;
+
+const HOST: &str = "https://catagolue.hatsya.com";
+// The census of asymmetric soups, the one almost every search contributes to.
+const SYMMETRY: &str = "C1";
+// apgsearch fills a square of this size from the 256 bits of a hash.
+const SOUP_SIZE: usize = 16;
+// Digits of the extended Wechsler format: the first 32 are a column of a
+// five-row strip each, and all 36 count the empty columns of a `y` run.
+const DIGITS: &str = "0123456789abcdefghijklmnopqrstuvwxyz";
+
+/// Check that `code` looks like an apgcode, so that it can go into a URL as it is.
+pub fn check_code(code: &str) -> Result<(), String> {
+    let valid = code.contains('_')
+        && code
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(format!("`{}` is not an apgcode such as xq4_153", code))
+    }
+}
+
+/// The URL that lists the seeds of soups in which an object turned up under `rule`.
+pub fn samples_url(code: &str, rule: Rule) -> String {
+    let rule = rule.to_string().to_lowercase().replace('/', "");
+    format!("{}/textsamples/{}/{}/{}", HOST, code, rule, SYMMETRY)
+}
+
+/// Pick one of the seeds listed at a `samples_url` at random.
+pub fn pick_seed(text: &str) -> Option<String> {
+    // Lines may be prefixed with the symmetry, as in `C1/k_...`
+    let seeds: Vec<&str> = text
+        .lines()
+        .map(|line| line.trim().rsplit('/').next().unwrap_or(""))
+        .filter(|seed| !seed.is_empty())
+        .collect();
+    seeds
+        .choose(&mut rand::thread_rng())
+        .map(|seed| seed.to_string())
+}
+
+/// Recreate the soup apgsearch generated from `seed`: the bits of its SHA-256
+/// hash, two bytes to a row and the highest bit leftmost.
+pub fn soup(seed: &str) -> Pattern {
+    let hash = Sha256::digest(seed.as_bytes());
+    let mut cells = Vec::new();
+    for (i, byte) in hash.iter().enumerate() {
+        for bit in 0..8 {
+            if byte & (0x80 >> bit) != 0 {
+                cells.push((bit + 8 * (i % 2), i / 2));
+            }
+        }
+    }
+    Pattern {
+        name: Some(seed.to_string()),
+        rule: None,
+        width: SOUP_SIZE,
+        height: SOUP_SIZE,
+        cells,
+    }
+}
+
+/// Decode a still life, oscillator or spaceship back from its apgcode, in the
+/// phase and orientation the code was made from.
+pub fn object(code: &str) -> Result<Pattern, String> {
+    let wechsler = code
+        .strip_prefix("xs")
+        .or_else(|| code.strip_prefix("xp"))
+        .or_else(|| code.strip_prefix("xq"))
+        .and_then(|rest| rest.split_once('_'))
+        .map(|(_, wechsler)| wechsler)
+        .ok_or_else(|| {
+            format!(
+                "only objects coded xs, xp or xq can be decoded, not {}",
+                code
+            )
+        })?;
+    let invalid = || format!("{} is not a valid apgcode", code);
+    let mut cells = Vec::new();
+    let (mut x, mut strip) = (0, 0);
+    let mut chars = wechsler.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            'w' => x += 2,
+            'x' => x += 3,
+            // Read before a `z` after it could be taken for the end of the strip
+            'y' => {
+                let run = chars
+                    .next()
+                    .and_then(|c| DIGITS.find(c))
+                    .ok_or_else(invalid)?;
+                x += 4 + run;
+            }
+            'z' => (x, strip) = (0, strip + 1),
+            _ => {
+                let column = DIGITS[..32].find(c).ok_or_else(invalid)?;
+                for row in 0..5 {
+                    if column & (1 << row) != 0 {
+                        cells.push((x, strip * 5 + row));
+                    }
+                }
+                x += 1;
+            }
+        }
+    }
+    if cells.is_empty() {
+        return Err(invalid());
+    }
+    Ok(Pattern {
+        name: Some(code.to_string()),
+        rule: None,
+        width: cells.iter().map(|&(x, _)| x + 1).max().unwrap_or(0),
+        height: cells.iter().map(|&(_, y)| y + 1).max().unwrap_or(0),
+        cells,
+    })
+}
@@ -0,0 +1,288 @@
+use ggez::graphics::{Canvas, Color, DrawMode, DrawParam, Mesh, Rect, Text};
+use ggez::{Context, GameResult};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashSet};
+
+use crate::locale::{tr, Message};
+use crate::period::MAX_PERIOD;
+use crate::rule::Rule;
+use crate::spaceships::{normalize, orient, step};
+
+// Objects with more cells are not followed in isolation and stay unidentified.
+const MAX_OBJECT_CELLS: usize = 400;
+// Lines of the panel before the rarest objects are summed up.
+const MAX_LINES: usize = 20;
+const PADDING: f32 = 8.0;
+const MARGIN: f32 = 8.0;
+// Digits of the extended Wechsler format: a column of a five-row strip, top row
+// in the lowest bit, and the lengths of long runs of empty columns.
+const DIGITS: &[u8; 36] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+// Common names of the objects soups settle into most, by apgcode.
+const NAMES: [(&str, &str); 12] = [
+    ("xs4_33", "block"),
+    ("xs6_696", "beehive"),
+    ("xs7_2596", "loaf"),
+    ("xs5_253", "boat"),
+    ("xs6_356", "ship"),
+    ("xs4_252", "tub"),
+    ("xs8_6996", "pond"),
+    ("xp2_7", "blinker"),
+    ("xp2_7e", "toad"),
+    ("xp2_318c", "beacon"),
+    ("xq4_153", "glider"),
+    ("xq4_6frc", "lightweight spaceship"),
+];
+
+/// The objects of a settled grid, each identified by its apgcode, the name
+/// Catagolue files it under.
+pub struct Census {
+    /// Number of objects by apgcode.
+    counts: BTreeMap<String, usize>,
+    /// Objects that are neither still, oscillating nor moving on their own.
+    unidentified: usize,
+    pub visible: bool,
+}
+
+impl Census {
+    pub fn new() -> Census {
+        Census {
+            counts: BTreeMap::new(),
+            unidentified: 0,
+            visible: false,
+        }
+    }
+
+    /// Split the live cells into objects and identify each of them.
+    pub fn take(&mut self, grid: &[Vec<bool>], rule: Rule) {
+        self.counts.clear();
+        self.unidentified = 0;
+        for object in objects(grid) {
+            match apgcode(&object, rule) {
+                Some(code) => *self.counts.entry(code).or_default() += 1,
+                None => self.unidentified += 1,
+            }
+        }
+    }
+
+    /// List the objects, the most common first, in the bottom right corner.
+    pub fn draw(&self, ctx: &mut Context, canvas: &mut Canvas) -> GameResult {
+        let mut counts: Vec<(&String, &usize)> = self.counts.iter().collect();
+        counts.sort_by_key(|&(_, &count)| Reverse(count));
+        let mut lines = vec![tr(Message::CensusTitle).to_string()];
+        for (code, count) in counts.iter().take(MAX_LINES) {
+            let name = NAMES.iter().find(|(known, _)| known == code);
+            lines.push(match name {
+                Some((_, name)) => format!("{:>5}  {} ({})", count, code, name),
+                None => format!("{:>5}  {}", count, code),
+            });
+        }
+        let rest: usize = counts.iter().skip(MAX_LINES).map(|(_, &count)| count).sum();
+        if rest > 0 {
+            lines.push(format!("{:>5}  {}", rest, tr(Message::CensusOthers)));
+        }
+        if self.unidentified > 0 {
+            lines.push(format!(
+                "{:>5}  {}",
+                self.unidentified,
+                tr(Message::CensusUnidentified)
+            ));
+        }
+        if lines.len() == 1 {
+            lines.push(tr(Message::CensusEmpty).to_string());
+        }
+        let text = Text::new(lines.join("\n"));
+        let size = text.measure(ctx)?;
+        let (window_width, window_height) = ctx.gfx.drawable_size();
+        let panel = Rect::new(
+            window_width - size.x - 2.0 * PADDING - MARGIN,
+            window_height - size.y - 2.0 * PADDING - MARGIN,
+            size.x + 2.0 * PADDING,
+            size.y + 2.0 * PADDING,
+        );
+        let backdrop =
+            Mesh::new_rectangle(ctx, DrawMode::fill(), panel, Color::new(0.0, 0.0, 0.0, 0.8))?;
+        canvas.draw(&backdrop, DrawParam::default());
+        canvas.draw(
+            &text,
+            DrawParam::default().dest([panel.x + PADDING, panel.y + PADDING]),
+        );
+        Ok(())
+    }
+}
+
+/// The groups of live cells that touch each other, even diagonally.
+fn objects(grid: &[Vec<bool>]) -> Vec<Vec<(i32, i32)>> {
+    let mut seen: Vec<Vec<bool>> = grid.iter().map(|row| vec![false; row.len()]).collect();
+    let mut objects = Vec::new();
+    for (y, row) in grid.iter().enumerate() {
+        for (x, &alive) in row.iter().enumerate() {
+            if !alive || seen[y][x] {
+                continue;
+            }
+            seen[y][x] = true;
+            let mut object = Vec::new();
+            let mut pending = vec![(x, y)];
+            while let Some((x, y)) = pending.pop() {
+                object.push((x as i32, y as i32));
+                for ny in y.saturating_sub(1)..=(y + 1).min(grid.len() - 1) {
+                    for nx in x.saturating_sub(1)..=(x + 1).min(grid[ny].len() - 1) {
+                        if grid[ny][nx] && !seen[ny][nx] {
+                            seen[ny][nx] = true;
+                            pending.push((nx, ny));
+                        }
+                    }
+                }
+            }
+            objects.push(object);
+        }
+    }
+    objects
+}
+
+/// The apgcode of an object that is still (`xs`), oscillates (`xp`) or moves
+/// (`xq`) when left alone, or `None` if it does neither within the longest
+/// recognized period.
+pub fn apgcode(cells: &[(i32, i32)], rule: Rule) -> Option<String> {
+    if cells.is_empty() || cells.len() > MAX_OBJECT_CELLS {
+        return None;
+    }
+    let start: HashSet<(i32, i32)> = cells.iter().copied().collect();
+    let shape = normalize(start.iter().copied());
+    let mut phases = vec![start.clone()];
+    let mut current = start.clone();
+    for period in 1..=MAX_PERIOD {
+        current = step(&current, rule);
+        if current.is_empty() || current.len() > MAX_OBJECT_CELLS {
+            return None;
+        }
+        let prefix = if current == start {
+            if period == 1 {
+                format!("xs{}", start.len())
+            } else {
+                format!("xp{}", period)
+            }
+        } else if normalize(current.iter().copied()) == shape {
+            format!("xq{}", period)
+        } else {
+            phases.push(current.clone());
+            continue;
+        };
+        // The canonical code is the shortest, then the first in order, of all
+        // phases in all orientations
+        let code = phases
+            .iter()
+            .flat_map(|phase| {
+                (0..8).map(move |orientation| {
+                    wechsler(&normalize(phase.iter().map(|&c| orient(c, orientation))))
+                })
+            })
+            .min_by(|a, b| a.len().cmp(&b.len()).then_with(|| a.cmp(b)))?;
+        return Some(format!("{}_{}", prefix, code));
+    }
+    None
+}
+
+/// Encode cells with their bounding box at the origin in the extended Wechsler format.
+fn wechsler(cells: &[(i32, i32)]) -> String {
+    let width = cells.iter().map(|&(x, _)| x + 1).max().unwrap_or(0) as usize;
+    let height = cells.iter().map(|&(_, y)| y + 1).max().unwrap_or(0) as usize;
+    let mut strips = vec![vec![0u8; width]; height.div_ceil(5)];
+    for &(x, y) in cells {
+        strips[y as usize / 5][x as usize] |= 1 << (y % 5);
+    }
+    let encoded: Vec<String> = strips
+        .iter()
+        .map(|strip| {
+            let end = strip
+                .iter()
+                .rposition(|&column| column != 0)
+                .map_or(0, |i| i + 1);
+            let mut text = String::new();
+            let mut zeros = 0;
+            for &column in &strip[..end] {
+                if column == 0 {
+                    zeros += 1;
+                    continue;
+                }
+                push_zeros(&mut text, zeros);
+                zeros = 0;
+                text.push(char::from(DIGITS[column as usize]));
+            }
+            text
+        })
+        .collect();
+    encoded.join("z")
+}
+
+/// Append a run of empty columns: `0`, `w` for two, `x` for three and `y`
+/// followed by a digit for four to 39.
+fn push_zeros(text: &mut String, mut zeros: usize) {
+    while zeros > 0 {
+        match zeros {
+            1 => text.push('0'),
+            2 => text.push('w'),
+            3 => text.push('x'),
+            _ => {
+                let run = zeros.min(39);
+                text.push('y');
+                text.push(char::from(DIGITS[run - 4]));
+                zeros -= run;
+                continue;
+            }
+        }
+        return;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaCha8Rng;
+
+    use super::wechsler;
+    use crate::catagolue;
+
+    /// Encode cells, decode them back with the Catagolue decoder, and compare.
+    fn round_trip(cells: &[(i32, i32)]) {
+        let code = format!("xs{}_{}", cells.len(), wechsler(cells));
+        let decoded = catagolue::object(&code).unwrap();
+        let mut decoded: Vec<(i32, i32)> = decoded
+            .cells
+            .iter()
+            .map(|&(x, y)| (x as i32, y as i32))
+            .collect();
+        let mut expected = cells.to_vec();
+        decoded.sort_unstable();
+        expected.sort_unstable();
+        assert_eq!(decoded, expected, "{}", code);
+    }
+
+    #[test]
+    fn empty_runs_of_every_length_round_trip() {
+        // Up to 39 columns fit in one `y` run, the longest ones ending in w to z
+        for gap in 1..100 {
+            round_trip(&[(0, 0), (gap + 1, 0), (gap + 1, 7)]);
+        }
+    }
+
+    #[test]
+    fn random_shapes_round_trip() {
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+        for _ in 0..500 {
+            let (width, height) = (rng.gen_range(1..60), rng.gen_range(1..20));
+            let mut cells: Vec<(i32, i32)> = (0..rng.gen_range(1..30))
+                .map(|_| (rng.gen_range(0..width), rng.gen_range(0..height)))
+                .collect();
+            cells.sort_unstable();
+            cells.dedup();
+            // Cells with their bounding box at the origin, as the census encodes them
+            let left = cells.iter().map(|&(x, _)| x).min().unwrap();
+            let top = cells.iter().map(|&(_, y)| y).min().unwrap();
+            for cell in &mut cells {
+                *cell = (cell.0 - left, cell.1 - top);
+            }
+            round_trip(&cells);
+        }
+    }
+}
@@ -0,0 +1,590 @@
+use std::env;
+use std::path::PathBuf;
+use std::process;
+use tracing::Level;
+
+use crate::alarm::Limits;
+use crate::background;
+use crate::engine::EngineKind;
+use crate::export;
+use crate::game::{MAX_GRID_SIZE, MIN_GRID_SIZE, RULE};
+use crate::rule::Rule;
+use crate::tick::TickKind;
+
+// Generations jumped by a super-step unless `--super-step` says otherwise, 2^20.
+const DEFAULT_SUPER_STEP: u64 = 1 << 20;
+// Far enough for any methuselah, and near enough to keep the quadtree small.
+const MAX_SUPER_STEP: u64 = 1_000_000_000_000;
+
+const USAGE: &str = "\
+Usage: krida [OPTIONS]
+       krida batch <DIR> --generations <N> --out <DIR> [--size <WxH>]
+       krida run-script <FILE> [--size <WxH>]
+       krida verify [--bless]
+       krida torus-scan <FILE> --sizes <WxH>[..<WxH>] [--shifts <N>[..<N>]] [--generations <N>]
+       krida soup-search [--soups <N>] [--size <WxH>] [--margin <N>] [--generations <N>]
+                         [--threads <N>] [--seed <N>] [--top <N>] [--rule <RULE>]
+                         [--checkpoint-every <N>] [--resume <FILE>]
+
+Commands:
+  batch  Run every .rle and .cells file in DIR for N generations without a window,
+         writing the final states and summary.csv to the --out directory.
+         The universe is WxH cells, by default the pattern plus a margin of
+         64 dead cells on every side.
+  run-script
+         Run a Lua script using a subset of Golly's API (new, open, save,
+         setcell, getcell, run, getpop, getgen, getrule, setrule, show)
+         on a WxH universe [default: 512x512].
+  verify Run the bundled reference patterns and compare their final states
+         with the golden values; --bless prints a new golden file instead.
+  torus-scan
+         Run a pattern on every torus from the first to the second size, with
+         the top and bottom edges joined at every shift in the range [default: 0],
+         for up to N generations [default: 1000]. Prints a CSV line per torus
+         telling whether the pattern died or settled and whether its period
+         differs from the one on an open plane.
+  soup-search
+         Run N random WxH soups [default: 10000 soups of 16x16] on every CPU
+         core, or on --threads of them, each on an empty board with a margin
+         of dead cells [default: 48] for up to --generations [default: 5000].
+         Shows soups per second and the longest-lived soup on stderr, then
+         prints the --top [default: 10] longest-lived soups as RLE. The same
+         --seed [default: the current time] gives the same soups. With
+         --checkpoint-every N soups, e.g. 1e6, the progress is saved to
+         saves/checkpoints so that --resume <FILE> carries on after a reboot.
+
+Options:
+  --width <N>      Width of the universe in cells, between 8 and 1000
+                   [default: the size of the last session, or 120]
+  --height <N>     Height of the universe in cells, between 8 and 1000
+                   [default: the size of the last session, or 90]
+  --cell-size <PX> Width and height of a cell in pixels
+                   [default: cell_width and cell_height of the config, or 15]
+  --speed <N>      Generations per second
+                   [default: the speed of the last session, or 10]
+  --rule <RULE>    Life-like rule in B/S notation, e.g. B36/S23 for HighLife
+                   or B3/S012345678 for Life without Death [default: B3/S23]
+  --engine <ENGINE>
+                   packed, scalar to step one cell at a time as a reference
+                   for --cross-check, or hashlife to run huge grids and long
+                   runs on an edgeless plane seen through the grid
+                   [default: packed]
+  --cross-check <ENGINE>
+                   Work out every generation with ENGINE too and pause, with
+                   the differing cells in red, when it disagrees with --engine
+  --super-step <N> Generations Shift+N jumps ahead with the hashlife engine,
+                   e.g. 1e6, up to 1e12 [default: 1048576]
+  --tick <SOURCE>  What makes generations happen: clock for every --speed
+                   interval, frames for every frame, udp:<ADDRESS> for every
+                   datagram arriving at ADDRESS, e.g. udp:0.0.0.0:7777, or
+                   manual for only the N key [default: clock]
+  --seed <N>       Seed of the first random soup, shown whenever one is seeded,
+                   to recreate it [default: a random one]
+  --spectate <ADDRESS>
+                   Show the generations another krida streams from its
+                   stream_address, e.g. 192.168.1.20:7878, instead of running
+  --stdin          Start with the RLE or plaintext pattern read from stdin
+  --headless <N>   Run N generations, e.g. 1e6, without opening a window and
+                   print the final generation, population, bounding box and
+                   time taken; the universe is 120x90 unless --width and
+                   --height say otherwise
+  --output <FILE>  Write the --headless report to FILE instead of stdout
+  --rle            Add the final board in RLE to the --headless report
+  --daily          Start with today's soup, the same for everyone on the same UTC day
+  --puzzle         Play the levels in resources/levels instead of the sandbox
+  --tutorial       Start the tutorial even if it has been seen before
+  --workspace <FILE>
+                   Reopen a .krida workspace saved with Shift+S
+  --overlay <FILE> Show an RLE or plaintext pattern translucently over the grid,
+                   without simulating it, to compare against
+  --torus          Wrap the edges around, so that patterns leaving one side
+                   come back on the opposite one
+  --present        Show the grid alone, full screen, with only pause, step and
+                   speed keys working; Shift+Escape quits
+  --screensaver    Animate random soups until any input is received
+  --borderless     Open the window without decorations
+  --always-on-top  Keep the window above all other windows
+  --background <IMAGE>
+                   Draw an image faintly behind the grid
+  --background-opacity <0..1>
+                   Opacity of the background image [default: 0.25]
+  --snapshot-peaks Save the grid to saves/snapshots whenever the population
+                   reaches a new high
+  --snapshot-above <N>
+                   Save the grid to saves/snapshots whenever the population
+                   rises above N
+  --alarm <LIMITS>  Pause with a beep and a flash when the population crosses
+                   a limit, e.g. \">10000 <50\"
+  --log-level <LEVEL>
+                   Log error, warn, info, debug or trace events and above to
+                   stderr and to the daily log file [default: info]
+  -h, --help       Print this help";
+
+/// What to do, as selected by the first command-line argument.
+pub enum Command {
+    /// Open the game window.
+    Play(Options),
+    /// Run a directory of patterns headlessly.
+    Batch(BatchOptions),
+    /// Run a Golly-style Lua script headlessly.
+    Script(ScriptOptions),
+    /// Check the engine against the golden reference states.
+    Verify(VerifyOptions),
+    /// Run a pattern on a range of tori headlessly.
+    TorusScan(TorusScanOptions),
+    /// Search random soups for long-lived ones on all cores.
+    SoupSearch(SoupSearchOptions),
+}
+
+/// Options of the `batch` command.
+pub struct BatchOptions {
+    pub input: PathBuf,
+    pub output: PathBuf,
+    pub generations: u64,
+    /// Size of the universe; defaults to the pattern plus a margin.
+    pub size: Option<(usize, usize)>,
+}
+
+/// Options of the `run-script` command.
+pub struct ScriptOptions {
+    pub path: PathBuf,
+    /// Size of the universe the script runs in.
+    pub size: Option<(usize, usize)>,
+}
+
+/// Options of the `verify` command.
+pub struct VerifyOptions {
+    /// Print the current results as a new golden file instead of comparing.
+    pub bless: bool,
+}
+
+/// Options of the `torus-scan` command.
+pub struct TorusScanOptions {
+    pub path: PathBuf,
+    /// Smallest and largest torus, both included.
+    pub sizes: ((usize, usize), (usize, usize)),
+    /// Smallest and largest shift of the cells across the top and bottom edges.
+    pub shifts: (usize, usize),
+    pub generations: u64,
+}
+
+/// Options of the `soup-search` command.
+#[derive(Clone)]
+pub struct SoupSearchOptions {
+    pub soups: u64,
+    /// Size of each soup.
+    pub size: (usize, usize),
+    /// Dead cells around each soup.
+    pub margin: usize,
+    pub generations: u64,
+    /// Worker threads; defaults to one per core.
+    pub threads: Option<usize>,
+    /// Seed of the first soup; the others follow from it.
+    pub seed: u64,
+    /// How many of the longest-lived soups are printed.
+    pub top: usize,
+    pub rule: String,
+    /// Soups between checkpoints; none are written if unset.
+    pub checkpoint_every: Option<u64>,
+    /// Checkpoint to resume, which also receives the new checkpoints.
+    pub resume: Option<PathBuf>,
+}
+
+/// Parse the process arguments, exiting with a usage message if they are invalid.
+pub fn parse_args() -> Command {
+    let mut args = env::args().skip(1).peekable();
+    match args.peek().map(String::as_str) {
+        Some("batch") => {
+            args.next();
+            Command::Batch(BatchOptions::parse(args))
+        }
+        Some("run-script") => {
+            args.next();
+            Command::Script(ScriptOptions::parse(args))
+        }
+        Some("verify") => {
+            args.next();
+            Command::Verify(VerifyOptions::parse(args))
+        }
+        Some("torus-scan") => {
+            args.next();
+            Command::TorusScan(TorusScanOptions::parse(args))
+        }
+        Some("soup-search") => {
+            args.next();
+            Command::SoupSearch(SoupSearchOptions::parse(args))
+        }
+        _ => Command::Play(Options::parse(args)),
+    }
+}
+
+/// Options given on the command line.
+pub struct Options {
+    /// Size of the universe, overriding the one of the last session.
+    pub width: Option<usize>,
+    pub height: Option<usize>,
+    /// Size of a square cell in pixels, overriding the configuration.
+    pub cell_size: Option<f32>,
+    /// Generations per second, overriding the speed of the last session.
+    pub speed: Option<f64>,
+    /// What makes generations happen while running.
+    pub tick: TickKind,
+    /// Life-like rule to start with instead of Conway's.
+    pub rule: Option<Rule>,
+    /// How the generations are worked out.
+    pub engine: EngineKind,
+    /// Second engine every generation is checked against.
+    pub cross_check: Option<EngineKind>,
+    /// Generations a super-step jumps ahead.
+    pub super_step: u64,
+    /// Seed of the first random soup, to recreate it.
+    pub seed: Option<u64>,
+    /// Address of another krida to show the streamed generations of.
+    pub spectate: Option<String>,
+    /// Start unpaused on a random soup, reseed when it settles and quit on any input.
+    pub screensaver: bool,
+    /// Show only the grid, full screen, and ignore every key but pause, step and speed.
+    pub present: bool,
+    /// Open the window without title bar and borders.
+    pub borderless: bool,
+    /// Read the starting pattern from stdin.
+    pub stdin: bool,
+    /// Run this many generations without a window and report the outcome.
+    pub headless: Option<u64>,
+    /// File the headless report goes to instead of stdout.
+    pub output: Option<PathBuf>,
+    /// Include the final board in the headless report.
+    pub rle: bool,
+    /// Start the tutorial regardless of whether it has been seen.
+    pub tutorial: bool,
+    /// Play the puzzle levels.
+    pub puzzle: bool,
+    /// Start with the daily soup.
+    pub daily: bool,
+    /// Workspace to reopen.
+    pub workspace: Option<PathBuf>,
+    /// Pattern shown over the grid for comparison.
+    pub overlay: Option<PathBuf>,
+    /// Wrap the edges of the universe around.
+    pub torus: bool,
+    /// Ask the window manager to keep the window above all others.
+    pub always_on_top: bool,
+    /// Image drawn behind the grid.
+    pub background: Option<PathBuf>,
+    /// Initial opacity of the background image.
+    pub background_opacity: f32,
+    /// Save the grid whenever the population reaches a new high.
+    pub snapshot_peaks: bool,
+    /// Save the grid whenever the population rises above this.
+    pub snapshot_above: Option<usize>,
+    /// Population limits that pause the simulation with an alarm.
+    pub alarm: Limits,
+    /// Least severe events that are logged.
+    pub log_level: Level,
+}
+
+impl Default for Options {
+    fn default() -> Options {
+        Options {
+            width: None,
+            height: None,
+            cell_size: None,
+            speed: None,
+            tick: TickKind::Clock,
+            rule: None,
+            engine: EngineKind::Packed,
+            cross_check: None,
+            super_step: DEFAULT_SUPER_STEP,
+            seed: None,
+            spectate: None,
+            screensaver: false,
+            present: false,
+            borderless: false,
+            stdin: false,
+            headless: None,
+            output: None,
+            rle: false,
+            tutorial: false,
+            puzzle: false,
+            daily: false,
+            workspace: None,
+            overlay: None,
+            torus: false,
+            always_on_top: false,
+            background: None,
+            background_opacity: background::DEFAULT_OPACITY,
+            snapshot_peaks: false,
+            snapshot_above: None,
+            alarm: Limits::default(),
+            log_level: Level::INFO,
+        }
+    }
+}
+
+impl Options {
+    fn parse(mut args: impl Iterator<Item = String>) -> Options {
+        let mut options = Options::default();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--width" => options.width = Some(parse_grid_size(&value(&mut args, &arg), &arg)),
+                "--height" => options.height = Some(parse_grid_size(&value(&mut args, &arg), &arg)),
+                "--cell-size" => {
+                    let size: f32 = parse(&value(&mut args, &arg), &arg);
+                    if !(size.is_finite() && size >= 1.0) {
+                        fail("`--cell-size` must be at least a pixel");
+                    }
+                    options.cell_size = Some(size);
+                }
+                "--speed" => {
+                    let speed: f64 = parse(&value(&mut args, &arg), &arg);
+                    if !(speed.is_finite() && speed > 0.0) {
+                        fail("`--speed` must be more than 0 generations per second");
+                    }
+                    options.speed = Some(speed);
+                }
+                "--tick" => {
+                    let name = value(&mut args, &arg);
+                    options.tick = name.parse().unwrap_or_else(|e: String| fail(&e));
+                }
+                "--rule" => {
+                    let text = value(&mut args, &arg);
+                    options.rule = Some(Rule::parse(&text).unwrap_or_else(|e| fail(&e)));
+                }
+                "--engine" => {
+                    let name = value(&mut args, &arg);
+                    options.engine = name.parse().unwrap_or_else(|e: String| fail(&e));
+                }
+                "--cross-check" => {
+                    let name = value(&mut args, &arg);
+                    options.cross_check = Some(name.parse().unwrap_or_else(|e: String| fail(&e)));
+                }
+                "--super-step" => {
+                    let generations = parse_count(&value(&mut args, &arg), &arg);
+                    if !(1..=MAX_SUPER_STEP).contains(&generations) {
+                        fail("`--super-step` must be from 1 to 1e12 generations");
+                    }
+                    options.super_step = generations;
+                }
+                "--seed" => options.seed = Some(parse(&value(&mut args, &arg), &arg)),
+                "--spectate" => options.spectate = Some(value(&mut args, &arg)),
+                "--screensaver" => options.screensaver = true,
+                "--present" => options.present = true,
+                "--borderless" => options.borderless = true,
+                "--stdin" => options.stdin = true,
+                "--headless" => options.headless = Some(parse_count(&value(&mut args, &arg), &arg)),
+                "--output" => options.output = Some(value(&mut args, &arg).into()),
+                "--rle" => options.rle = true,
+                "--tutorial" => options.tutorial = true,
+                "--puzzle" => options.puzzle = true,
+                "--daily" => options.daily = true,
+                "--torus" => options.torus = true,
+                "--always-on-top" => options.always_on_top = true,
+                "--background" => options.background = Some(value(&mut args, &arg).into()),
+                "--workspace" => options.workspace = Some(value(&mut args, &arg).into()),
+                "--overlay" => options.overlay = Some(value(&mut args, &arg).into()),
+                "--background-opacity" => {
+                    options.background_opacity = parse(&value(&mut args, &arg), &arg)
+                }
+                "--snapshot-peaks" => options.snapshot_peaks = true,
+                "--snapshot-above" => {
+                    options.snapshot_above = Some(parse(&value(&mut args, &arg), &arg))
+                }
+                "--alarm" => {
+                    options.alarm =
+                        Limits::parse(&value(&mut args, &arg)).unwrap_or_else(|e| fail(&e))
+                }
+                "--log-level" => options.log_level = parse(&value(&mut args, &arg), &arg),
+                "-h" | "--help" => help(),
+                _ => fail(&format!("unknown argument `{}`", arg)),
+            }
+        }
+        if options.headless.is_none() && (options.output.is_some() || options.rle) {
+            fail("`--output` and `--rle` only apply to `--headless`");
+        }
+        options
+    }
+}
+
+impl BatchOptions {
+    fn parse(mut args: impl Iterator<Item = String>) -> BatchOptions {
+        let (mut input, mut output, mut generations, mut size) = (None, None, None, None);
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--generations" => generations = Some(parse(&value(&mut args, &arg), &arg)),
+                "--out" => output = Some(PathBuf::from(value(&mut args, &arg))),
+                "--size" => size = Some(parse_size(&value(&mut args, &arg), &arg)),
+                "-h" | "--help" => help(),
+                _ if arg.starts_with('-') => fail(&format!("unknown argument `{}`", arg)),
+                _ if input.is_none() => input = Some(PathBuf::from(arg)),
+                _ => fail(&format!("unexpected argument `{}`", arg)),
+            }
+        }
+        BatchOptions {
+            input: input.unwrap_or_else(|| fail("batch expects a pattern directory")),
+            output: output.unwrap_or_else(|| fail("batch expects `--out <DIR>`")),
+            generations: generations.unwrap_or_else(|| fail("batch expects `--generations <N>`")),
+            size,
+        }
+    }
+}
+
+impl ScriptOptions {
+    fn parse(mut args: impl Iterator<Item = String>) -> ScriptOptions {
+        let (mut path, mut size) = (None, None);
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--size" => size = Some(parse_size(&value(&mut args, &arg), &arg)),
+                "-h" | "--help" => help(),
+                _ if arg.starts_with('-') => fail(&format!("unknown argument `{}`", arg)),
+                _ if path.is_none() => path = Some(PathBuf::from(arg)),
+                _ => fail(&format!("unexpected argument `{}`", arg)),
+            }
+        }
+        ScriptOptions {
+            path: path.unwrap_or_else(|| fail("run-script expects a script file")),
+            size,
+        }
+    }
+}
+
+impl VerifyOptions {
+    fn parse(args: impl Iterator<Item = String>) -> VerifyOptions {
+        let mut options = VerifyOptions { bless: false };
+        for arg in args {
+            match arg.as_str() {
+                "--bless" => options.bless = true,
+                "-h" | "--help" => help(),
+                _ => fail(&format!("unknown argument `{}`", arg)),
+            }
+        }
+        options
+    }
+}
+
+impl TorusScanOptions {
+    fn parse(mut args: impl Iterator<Item = String>) -> TorusScanOptions {
+        let (mut path, mut sizes, mut shifts, mut generations) = (None, None, (0, 0), 1000);
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--sizes" => {
+                    let value = value(&mut args, &arg);
+                    let (from, to) = value.split_once("..").unwrap_or((&value, &value));
+                    sizes = Some((parse_size(from, &arg), parse_size(to, &arg)));
+                }
+                "--shifts" => {
+                    let value = value(&mut args, &arg);
+                    let (from, to) = value.split_once("..").unwrap_or((&value, &value));
+                    shifts = (parse(from, &arg), parse(to, &arg));
+                }
+                "--generations" => generations = parse(&value(&mut args, &arg), &arg),
+                "-h" | "--help" => help(),
+                _ if arg.starts_with('-') => fail(&format!("unknown argument `{}`", arg)),
+                _ if path.is_none() => path = Some(PathBuf::from(arg)),
+                _ => fail(&format!("unexpected argument `{}`", arg)),
+            }
+        }
+        TorusScanOptions {
+            path: path.unwrap_or_else(|| fail("torus-scan expects a pattern file")),
+            sizes: sizes.unwrap_or_else(|| fail("torus-scan expects `--sizes <WxH>..<WxH>`")),
+            shifts,
+            generations,
+        }
+    }
+}
+
+impl SoupSearchOptions {
+    fn parse(mut args: impl Iterator<Item = String>) -> SoupSearchOptions {
+        let mut options = SoupSearchOptions {
+            soups: 10_000,
+            size: (16, 16),
+            margin: 48,
+            generations: 5000,
+            threads: None,
+            seed: export::unix_time(),
+            top: 10,
+            rule: RULE.to_string(),
+            checkpoint_every: None,
+            resume: None,
+        };
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--soups" => options.soups = parse(&value(&mut args, &arg), &arg),
+                "--size" => options.size = parse_size(&value(&mut args, &arg), &arg),
+                "--margin" => options.margin = parse(&value(&mut args, &arg), &arg),
+                "--generations" => options.generations = parse(&value(&mut args, &arg), &arg),
+                "--threads" => options.threads = Some(parse(&value(&mut args, &arg), &arg)),
+                "--seed" => options.seed = parse(&value(&mut args, &arg), &arg),
+                "--top" => options.top = parse(&value(&mut args, &arg), &arg),
+                "--rule" => options.rule = value(&mut args, &arg),
+                "--checkpoint-every" => {
+                    options.checkpoint_every = Some(parse_count(&value(&mut args, &arg), &arg))
+                }
+                "--resume" => options.resume = Some(value(&mut args, &arg).into()),
+                "-h" | "--help" => help(),
+                _ => fail(&format!("unknown argument `{}`", arg)),
+            }
+        }
+        if options.threads == Some(0) {
+            fail("`--threads` must be at least 1");
+        }
+        if options.checkpoint_every == Some(0) {
+            fail("`--checkpoint-every` must be at least 1");
+        }
+        options
+    }
+}
+
+/// Parse a size such as `200x150`.
+fn parse_size(value: &str, option: &str) -> (usize, usize) {
+    let (width, height) = value
+        .split_once('x')
+        .unwrap_or_else(|| fail(&format!("`{}` expects a size like 200x150", option)));
+    (parse(width, option), parse(height, option))
+}
+
+/// Parse a count, also written like `1e6`.
+fn parse_count(value: &str, option: &str) -> u64 {
+    value.parse().unwrap_or_else(|_| {
+        let count: f64 = parse(value, option);
+        if !(count.is_finite() && count >= 0.0 && count.fract() == 0.0) {
+            fail(&format!("invalid value `{}` for `{}`", value, option));
+        }
+        count as u64
+    })
+}
+
+/// Parse a width or height of the universe, which the game can only resize within limits.
+fn parse_grid_size(value: &str, option: &str) -> usize {
+    let size = parse(value, option);
+    if !(MIN_GRID_SIZE..=MAX_GRID_SIZE).contains(&size) {
+        fail(&format!(
+            "`{}` must be between {} and {} cells",
+            option, MIN_GRID_SIZE, MAX_GRID_SIZE
+        ));
+    }
+    size
+}
+
+fn help() -> ! {
+    println!("{}", USAGE);
+    process::exit(0);
+}
+
+/// Take the value following an option.
+fn value(args: &mut impl Iterator<Item = String>, option: &str) -> String {
+    args.next()
+        .unwrap_or_else(|| fail(&format!("`{}` expects a value", option)))
+}
+
+/// Parse the value of an option.
+fn parse<T: std::str::FromStr>(value: &str, option: &str) -> T {
+    value
+        .parse()
+        .unwrap_or_else(|_| fail(&format!("invalid value `{}` for `{}`", value, option)))
+}
+
+/// Exit after printing an error and the usage message.
+fn fail(message: &str) -> ! {
+    eprintln!("krida: {}\n\n{}", message, USAGE);
+    process::exit(2);
+}
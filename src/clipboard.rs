@@ -0,0 +1,31 @@
+/// The system clipboard, opened on first use.
+///
+/// The handle lives as long as the game because on X11 copied text is only
+/// available while the program that copied it is still running.
+pub struct Clipboard {
+    handle: Option<arboard::Clipboard>,
+}
+
+impl Clipboard {
+    /// Create a clipboard that connects to the system on first use.
+    pub fn new() -> Clipboard {
+        Clipboard { handle: None }
+    }
+
+    /// Read the text currently on the clipboard.
+    pub fn get_text(&mut self) -> Result<String, arboard::Error> {
+        self.handle()?.get_text()
+    }
+
+    /// Put text on the clipboard.
+    pub fn set_text(&mut self, text: String) -> Result<(), arboard::Error> {
+        self.handle()?.set_text(text)
+    }
+
+    fn handle(&mut self) -> Result<&mut arboard::Clipboard, arboard::Error> {
+        if self.handle.is_none() {
+            self.handle = Some(arboard::Clipboard::new()?);
+        }
+        Ok(self.handle.as_mut().unwrap())
+    }
+}
@@ -0,0 +1,118 @@
+use directories::ProjectDirs;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use tracing::{error, warn};
+
+use crate::border::BorderConfig;
+use crate::emitter::EmitterConfig;
+use crate::game::CELL_SIZE;
+use crate::midi::MidiConfig;
+use crate::mouse::MouseAction;
+use crate::render::CellSize;
+use crate::schedule::SpeedSchedule;
+
+/// Settings read from `config.toml` in the platform's configuration directory.
+#[derive(Default, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Language of the interface, e.g. `"de"`; the system locale is used if unset.
+    pub language: Option<String>,
+    /// Width of a cell in pixels; cells are square unless this differs from the height.
+    pub cell_width: Option<f32>,
+    /// Height of a cell in pixels.
+    pub cell_height: Option<f32>,
+    /// Pause while the window is in the background and resume when it comes back.
+    pub pause_on_focus_loss: bool,
+    /// Mouse buttons with modifiers, e.g. `"shift+left"`, and the actions they trigger.
+    pub mouse: HashMap<String, MouseAction>,
+    /// Speeds the screensaver runs each soup at, by generation.
+    pub speed_schedule: SpeedSchedule,
+    /// Frame around the universe and shade beyond it.
+    pub border: BorderConfig,
+    /// Patterns injected at fixed positions and intervals.
+    pub emitters: Vec<EmitterConfig>,
+    /// UDP address, e.g. `"127.0.0.1:57120"`, that receives OSC messages every generation.
+    pub osc_target: Option<String>,
+    /// TCP address, e.g. `"127.0.0.1:9184"`, that serves Prometheus metrics at `/metrics`.
+    pub metrics_address: Option<String>,
+    /// Where to stream the generations to spectators from.
+    pub stream_address: Option<String>,
+    /// Generations between the keyframes of the stream.
+    pub stream_keyframe_interval: Option<u64>,
+    /// MIDI output of the births in some rows and columns.
+    pub midi: Option<MidiConfig>,
+    /// Grid snapshots kept for undoing.
+    pub history_depth: Option<usize>,
+    /// Also undo whole generations, not just edits.
+    pub undo_generations: bool,
+    /// Generations kept to rewind through; 0 keeps none.
+    pub rewind_depth: Option<usize>,
+    /// Generations between the snapshots of the gallery; 0 takes none.
+    pub gallery_interval: Option<u64>,
+    /// Smoothing fuzzy Life starts with, from 0 (the rule as it is) to 1.
+    pub fuzzy_smoothing: Option<f32>,
+    /// Dead cells left around the live ones in exported RLE and PNG files.
+    pub export_margin: Option<usize>,
+    /// Radius of the neighborhood the heat layer sums the live cells of.
+    pub heat_radius: Option<usize>,
+}
+
+impl Config {
+    /// Location of the configuration file, e.g. `~/.config/krida/config.toml` on Linux.
+    pub fn path() -> Option<PathBuf> {
+        project_dirs().map(|dirs| dirs.config_dir().join("config.toml"))
+    }
+
+    /// Size of a cell on screen, falling back to the default for missing values.
+    pub fn cell_size(&self) -> CellSize {
+        CellSize {
+            width: self.cell_width.unwrap_or(CELL_SIZE),
+            height: self.cell_height.unwrap_or(CELL_SIZE),
+        }
+    }
+
+    /// Read the configuration, falling back to the defaults if there is none.
+    pub fn load() -> Config {
+        let Some(path) = Config::path() else {
+            return Config::default();
+        };
+        match fs::read_to_string(&path) {
+            Ok(text) => toml::from_str(&text)
+                .map(Config::validated)
+                .unwrap_or_else(|e| {
+                    warn!("ignoring {}: {}", path.display(), e);
+                    Config::default()
+                }),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Config::default(),
+            Err(e) => {
+                error!("cannot read {}: {}", path.display(), e);
+                Config::default()
+            }
+        }
+    }
+
+    /// Drop values that cannot be used, with a warning, so that the defaults apply.
+    fn validated(mut self) -> Config {
+        for (key, value) in [
+            ("cell_width", &mut self.cell_width),
+            ("cell_height", &mut self.cell_height),
+        ] {
+            if let Some(size) = value.filter(|size| !(size.is_finite() && *size >= 1.0)) {
+                warn!(
+                    "ignoring `{} = {}`, cells must be at least a pixel",
+                    key, size
+                );
+                *value = None;
+            }
+        }
+        self
+    }
+}
+
+/// Platform directories for configuration and data, if the home directory is known.
+pub fn project_dirs() -> Option<ProjectDirs> {
+    ProjectDirs::from("", "nealpro", "krida")
+}
@@ -0,0 +1,79 @@
+use ggez::graphics::{Canvas, Color, DrawMode, DrawParam, Mesh, MeshBuilder};
+use ggez::{Context, GameResult};
+
+use crate::engine::{Engine, EngineKind};
+use crate::render::CellSize;
+use crate::universe::Universe;
+
+// Cells the engines disagree on: filled where the main engine has a live cell,
+// outlined where only the checking engine has one.
+const DIFF_COLOR: Color = Color::new(1.0, 0.1, 0.1, 0.9);
+
+/// A second engine worked through every generation alongside the main one,
+/// on a copy of the universe, to catch the first generation they disagree on.
+pub struct CrossCheck {
+    /// The engine the game runs, and the one checking it.
+    main: EngineKind,
+    checking: EngineKind,
+    engine: Box<dyn Engine>,
+    /// Cells of the last checked generation that differ, with whether the main
+    /// engine has them alive.
+    diff: Vec<(usize, usize, bool)>,
+}
+
+impl CrossCheck {
+    pub fn new(main: EngineKind, checking: EngineKind) -> CrossCheck {
+        CrossCheck {
+            main,
+            checking,
+            engine: checking.create(),
+            diff: Vec::new(),
+        }
+    }
+
+    /// The main and the checking engine.
+    pub fn engines(&self) -> (EngineKind, EngineKind) {
+        (self.main, self.checking)
+    }
+
+    /// Work out the generations from `before` to `after` again with the
+    /// checking engine; returns whether the results differ.
+    pub fn check(&mut self, before: &Universe, after: &Universe, generations: u64) -> bool {
+        let mut copy = before.clone();
+        self.engine.advance(&mut copy, generations);
+        self.diff.clear();
+        for (y, (expected, actual)) in after.cells().iter().zip(copy.cells()).enumerate() {
+            for (x, (&alive, &other)) in expected.iter().zip(actual).enumerate() {
+                if alive != other {
+                    self.diff.push((x, y, alive));
+                }
+            }
+        }
+        !self.diff.is_empty()
+    }
+
+    /// Number of cells the engines disagreed on in the last generation checked.
+    pub fn differences(&self) -> usize {
+        self.diff.len()
+    }
+
+    /// Highlight the cells the engines disagreed on.
+    pub fn draw(&self, ctx: &mut Context, canvas: &mut Canvas, cell: CellSize) -> GameResult {
+        // A mesh without any vertices cannot be built
+        if self.diff.is_empty() {
+            return Ok(());
+        }
+        let mut builder = MeshBuilder::new();
+        for &(x, y, alive) in &self.diff {
+            let rect = cell.rect(x as f32, y as f32, 1.0, 1.0);
+            let mode = if alive {
+                DrawMode::fill()
+            } else {
+                DrawMode::stroke(2.0)
+            };
+            builder.rectangle(mode, rect, DIFF_COLOR)?;
+        }
+        canvas.draw(&Mesh::from_data(ctx, builder.build()), DrawParam::default());
+        Ok(())
+    }
+}
@@ -0,0 +1,54 @@
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use std::fmt;
+
+use crate::export;
+
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// A day of the UTC calendar, so that everyone shares the same day's soup.
+#[derive(Clone, Copy)]
+pub struct Date {
+    year: i64,
+    month: u32,
+    day: u32,
+}
+
+impl Date {
+    /// The current UTC date.
+    pub fn today() -> Date {
+        Date::from_days((export::unix_time() / SECONDS_PER_DAY) as i64)
+    }
+
+    /// Convert days since 1970-01-01 to a date of the proleptic Gregorian calendar.
+    fn from_days(days: i64) -> Date {
+        // Howard Hinnant's civil_from_days, with eras of 400 years starting on March 1st
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let day_of_era = z.rem_euclid(146_097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+        let month = if shifted_month < 10 {
+            shifted_month + 3
+        } else {
+            shifted_month - 9
+        } as u32;
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+        Date { year, month, day }
+    }
+
+    /// A random number generator that produces the same numbers on every machine for this date.
+    pub fn rng(&self) -> ChaCha8Rng {
+        let seed = self.year as u64 * 10_000 + self.month as u64 * 100 + self.day as u64;
+        ChaCha8Rng::seed_from_u64(seed)
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
@@ -0,0 +1,43 @@
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+use std::time::Duration;
+
+// Give up on servers that do not answer within this time.
+const TIMEOUT: Duration = Duration::from_secs(20);
+
+/// A text file being fetched over HTTP(S) on a background thread.
+pub struct Download {
+    pub url: String,
+    receiver: Receiver<Result<String, String>>,
+}
+
+impl Download {
+    /// Start fetching `url` without blocking the caller.
+    pub fn start(url: &str) -> Download {
+        let (sender, receiver) = mpsc::channel();
+        let target = url.to_string();
+        thread::spawn(move || {
+            let agent = ureq::AgentBuilder::new().timeout(TIMEOUT).build();
+            let result = agent
+                .get(&target)
+                .call()
+                .map_err(|e| e.to_string())
+                .and_then(|response| response.into_string().map_err(|e| e.to_string()));
+            // The receiver may be gone if another download replaced this one
+            let _ = sender.send(result);
+        });
+        Download {
+            url: url.to_string(),
+            receiver,
+        }
+    }
+
+    /// Return the body once the download has finished.
+    pub fn poll(&self) -> Option<Result<String, String>> {
+        match self.receiver.try_recv() {
+            Ok(result) => Some(result),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => Some(Err("download thread stopped".to_string())),
+        }
+    }
+}
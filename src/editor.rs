@@ -0,0 +1,167 @@
+use ggez::graphics::{Canvas, Color, DrawMode, DrawParam, Mesh, MeshBuilder, Text};
+use ggez::{Context, GameResult};
+
+use crate::locale::{tr, Message};
+use crate::pattern::Pattern;
+use crate::render::QuadBatch;
+use crate::universe::Universe;
+
+/// Size of the editing grid in cells; small, so that every cell is easy to hit.
+pub const EDITOR_WIDTH: usize = 48;
+pub const EDITOR_HEIGHT: usize = 36;
+const GRID_COLOR: Color = Color::new(0.25, 0.25, 0.25, 1.0);
+const AXIS_COLOR: Color = Color::new(0.9, 0.3, 0.3, 1.0);
+const CELL_COLOR: Color = Color::new(0.9, 0.9, 0.6, 1.0);
+
+/// Copies made of every edit.
+#[derive(Clone, Copy)]
+pub enum Symmetry {
+    None,
+    /// Mirror across the vertical axis.
+    LeftRight,
+    /// Mirror across the horizontal axis.
+    TopBottom,
+    /// Mirror across both axes.
+    FourWay,
+    /// Rotate by half a turn around the center.
+    Rotational,
+}
+
+/// A small, always paused grid for building components before trying them out.
+pub struct Editor {
+    universe: Universe,
+    symmetry: Symmetry,
+}
+
+impl Editor {
+    /// Create an empty editor without symmetry.
+    pub fn new() -> Editor {
+        Editor {
+            universe: Universe::new(EDITOR_WIDTH, EDITOR_HEIGHT),
+            symmetry: Symmetry::None,
+        }
+    }
+
+    /// Switch to the next kind of symmetry.
+    pub fn cycle_symmetry(&mut self) {
+        self.symmetry = match self.symmetry {
+            Symmetry::None => Symmetry::LeftRight,
+            Symmetry::LeftRight => Symmetry::TopBottom,
+            Symmetry::TopBottom => Symmetry::FourWay,
+            Symmetry::FourWay => Symmetry::Rotational,
+            Symmetry::Rotational => Symmetry::None,
+        };
+    }
+
+    /// Kill every cell.
+    pub fn clear(&mut self) {
+        self.universe.clear();
+    }
+
+    /// The live cells, cropped to their bounding box.
+    pub fn pattern(&self) -> Pattern {
+        self.universe.to_pattern()
+    }
+
+    /// Toggle the cell under a window position together with its symmetric copies.
+    pub fn toggle_at(&mut self, ctx: &Context, x: f32, y: f32) {
+        let size = cell_size(ctx);
+        let (x, y) = ((x / size) as usize, (y / size) as usize);
+        if x >= EDITOR_WIDTH || y >= EDITOR_HEIGHT {
+            return;
+        }
+        let alive = !self.universe.cells()[y][x];
+        let (mirror_x, mirror_y) = (EDITOR_WIDTH - 1 - x, EDITOR_HEIGHT - 1 - y);
+        let copies: &[(usize, usize)] = match self.symmetry {
+            Symmetry::None => &[],
+            Symmetry::LeftRight => &[(mirror_x, y)],
+            Symmetry::TopBottom => &[(x, mirror_y)],
+            Symmetry::FourWay => &[(mirror_x, y), (x, mirror_y), (mirror_x, mirror_y)],
+            Symmetry::Rotational => &[(mirror_x, mirror_y)],
+        };
+        self.universe.set(x, y, alive);
+        for &(x, y) in copies {
+            self.universe.set(x, y, alive);
+        }
+    }
+
+    /// Draw the grid lines, the cells, the symmetry axes and a reminder of the keys.
+    pub fn draw(&self, ctx: &mut Context, canvas: &mut Canvas) -> GameResult {
+        let size = cell_size(ctx);
+        let (width, height) = (EDITOR_WIDTH as f32 * size, EDITOR_HEIGHT as f32 * size);
+
+        let mut cells = QuadBatch::new();
+        for (y, row) in self.universe.cells().iter().enumerate() {
+            for (x, _) in row.iter().enumerate().filter(|(_, &alive)| alive) {
+                let (left, top) = (x as f32 * size, y as f32 * size);
+                let (right, bottom) = (left + size, top + size);
+                cells.push(
+                    [[left, top], [right, top], [right, bottom], [left, bottom]],
+                    CELL_COLOR,
+                );
+            }
+        }
+        cells.draw(ctx, canvas);
+
+        let mut lines = MeshBuilder::new();
+        for x in 0..=EDITOR_WIDTH {
+            let x = x as f32 * size;
+            lines.line(&[[x, 0.0], [x, height]], 1.0, GRID_COLOR)?;
+        }
+        for y in 0..=EDITOR_HEIGHT {
+            let y = y as f32 * size;
+            lines.line(&[[0.0, y], [width, y]], 1.0, GRID_COLOR)?;
+        }
+        let (center_x, center_y) = (width / 2.0, height / 2.0);
+        match self.symmetry {
+            Symmetry::None => (),
+            Symmetry::LeftRight => {
+                lines.line(&[[center_x, 0.0], [center_x, height]], 2.0, AXIS_COLOR)?;
+            }
+            Symmetry::TopBottom => {
+                lines.line(&[[0.0, center_y], [width, center_y]], 2.0, AXIS_COLOR)?;
+            }
+            Symmetry::FourWay => {
+                lines.line(&[[center_x, 0.0], [center_x, height]], 2.0, AXIS_COLOR)?;
+                lines.line(&[[0.0, center_y], [width, center_y]], 2.0, AXIS_COLOR)?;
+            }
+            Symmetry::Rotational => {
+                lines.circle(
+                    DrawMode::stroke(2.0),
+                    [center_x, center_y],
+                    size / 2.0,
+                    0.5,
+                    AXIS_COLOR,
+                )?;
+            }
+        }
+        canvas.draw(&Mesh::from_data(ctx, lines.build()), DrawParam::default());
+
+        let symmetry = match self.symmetry {
+            Symmetry::None => Message::SymmetryNone,
+            Symmetry::LeftRight => Message::SymmetryLeftRight,
+            Symmetry::TopBottom => Message::SymmetryTopBottom,
+            Symmetry::FourWay => Message::SymmetryFourWay,
+            Symmetry::Rotational => Message::SymmetryRotational,
+        };
+        let hint = Text::new(format!(
+            "{} ({})\n{}",
+            tr(Message::EditorTitle),
+            tr(symmetry),
+            tr(Message::EditorKeys)
+        ));
+        canvas.draw(
+            &hint,
+            DrawParam::default()
+                .dest([8.0, height + 8.0])
+                .color(Color::WHITE),
+        );
+        Ok(())
+    }
+}
+
+/// Side of a cell in pixels; the grid fills the window but leaves room for the hint below it.
+fn cell_size(ctx: &Context) -> f32 {
+    let (window_width, window_height) = ctx.gfx.drawable_size();
+    (window_width / EDITOR_WIDTH as f32).min((window_height - 60.0) / EDITOR_HEIGHT as f32)
+}
@@ -0,0 +1,49 @@
+use std::panic;
+use std::sync::Mutex;
+use tracing::error;
+
+use crate::save::{self, State};
+use crate::session::Session;
+
+// The latest state of the game, written out if it panics.
+static SNAPSHOT: Mutex<Option<(State, Session)>> = Mutex::new(None);
+
+/// Save the last recorded grid and settings when the game panics, after the
+/// panic itself has been reported.
+pub fn install_panic_hook() {
+    let report = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        report(info);
+        dump();
+    }));
+}
+
+/// Replace what is saved on a panic.
+pub fn record(state: State, session: Session) {
+    if let Ok(mut snapshot) = SNAPSHOT.lock() {
+        *snapshot = Some((state, session));
+    }
+}
+
+fn dump() {
+    // The panic may have struck while a snapshot was being recorded
+    let Ok(snapshot) = SNAPSHOT.try_lock() else {
+        return;
+    };
+    let Some((state, session)) = snapshot.as_ref() else {
+        return;
+    };
+    let path = save::emergency_path();
+    match save::write_state(&path, &state.grid, &state.labels) {
+        Ok(()) => eprintln!(
+            "\nkrida crashed, sorry! Your grid was saved to {}.\n\
+             To get it back, copy it to {} and press L.",
+            path.display(),
+            save::quicksave_path().display()
+        ),
+        Err(e) => error!("cannot save the grid to {}: {}", path.display(), e),
+    }
+    if let Err(e) = session.save() {
+        error!("cannot save the session: {}", e);
+    }
+}
@@ -0,0 +1,111 @@
+use ggez::graphics::{Canvas, Color, DrawMode, DrawParam, Mesh, MeshBuilder};
+use ggez::{Context, GameResult};
+use serde::Deserialize;
+use tracing::warn;
+
+use crate::pattern::Pattern;
+use crate::render::CellSize;
+use crate::universe::Universe;
+
+const OUTLINE_COLOR: Color = Color::new(1.0, 0.4, 0.8, 0.6);
+
+/// An emitter as written in the `[[emitters]]` tables of the configuration.
+#[derive(Deserialize)]
+pub struct EmitterConfig {
+    /// RLE or plaintext of the pattern to inject.
+    pub pattern: String,
+    /// Cell the top left corner of the pattern goes to.
+    pub x: usize,
+    pub y: usize,
+    /// Generations between injections.
+    pub every: u64,
+    /// Generation of the first injection.
+    #[serde(default)]
+    pub from: u64,
+}
+
+/// A pattern injected at a fixed position and interval, a stand-in for a gun.
+struct Emitter {
+    pattern: Pattern,
+    left: usize,
+    top: usize,
+    every: u64,
+    from: u64,
+}
+
+/// The emitters of the configuration that could be read.
+pub struct Emitters {
+    emitters: Vec<Emitter>,
+    pub enabled: bool,
+}
+
+impl Emitters {
+    /// Parse the patterns of the configured emitters, leaving out the ones that fail.
+    pub fn new(configs: &[EmitterConfig]) -> Emitters {
+        let emitters = configs
+            .iter()
+            .enumerate()
+            .filter_map(|(i, config)| {
+                if config.every == 0 {
+                    warn!("ignoring emitter {}, `every` must be at least 1", i + 1);
+                    return None;
+                }
+                match Pattern::parse(&config.pattern) {
+                    Ok(pattern) => Some(Emitter {
+                        pattern,
+                        left: config.x,
+                        top: config.y,
+                        every: config.every,
+                        from: config.from,
+                    }),
+                    Err(e) => {
+                        warn!("ignoring emitter {}: {}", i + 1, e);
+                        None
+                    }
+                }
+            })
+            .collect();
+        Emitters {
+            emitters,
+            enabled: true,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.emitters.is_empty()
+    }
+
+    /// Bring the patterns of the emitters that are due at the current generation to life.
+    pub fn inject(&self, universe: &mut Universe) {
+        if !self.enabled {
+            return;
+        }
+        let generation = universe.generation();
+        for emitter in &self.emitters {
+            if generation >= emitter.from
+                && (generation - emitter.from).is_multiple_of(emitter.every)
+            {
+                universe.place(&emitter.pattern, emitter.left, emitter.top);
+            }
+        }
+    }
+
+    /// Outline where each emitter injects its pattern.
+    pub fn draw(&self, ctx: &mut Context, canvas: &mut Canvas, cell: CellSize) -> GameResult {
+        let mut builder = MeshBuilder::new();
+        for emitter in &self.emitters {
+            builder.rectangle(
+                DrawMode::stroke(1.0),
+                cell.rect(
+                    emitter.left as f32,
+                    emitter.top as f32,
+                    emitter.pattern.width as f32,
+                    emitter.pattern.height as f32,
+                ),
+                OUTLINE_COLOR,
+            )?;
+        }
+        canvas.draw(&Mesh::from_data(ctx, builder.build()), DrawParam::default());
+        Ok(())
+    }
+}
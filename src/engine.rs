@@ -0,0 +1,88 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::hashlife::HashLife;
+use crate::universe::Universe;
+
+/// A way of working out the generations of a universe.
+pub trait Engine {
+    /// Advance the universe by `generations`.
+    fn advance(&mut self, universe: &mut Universe, generations: u64);
+
+    /// Whether the engine can jump far ahead in this universe without working
+    /// through every generation on the way.
+    fn skips_ahead(&self, universe: &Universe) -> bool;
+}
+
+/// The engines to choose from at startup.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum EngineKind {
+    Packed,
+    Scalar,
+    HashLife,
+}
+
+impl FromStr for EngineKind {
+    type Err = String;
+
+    /// Parse `packed` (or `naive`, its old name), `scalar` or `hashlife`.
+    fn from_str(name: &str) -> Result<EngineKind, String> {
+        match name {
+            "packed" | "naive" => Ok(EngineKind::Packed),
+            "scalar" => Ok(EngineKind::Scalar),
+            "hashlife" => Ok(EngineKind::HashLife),
+            _ => Err(format!("unknown engine `{}`", name)),
+        }
+    }
+}
+
+impl fmt::Display for EngineKind {
+    /// Write the name `--engine` takes.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            EngineKind::Packed => "packed",
+            EngineKind::Scalar => "scalar",
+            EngineKind::HashLife => "hashlife",
+        })
+    }
+}
+
+impl EngineKind {
+    pub fn create(self) -> Box<dyn Engine> {
+        match self {
+            EngineKind::Packed => Box::new(Packed),
+            EngineKind::Scalar => Box::new(Scalar),
+            EngineKind::HashLife => Box::new(HashLife::new()),
+        }
+    }
+}
+
+/// Updates every cell of every generation in turn, 64 at a time, the only
+/// engine that supports every kind of universe.
+pub struct Packed;
+
+impl Engine for Packed {
+    fn advance(&mut self, universe: &mut Universe, generations: u64) {
+        universe.advance(generations);
+    }
+
+    fn skips_ahead(&self, _universe: &Universe) -> bool {
+        false
+    }
+}
+
+/// Updates one cell at a time, counting its neighbors one by one: far slower
+/// than `Packed`, and kept as the reference to cross-check it against.
+pub struct Scalar;
+
+impl Engine for Scalar {
+    fn advance(&mut self, universe: &mut Universe, generations: u64) {
+        for _ in 0..generations {
+            universe.step_scalar();
+        }
+    }
+
+    fn skips_ahead(&self, _universe: &Universe) -> bool {
+        false
+    }
+}
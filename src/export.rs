@@ -0,0 +1,300 @@
+use gif::{Encoder, Frame, Repeat};
+use serde::Serialize;
+use std::borrow::Cow;
+use std::fmt::Write as _;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::pattern::Pattern;
+
+/// Directory that receives exported patterns.
+pub const EXPORT_DIR: &str = "exports";
+/// Dead cells around the live ones in exported RLE and PNG files when the
+/// configuration does not say otherwise.
+pub const DEFAULT_MARGIN: usize = 1;
+// Size of a cell in exported animations, in pixels.
+const GIF_CELL_SIZE: usize = 4;
+// Dead cells around the live ones in exported animations.
+const GIF_MARGIN: usize = 2;
+// Palette of exported animations: dead cells black, live ones white.
+const GIF_PALETTE: [u8; 6] = [0, 0, 0, 255, 255, 255];
+// Size of a cell in exported pictures, in pixels.
+const PNG_CELL_SIZE: usize = 4;
+const DEAD_PIXEL: [u8; 4] = [0, 0, 0, 255];
+const ALIVE_PIXEL: [u8; 4] = [255, 255, 255, 255];
+// Coordinate pairs per line of exported source code.
+const SOURCE_CELLS_PER_LINE: usize = 8;
+
+/// Languages patterns can be exported to as source code.
+#[derive(Clone, Copy)]
+pub enum SourceLanguage {
+    Rust,
+    C,
+}
+
+impl SourceLanguage {
+    pub fn extension(self) -> &'static str {
+        match self {
+            SourceLanguage::Rust => "rs",
+            SourceLanguage::C => "h",
+        }
+    }
+}
+
+/// Machine-readable snapshot of the universe for external scripts and viewers.
+#[derive(Serialize)]
+struct JsonState<'a> {
+    format: &'static str,
+    version: u32,
+    rule: &'a str,
+    width: usize,
+    height: usize,
+    generation: u64,
+    population: usize,
+    /// Seconds since the Unix epoch.
+    exported_at: u64,
+    /// Coordinates `[x, y]` of every live cell, row by row.
+    cells: Vec<[usize; 2]>,
+}
+
+/// Create a fresh file name in the export directory, e.g. `exports/krida_1700000000.json`.
+pub fn export_path(extension: &str) -> PathBuf {
+    Path::new(EXPORT_DIR).join(format!("krida_{}.{}", unix_time(), extension))
+}
+
+/// Write the grid, the rule and some metadata as JSON.
+pub fn write_json(path: &Path, grid: &[Vec<bool>], rule: &str, generation: u64) -> io::Result<()> {
+    let cells: Vec<[usize; 2]> = grid
+        .iter()
+        .enumerate()
+        .flat_map(|(y, row)| {
+            row.iter()
+                .enumerate()
+                .filter(|(_, &alive)| alive)
+                .map(move |(x, _)| [x, y])
+        })
+        .collect();
+    let state = JsonState {
+        format: "krida",
+        version: 1,
+        rule,
+        width: grid.first().map_or(0, Vec::len),
+        height: grid.len(),
+        generation,
+        population: cells.len(),
+        exported_at: unix_time(),
+        cells,
+    };
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut out = BufWriter::new(File::create(path)?);
+    serde_json::to_writer_pretty(&mut out, &state)?;
+    writeln!(out)?;
+    out.flush()
+}
+
+/// Write generations as a looping GIF animation, cropped to the cells that are
+/// alive in any of them, showing each for `delay` hundredths of a second.
+pub fn write_gif(path: &Path, frames: &[Vec<Vec<bool>>], delay: u16) -> io::Result<()> {
+    let (left, top, right, bottom) = live_bounds(frames.iter().map(Vec::as_slice))
+        .ok_or_else(|| io::Error::other("there are no live cells"))?;
+    let columns = right - left + 1 + 2 * GIF_MARGIN;
+    let rows = bottom - top + 1 + 2 * GIF_MARGIN;
+    let (width, height) = (columns * GIF_CELL_SIZE, rows * GIF_CELL_SIZE);
+    let too_large = || io::Error::other("the pattern is too large for a GIF");
+    let gif_width = u16::try_from(width).map_err(|_| too_large())?;
+    let gif_height = u16::try_from(height).map_err(|_| too_large())?;
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let file = BufWriter::new(File::create(path)?);
+    let mut encoder =
+        Encoder::new(file, gif_width, gif_height, &GIF_PALETTE).map_err(io::Error::other)?;
+    encoder
+        .set_repeat(Repeat::Infinite)
+        .map_err(io::Error::other)?;
+    for grid in frames {
+        let mut pixels = vec![0u8; width * height];
+        for (py, line) in pixels.chunks_mut(width).enumerate() {
+            // Cells outside the grid are part of the margin
+            let y = (py / GIF_CELL_SIZE + top).checked_sub(GIF_MARGIN);
+            let Some(row) = y.and_then(|y| grid.get(y)) else {
+                continue;
+            };
+            for (px, pixel) in line.iter_mut().enumerate() {
+                let x = (px / GIF_CELL_SIZE + left).checked_sub(GIF_MARGIN);
+                *pixel = u8::from(x.and_then(|x| row.get(x)).copied().unwrap_or(false));
+            }
+        }
+        let frame = Frame {
+            width: gif_width,
+            height: gif_height,
+            delay,
+            buffer: Cow::Owned(pixels),
+            ..Frame::default()
+        };
+        encoder.write_frame(&frame).map_err(io::Error::other)?;
+    }
+    Ok(())
+}
+
+/// The bounding box `(left, top, right, bottom)` of the cells alive in any of the grids.
+pub fn live_bounds<'a>(
+    grids: impl Iterator<Item = &'a [Vec<bool>]>,
+) -> Option<(usize, usize, usize, usize)> {
+    let (mut left, mut top, mut right, mut bottom) = (usize::MAX, usize::MAX, 0, 0);
+    for (y, row) in grids.flat_map(|grid| grid.iter().enumerate()) {
+        for (x, _) in row.iter().enumerate().filter(|(_, &alive)| alive) {
+            (left, top) = (left.min(x), top.min(y));
+            (right, bottom) = (right.max(x), bottom.max(y));
+        }
+    }
+    (left <= right).then_some((left, top, right, bottom))
+}
+
+/// The cells inside a bounding box with `margin` dead cells around it, those
+/// past the edges of the grid dead too.
+pub fn crop(
+    grid: &[Vec<bool>],
+    (left, top, right, bottom): (usize, usize, usize, usize),
+    margin: usize,
+) -> Vec<Vec<bool>> {
+    let cell = |x: usize, y: usize| {
+        let (x, y) = (x.checked_sub(margin), y.checked_sub(margin));
+        x.zip(y)
+            .and_then(|(x, y)| grid.get(y)?.get(x).copied())
+            .unwrap_or(false)
+    };
+    (top..=bottom + 2 * margin)
+        .map(|y| (left..=right + 2 * margin).map(|x| cell(x, y)).collect())
+        .collect()
+}
+
+/// Write the live cells as an RLE file, with `margin` dead cells around them
+/// counted in the size of the header.
+pub fn write_rle(path: &Path, pattern: &Pattern, rule: &str, margin: usize) -> io::Result<()> {
+    let mut padded = pattern.clone();
+    padded.width += 2 * margin;
+    padded.height += 2 * margin;
+    for cell in &mut padded.cells {
+        *cell = (cell.0 + margin, cell.1 + margin);
+    }
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, padded.to_rle(rule))
+}
+
+/// Write the live cells as a PNG picture, white on black, cropped to them with
+/// `margin` dead cells around them.
+pub fn write_png(path: &Path, grid: &[Vec<bool>], margin: usize) -> io::Result<()> {
+    let bounds = live_bounds(std::iter::once(grid))
+        .ok_or_else(|| io::Error::other("there are no live cells"))?;
+    let cells = crop(grid, bounds, margin);
+    let (columns, rows) = (cells.first().map_or(0, Vec::len), cells.len());
+    let too_large = || io::Error::other("the pattern is too large for a picture");
+    let width = u32::try_from(columns * PNG_CELL_SIZE).map_err(|_| too_large())?;
+    let height = u32::try_from(rows * PNG_CELL_SIZE).map_err(|_| too_large())?;
+    let pixels = image::RgbaImage::from_fn(width, height, |px, py| {
+        let alive = cells[py as usize / PNG_CELL_SIZE][px as usize / PNG_CELL_SIZE];
+        image::Rgba(if alive { ALIVE_PIXEL } else { DEAD_PIXEL })
+    });
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    pixels.save(path).map_err(io::Error::other)
+}
+
+/// Render the cells inside a bounding box as ASCII art, `O` for alive and `.` for dead.
+pub fn ascii_art(
+    grid: &[Vec<bool>],
+    (left, top, right, bottom): (usize, usize, usize, usize),
+) -> String {
+    let mut art = String::new();
+    for row in &grid[top..=bottom] {
+        art.extend(
+            row[left..=right]
+                .iter()
+                .map(|&alive| if alive { 'O' } else { '.' }),
+        );
+        art.push('\n');
+    }
+    art
+}
+
+/// Write the live cells inside a bounding box as a Rust slice or a C array of
+/// `(x, y)` coordinates relative to its top left corner, to embed the pattern
+/// in other programs.
+pub fn write_source(
+    path: &Path,
+    grid: &[Vec<bool>],
+    (left, top, right, bottom): (usize, usize, usize, usize),
+    rule: &str,
+    language: SourceLanguage,
+) -> io::Result<()> {
+    let cells: Vec<(usize, usize)> = grid[top..=bottom]
+        .iter()
+        .enumerate()
+        .flat_map(|(y, row)| {
+            row[left..=right]
+                .iter()
+                .enumerate()
+                .filter(|(_, &alive)| alive)
+                .map(move |(x, _)| (x, y))
+        })
+        .collect();
+    let (width, height) = (right - left + 1, bottom - top + 1);
+    let summary = format!(
+        "Exported from krida: {} live cells in {}x{}, rule {}.",
+        cells.len(),
+        width,
+        height,
+        rule
+    );
+
+    let mut source = String::new();
+    let pair = |&(x, y): &(usize, usize)| match language {
+        SourceLanguage::Rust => format!("({}, {})", x, y),
+        SourceLanguage::C => format!("{{{}, {}}}", x, y),
+    };
+    match language {
+        SourceLanguage::Rust => {
+            let _ = writeln!(source, "// {}", summary);
+            let _ = writeln!(source, "pub const PATTERN_WIDTH: usize = {};", width);
+            let _ = writeln!(source, "pub const PATTERN_HEIGHT: usize = {};", height);
+            let _ = writeln!(source, "pub const PATTERN: &[(usize, usize)] = &[");
+        }
+        SourceLanguage::C => {
+            let _ = writeln!(source, "/* {} */", summary);
+            let _ = writeln!(source, "#define PATTERN_WIDTH {}", width);
+            let _ = writeln!(source, "#define PATTERN_HEIGHT {}", height);
+            let _ = writeln!(source, "#define PATTERN_CELLS {}", cells.len());
+            let _ = writeln!(
+                source,
+                "static const unsigned int pattern[PATTERN_CELLS][2] = {{"
+            );
+        }
+    }
+    for line in cells.chunks(SOURCE_CELLS_PER_LINE) {
+        let pairs: Vec<String> = line.iter().map(pair).collect();
+        let _ = writeln!(source, "    {},", pairs.join(", "));
+    }
+    source.push_str("};\n");
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, source)
+}
+
+/// Seconds since the Unix epoch.
+pub fn unix_time() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
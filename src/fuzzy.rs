@@ -0,0 +1,118 @@
+use crate::universe::Universe;
+
+/// Smoothing the mode starts with, halfway between Life and a continuous automaton.
+pub const DEFAULT_SMOOTHING: f32 = 0.5;
+// How far a key press moves the smoothing.
+const SMOOTHING_STEP: f32 = 0.05;
+// States at or above this count as alive, for everything that only knows dead and alive.
+const ALIVE_THRESHOLD: f32 = 0.5;
+
+/// Life with cells that are anywhere between dead (0) and alive (1). A cell's
+/// next state thresholds the sum of its neighbors' states against the rule's
+/// counts, with every count widened into a ramp by the smoothing: at 0 this is
+/// exactly the rule, at 1 every sum between two counts blends their outcomes.
+pub struct FuzzyLife {
+    states: Vec<Vec<f32>>,
+    next: Vec<Vec<f32>>,
+    smoothing: f32,
+    pub enabled: bool,
+}
+
+impl FuzzyLife {
+    pub fn new(smoothing: f32) -> FuzzyLife {
+        FuzzyLife {
+            states: Vec::new(),
+            next: Vec::new(),
+            smoothing: smoothing.clamp(0.0, 1.0),
+            enabled: false,
+        }
+    }
+
+    /// Forget the in-between states, so that the next generation starts from
+    /// the dead and live cells of the grid.
+    pub fn clear(&mut self) {
+        self.states.clear();
+        self.next.clear();
+    }
+
+    /// The state of every cell, rows first.
+    pub fn states(&self) -> &[Vec<f32>] {
+        &self.states
+    }
+
+    pub fn smoothing(&self) -> f32 {
+        self.smoothing
+    }
+
+    pub fn increase_smoothing(&mut self) {
+        self.smoothing = (self.smoothing + SMOOTHING_STEP).min(1.0);
+    }
+
+    pub fn decrease_smoothing(&mut self) {
+        self.smoothing = (self.smoothing - SMOOTHING_STEP).max(0.0);
+    }
+
+    /// Take over the cells that were drawn, cleared or loaded since the last
+    /// generation, keeping the in-between states of the others.
+    pub fn sync(&mut self, cells: &[Vec<bool>]) {
+        let same_size = self.states.len() == cells.len()
+            && self.states.first().map(Vec::len) == cells.first().map(Vec::len);
+        if !same_size {
+            self.states = cells
+                .iter()
+                .map(|row| row.iter().map(|&alive| alive as u8 as f32).collect())
+                .collect();
+            self.next = self.states.clone();
+            return;
+        }
+        for (states, row) in self.states.iter_mut().zip(cells) {
+            for (state, &alive) in states.iter_mut().zip(row) {
+                if (*state >= ALIVE_THRESHOLD) != alive {
+                    *state = alive as u8 as f32;
+                }
+            }
+        }
+    }
+
+    /// Work out the next generation under the universe's rule and edges, and
+    /// return which of its cells count as alive.
+    pub fn step(&mut self, universe: &Universe) -> Vec<Vec<bool>> {
+        self.sync(universe.cells());
+        let (rule, smoothing, states) = (universe.rule(), self.smoothing, &self.states);
+        for (y, row) in self.next.iter_mut().enumerate() {
+            for (x, next) in row.iter_mut().enumerate() {
+                let sum: f32 = (-1..=1)
+                    .flat_map(|dy| (-1..=1).map(move |dx| (dx, dy)))
+                    .filter(|&offset| offset != (0, 0))
+                    .filter_map(|(dx, dy)| universe.offset(x, y, dx, dy))
+                    .map(|(nx, ny)| states[ny][nx])
+                    .sum();
+                let state = states[y][x];
+                let born = membership(sum, smoothing, |count| rule.born(count));
+                let survives = membership(sum, smoothing, |count| rule.survives(count));
+                *next = state * survives + (1.0 - state) * born;
+            }
+        }
+        std::mem::swap(&mut self.states, &mut self.next);
+        self.states
+            .iter()
+            .map(|row| row.iter().map(|&state| state >= ALIVE_THRESHOLD).collect())
+            .collect()
+    }
+}
+
+/// How much a neighbor sum matches the counts the rule accepts: 1 near one
+/// of them, falling off over the width of the smoothing.
+fn membership(sum: f32, smoothing: f32, accepts: impl Fn(usize) -> bool) -> f32 {
+    (0..=8)
+        .filter(|&count| accepts(count))
+        .map(|count| {
+            let distance = (sum - count as f32).abs();
+            if smoothing == 0.0 {
+                (distance < 0.5) as u8 as f32
+            } else {
+                ((0.5 + smoothing / 2.0 - distance) / smoothing).clamp(0.0, 1.0)
+            }
+        })
+        .fold(0.0, f32::max)
+}
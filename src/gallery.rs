@@ -0,0 +1,235 @@
+use ggez::graphics::{
+    Canvas, Color, DrawMode, DrawParam, Image, ImageFormat, Mesh, Rect, Sampler, Text,
+};
+use ggez::{Context, GameResult};
+use std::collections::VecDeque;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::bitgrid::BitGrid;
+use crate::export;
+use crate::history::Snapshot;
+use crate::locale::{tr, Message};
+
+/// Generations between snapshots when the configuration does not say otherwise.
+pub const DEFAULT_INTERVAL: u64 = 100;
+// Snapshots kept, the oldest going first; as many as fit across a small window.
+const CAPACITY: usize = 8;
+// Largest side of a thumbnail in pixels.
+const THUMBNAIL_SIZE: f32 = 96.0;
+const PADDING: f32 = 8.0;
+const MARGIN: f32 = 8.0;
+// Largest side of each picture of the exported strip, in pixels, and the gap between them.
+const STRIP_SIZE: usize = 256;
+const STRIP_GAP: usize = 4;
+const DEAD_PIXEL: [u8; 4] = [0, 0, 0, 255];
+const ALIVE_PIXEL: [u8; 4] = [255, 255, 255, 255];
+const GAP_PIXEL: [u8; 4] = [64, 64, 64, 255];
+
+/// A snapshot with the picture the panel shows of it.
+struct Thumbnail {
+    snapshot: Snapshot,
+    /// Made on the first draw, once there is a context to make it with.
+    image: Option<Image>,
+}
+
+/// Snapshots of the grid taken every few generations, shown as a row of
+/// thumbnails along the bottom of the window.
+pub struct Gallery {
+    interval: u64,
+    /// Oldest first.
+    thumbnails: VecDeque<Thumbnail>,
+    pub visible: bool,
+}
+
+impl Gallery {
+    /// Keep a snapshot every `interval` generations; 0 keeps none.
+    pub fn new(interval: u64) -> Gallery {
+        Gallery {
+            interval,
+            thumbnails: VecDeque::new(),
+            visible: false,
+        }
+    }
+
+    /// Take a snapshot if the generation is due for one.
+    pub fn observe(&mut self, cells: &BitGrid, generation: u64) {
+        if self.interval == 0 || generation == 0 || !generation.is_multiple_of(self.interval) {
+            return;
+        }
+        if self.thumbnails.len() == CAPACITY {
+            self.thumbnails.pop_front();
+        }
+        self.thumbnails.push_back(Thumbnail {
+            snapshot: Snapshot {
+                cells: cells.to_rows(),
+                generation,
+            },
+            image: None,
+        });
+    }
+
+    /// Forget the snapshots, which no longer fit a resized universe.
+    pub fn clear(&mut self) {
+        self.thumbnails.clear();
+    }
+
+    /// A copy of the snapshot whose thumbnail is at `(x, y)` in the window.
+    pub fn snapshot_at(&self, ctx: &Context, x: f32, y: f32) -> Option<Snapshot> {
+        let (_, slots) = self.layout(ctx);
+        let index = slots.iter().position(|slot| slot.contains([x, y]))?;
+        let snapshot = &self.thumbnails[index].snapshot;
+        Some(Snapshot {
+            cells: snapshot.cells.clone(),
+            generation: snapshot.generation,
+        })
+    }
+
+    /// The panel along the bottom of the window and a slot for each thumbnail in it.
+    fn layout(&self, ctx: &Context) -> (Rect, Vec<Rect>) {
+        let (window_width, window_height) = ctx.gfx.drawable_size();
+        let count = self.thumbnails.len().max(1) as f32;
+        let width = count * THUMBNAIL_SIZE + (count + 1.0) * PADDING;
+        // Leave room for the generation below each thumbnail
+        let height = THUMBNAIL_SIZE + 2.0 * PADDING + 20.0;
+        let panel = Rect::new(
+            (window_width - width) / 2.0,
+            window_height - height - MARGIN,
+            width,
+            height,
+        );
+        let slots = (0..self.thumbnails.len())
+            .map(|i| {
+                Rect::new(
+                    panel.x + PADDING + i as f32 * (THUMBNAIL_SIZE + PADDING),
+                    panel.y + PADDING,
+                    THUMBNAIL_SIZE,
+                    THUMBNAIL_SIZE,
+                )
+            })
+            .collect();
+        (panel, slots)
+    }
+
+    pub fn draw(&mut self, ctx: &mut Context, canvas: &mut Canvas) -> GameResult {
+        let (panel, slots) = self.layout(ctx);
+        let backdrop =
+            Mesh::new_rectangle(ctx, DrawMode::fill(), panel, Color::new(0.0, 0.0, 0.0, 0.8))?;
+        canvas.draw(&backdrop, DrawParam::default());
+        if self.thumbnails.is_empty() {
+            let text = Text::new(tr(Message::GalleryEmpty));
+            canvas.draw(
+                &text,
+                DrawParam::default().dest([panel.x + PADDING, panel.y + PADDING]),
+            );
+            return Ok(());
+        }
+        // Keep the cells crisp instead of blurring them when scaled up
+        canvas.set_sampler(Sampler::nearest_clamp());
+        for (thumbnail, slot) in self.thumbnails.iter_mut().zip(slots) {
+            let cells = &thumbnail.snapshot.cells;
+            let image = thumbnail.image.get_or_insert_with(|| picture(ctx, cells));
+            let scale = (slot.w / image.width() as f32).min(slot.h / image.height() as f32);
+            let outline = Mesh::new_rectangle(
+                ctx,
+                DrawMode::stroke(1.0),
+                Rect::new(
+                    slot.x,
+                    slot.y,
+                    image.width() as f32 * scale,
+                    image.height() as f32 * scale,
+                ),
+                Color::new(0.5, 0.5, 0.5, 1.0),
+            )?;
+            canvas.draw(
+                &*image,
+                DrawParam::default()
+                    .dest([slot.x, slot.y])
+                    .scale([scale, scale]),
+            );
+            canvas.draw(&outline, DrawParam::default());
+            let label = Text::new(format!("{}", thumbnail.snapshot.generation));
+            canvas.draw(
+                &label,
+                DrawParam::default().dest([slot.x, slot.y + slot.h + 4.0]),
+            );
+        }
+        canvas.set_default_sampler();
+        Ok(())
+    }
+
+    /// Write the snapshots side by side as a PNG, oldest on the left, each cropped
+    /// to the cells alive in any of them with `margin` dead cells around those.
+    pub fn write_strip(&self, path: &Path, margin: usize) -> io::Result<()> {
+        let Some(first) = self.thumbnails.front() else {
+            return Err(io::Error::other("the gallery is empty"));
+        };
+        let grids = self.thumbnails.iter().map(|t| t.snapshot.cells.as_slice());
+        let (bounds, margin) = match export::live_bounds(grids) {
+            Some(bounds) => (bounds, margin),
+            // Nothing is alive, so there is nothing to crop to
+            None => {
+                let cells = &first.snapshot.cells;
+                let right = cells.first().map_or(0, Vec::len).saturating_sub(1);
+                ((0, 0, right, cells.len().saturating_sub(1)), 0)
+            }
+        };
+        let pictures: Vec<Vec<Vec<bool>>> = self
+            .thumbnails
+            .iter()
+            .map(|thumbnail| export::crop(&thumbnail.snapshot.cells, bounds, margin))
+            .collect();
+        let rows = pictures[0].len();
+        let columns = pictures[0].first().map_or(0, Vec::len);
+        let scale = (STRIP_SIZE / rows.max(columns).max(1)).max(1);
+        let (picture_width, picture_height) = (columns * scale, rows * scale);
+        let count = self.thumbnails.len();
+        let width = count * picture_width + (count - 1) * STRIP_GAP;
+        let mut pixels = image::RgbaImage::from_pixel(
+            width as u32,
+            picture_height as u32,
+            image::Rgba(GAP_PIXEL),
+        );
+        for (i, picture) in pictures.iter().enumerate() {
+            let left = i * (picture_width + STRIP_GAP);
+            for (py, x, alive) in cells_scaled(picture, scale) {
+                let color = if alive { ALIVE_PIXEL } else { DEAD_PIXEL };
+                pixels.put_pixel((left + x) as u32, py as u32, image::Rgba(color));
+            }
+        }
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        pixels.save(path).map_err(io::Error::other)
+    }
+}
+
+/// Every pixel `(y, x, alive)` of the cells drawn `scale` pixels to a side.
+fn cells_scaled(
+    cells: &[Vec<bool>],
+    scale: usize,
+) -> impl Iterator<Item = (usize, usize, bool)> + '_ {
+    cells.iter().enumerate().flat_map(move |(y, row)| {
+        (0..scale * row.len())
+            .flat_map(move |x| (0..scale).map(move |dy| (y * scale + dy, x, row[x / scale])))
+    })
+}
+
+/// A picture of the cells, a pixel to a cell.
+fn picture(ctx: &Context, cells: &[Vec<bool>]) -> Image {
+    let height = cells.len();
+    let width = cells.first().map_or(0, Vec::len);
+    let pixels: Vec<u8> = cells
+        .iter()
+        .flatten()
+        .flat_map(|&alive| if alive { ALIVE_PIXEL } else { DEAD_PIXEL })
+        .collect();
+    Image::from_pixels(
+        ctx,
+        &pixels,
+        ImageFormat::Rgba8UnormSrgb,
+        width as u32,
+        height as u32,
+    )
+}
@@ -1,206 +1,2911 @@
 use ggez::event::EventHandler;
 use ggez::glam::*;
-use ggez::graphics::{self, Canvas, Color, Mesh, Rect};
-use ggez::timer;
+use ggez::graphics::{self, Canvas, Color, DrawMode, InstanceArray, Mesh, MeshBuilder};
+use ggez::input::keyboard::KeyCode;
+use ggez::input::mouse::MouseButton;
 use ggez::{Context, GameResult};
-use rand::random;
-use std::time::Duration;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant};
+use tracing::{debug, error, info, warn};
+
+use crate::achievements::{self, Achievements};
+use crate::alarm::{Alarm, Crossing, Limits};
+use crate::annotations::Annotations;
+use crate::background::Background;
+use crate::bitgrid::BitGrid;
+use crate::border::Border;
+use crate::brush::Brush;
+use crate::camera::Camera;
+use crate::catagolue;
+use crate::census::Census;
+use crate::cli::Options;
+use crate::clipboard::Clipboard;
+use crate::config::Config;
+use crate::crosscheck::CrossCheck;
+use crate::daily::Date;
+use crate::delta;
+use crate::download::Download;
+use crate::editor::Editor;
+use crate::emergency;
+use crate::emitter::Emitters;
+use crate::engine::Engine;
+use crate::export::{self, SourceLanguage};
+use crate::fuzzy::{self, FuzzyLife};
+use crate::gallery::{self, Gallery};
+use crate::graph::PopulationGraph;
+use crate::heat::{self, HeatLayer};
+use crate::history::{self, History, Snapshot};
+use crate::hud::{self, Hud};
+use crate::isometric;
+use crate::library::Library;
+use crate::lifespan::{self, LifespanStats};
+use crate::locale::{tr, tr_with, Message};
+use crate::measure::Measure;
+use crate::metrics::MetricsServer;
+use crate::midi::MidiSender;
+use crate::mouse::{self, MouseAction, MouseBindings};
+use crate::multistate::{self, MultiState, AUTOMATA};
+use crate::osc::OscSender;
+use crate::overlay::Overlay;
+use crate::panel::{self, Command, Panel};
+use crate::pattern::Pattern;
+use crate::perf::PerfOverlay;
+use crate::period::PeriodDetector;
+use crate::placements::Placements;
+use crate::prompt::{Outcome, Prompt};
+use crate::puzzle::{Puzzle, Status};
+use crate::recording::Recorder;
+use crate::render::CellSize;
+use crate::rewind::{self, Rewind};
+use crate::rule::Rule;
+use crate::rulebook::{self, Demo};
+use crate::save::{self, State};
+use crate::schedule::SpeedSchedule;
+use crate::seeding::{self, Distribution, SoupSymmetry, DISTRIBUTIONS};
+use crate::session::Session;
+use crate::spaceships::{Ship, ShipDetector};
+use crate::spacetime::SpaceTime;
+use crate::stamps::{self, STAMPS};
+use crate::stream::{Spectator, StreamServer};
+use crate::theme::Theme;
+use crate::tick::{self, Clock, TickSource};
+use crate::toast::Toasts;
+use crate::trails::BirthTrails;
+use crate::tutorial::{Action, Tutorial};
+use crate::universe::{Anchor, Region, Universe};
+use crate::watcher::{self, PopulationWatcher};
+use crate::workspace::{self, Rows, Workspace};
 
 // Define the size of the grid.
 pub const GRID_WIDTH: usize = 120; // Alternatively 80
 pub const GRID_HEIGHT: usize = 90; // Alternatively 60
+/// Default width and height of a cell in pixels.
 pub const CELL_SIZE: f32 = 15.0; // Alternatively 10.0
-const DEFAULT_UPDATE_DELAY_MILISECONDS: u64 = 100;
+
+/// The rule of Conway's Game of Life, which the headless tools run, in B/S notation.
+pub const RULE: &str = "B3/S23";
+pub const DEFAULT_UPDATE_DELAY_MILISECONDS: u64 = 100;
 const DEFAULT_UPDATE_DELAY: Duration = Duration::from_millis(DEFAULT_UPDATE_DELAY_MILISECONDS);
+/// Percent by which the speed keys lengthen the update delay, and by which
+/// they shorten it back, with its limits and step.
+pub const DEFAULT_SPEED_STEP_PERCENT: u32 = 25;
+pub const MIN_SPEED_STEP_PERCENT: u32 = 5;
+pub const MAX_SPEED_STEP_PERCENT: u32 = 100;
+const SPEED_STEP_CHANGE_PERCENT: u32 = 5;
+// Distance in pixels the mouse has to travel before it wakes up the screensaver.
+const SCREENSAVER_MOUSE_THRESHOLD: f32 = 10.0;
+// Limits and step of runtime resizing, in cells.
+pub const MIN_GRID_SIZE: usize = 8;
+pub const MAX_GRID_SIZE: usize = 1000;
+const RESIZE_STEP: usize = 10;
+// Loaded with `G`, and by the tutorial.
+const GLIDER_GUN: &str = include_str!("../resources/patterns/gosper_gun.rle");
+// Ghosts of the cells across a wrapped edge: how far they reach in, in cells, and their color.
+const WRAP_GHOST_DEPTH: f32 = 0.3;
+const WRAP_GHOST_COLOR: Color = Color::new(1.0, 1.0, 1.0, 0.35);
+const WRAP_SEAM_COLOR: Color = Color::new(0.4, 0.6, 1.0, 0.5);
+// Fill and outline of frozen regions.
+const FROZEN_COLOR: Color = Color::new(0.3, 0.6, 1.0, 0.2);
+const FROZEN_OUTLINE_COLOR: Color = Color::new(0.5, 0.8, 1.0, 0.8);
+// States of fuzzy Life too faint to be worth drawing.
+const FUZZY_VISIBLE: f32 = 0.02;
+// How often the grid is copied aside to be saved if the game crashes.
+const SNAPSHOT_INTERVAL: Duration = Duration::from_secs(1);
+// Generations Ctrl+Shift+B goes back at once.
+const REWIND_JUMP: usize = 10;
+// Most generations run in one frame, and the longest they may take together,
+// after which the simulation falls behind rather than holding up the frame.
+const MAX_STEPS_PER_FRAME: u32 = 64;
+const STEP_BUDGET: Duration = Duration::from_millis(12);
+// Zoom factor of one notch of the mouse wheel, and how far the view moves per arrow key.
+const ZOOM_STEP: f32 = 1.25;
+const PAN_STEP: f32 = 60.0;
+// Share of the screen the window may take up, leaving room for its frame and the taskbar.
+const SCREEN_SHARE: f32 = 0.9;
+// Name first offered when saving or opening a workspace.
+const DEFAULT_WORKSPACE_NAME: &str = "workspace";
+
+/// How the grid is rendered.
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ViewMode {
+    /// Plain top-down view of the current generation.
+    Flat,
+    /// Recent generations extruded along a time axis.
+    SpaceTime,
+    /// Live cells drawn as raised tiles in an isometric projection.
+    Isometric,
+}
+
+/// What the text typed into the prompt is used for.
+#[derive(Clone, Copy)]
+enum PromptPurpose {
+    /// The label of a cell.
+    Label(usize, usize),
+    /// The size of the universe.
+    Resize,
+    /// The name to save the workspace under.
+    SaveWorkspace,
+    /// The name of the workspace to reopen.
+    OpenWorkspace,
+    /// A rule in B/S notation to switch to.
+    Rule,
+    /// An apgcode to load from Catagolue, optionally prefixed with `soup`.
+    Catagolue,
+    /// Population limits for the alarm, such as `>10000 <50`.
+    Alarm,
+    /// How many copies of the held stamp to scatter.
+    Scatter,
+}
 
 /// Struct representing the game state.
 pub struct MainState {
-    grid: Vec<Vec<bool>>,
-    next_grid: Vec<Vec<bool>>,
+    universe: Universe,
+    /// Size of a cell on screen.
+    cell: CellSize,
+    /// Size of a cell asked for in the configuration, used whenever the window fits the screen.
+    preferred_cell: CellSize,
+    /// Room left on either side of the grid and above and below it, in window
+    /// pixels, when the window is not the shape of the grid.
+    margin: (f32, f32),
     paused: bool,
     update_delay: Duration,
-    change_update_delay: Duration,
+    /// The tick source picked with `--tick`, the wall clock, the frames, UDP
+    /// packets or manual steps, which decides when the next generation is due
+    /// while running.
+    tick: Box<dyn TickSource>,
+    speed_step_percent: u32,
+    view: ViewMode,
+    spacetime: SpaceTime,
+    screensaver: bool,
+    screensaver_mouse_travel: f32,
+    /// Show nothing but the grid and accept only the pause, step and speed keys.
+    presenting: bool,
+    /// Speeds by generation, overriding the update delay once the first stage starts.
+    schedule: SpeedSchedule,
+    period: PeriodDetector,
+    /// Period of the newest generation, if it repeats a recent one.
+    current_period: Option<usize>,
+    background: Option<Background>,
+    download: Option<Download>,
+    /// Seeds of Catagolue soups being fetched, with the apgcode they were asked for.
+    catagolue: Option<(String, Download)>,
+    clipboard: Clipboard,
+    emitters: Emitters,
+    history: History,
+    /// The last few hundred generations, to scrub back through.
+    rewind: Rewind,
+    /// Whether every generation can be undone, not just edits.
+    undo_generations: bool,
+    engine: Box<dyn Engine>,
+    /// Engine every generation is worked out with again, to debug the main one.
+    cross_check: Option<CrossCheck>,
+    /// Generations jumped ahead by Shift+N.
+    super_step: u64,
+    osc: Option<OscSender>,
+    midi: Option<MidiSender>,
+    metrics: Option<MetricsServer>,
+    /// Spectators the generations are streamed to.
+    stream: Option<StreamServer>,
+    /// The stream the generations come from instead of the engine.
+    spectator: Option<Spectator>,
+    border: Border,
+    census: Census,
+    /// Snapshots taken every few generations to go back to.
+    gallery: Gallery,
+    graph: PopulationGraph,
+    heat: HeatLayer,
+    /// Dead cells left around the live ones in exported RLE and PNG files.
+    export_margin: usize,
+    /// Generations captured for an animated GIF.
+    recorder: Recorder,
+    /// Live cells of the flat view, refilled every frame.
+    cell_instances: InstanceArray,
+    perf: PerfOverlay,
+    hud: Hud,
+    lifespans: LifespanStats,
+    show_lifespans: bool,
+    /// Whether cells are colored by how long they have been alive.
+    color_by_age: bool,
+    /// Cells between dead and alive, stepped instead of the engine while enabled.
+    fuzzy: FuzzyLife,
+    /// The multi-state automaton running instead of the rule, if any.
+    multistate: MultiState,
+    ships: ShipDetector,
+    tutorial: Option<Tutorial>,
+    puzzle: Option<Puzzle>,
+    achievements: Achievements,
+    /// Date of the daily soup on the grid, if that is what is on it.
+    daily: Option<Date>,
+    /// Where the seeds of the random soups come from, itself seeded by `--seed`.
+    seeds: ChaCha8Rng,
+    /// Seed of the next random soup; the first one is the `--seed` itself.
+    next_seed: u64,
+    /// Seed of the last random soup, to recreate it with `--seed`.
+    soup_seed: Option<u64>,
+    editor: Editor,
+    /// Whether the pattern editor is shown instead of the sandbox.
+    editing: bool,
+    annotations: Annotations,
+    /// Text being typed, and what it is for.
+    prompt: Option<(Prompt, PromptPurpose)>,
+    /// The measuring tool, while clicks pick cells instead of toggling them.
+    measure: Option<Measure>,
+    /// Pause when the window loses focus.
+    pause_on_focus_loss: bool,
+    mouse: MouseBindings,
+    // Line or region being dragged out: what it is for, the cell it starts at and the button
+    dragging: Option<(MouseAction, (usize, usize), MouseButton)>,
+    /// Rectangle selected to copy or cut.
+    selection: Option<Region>,
+    /// Cells copied or cut from a selection, to paste.
+    copied: Option<Pattern>,
+    // Cells being painted while a button is held: the state painted, the last cell painted and the button
+    painting: Option<(bool, (usize, usize), MouseButton)>,
+    /// What painting brings to life or kills around the cursor.
+    brush: Brush,
+    camera: Camera,
+    /// Whether the camera keeps the live cells in the middle of the window.
+    following: bool,
+    /// Button held down to pan the view.
+    panning: Option<MouseButton>,
+    /// Whether losing focus paused the simulation, so that regaining it resumes.
+    paused_by_focus_loss: bool,
+    toasts: Toasts,
+    /// Whether the menu of seeding distributions is open.
+    seeding_menu: bool,
+    /// Symmetry of the soups seeded from the menu.
+    seeding_symmetry: SoupSymmetry,
+    /// Whether the menu of built-in rules is open.
+    rulebook_menu: bool,
+    /// Whether the menu of stamps is open.
+    stamp_menu: bool,
+    /// The stamp placed with a click, as currently turned.
+    stamp: Option<Pattern>,
+    // Built-in rule last switched to, described while the demo waits to be run
+    rule_notes: Option<usize>,
+    /// The rule that was active before a pattern switched to its own, until reverted.
+    replaced_rule: Option<Rule>,
+    /// Name the workspace was last saved or opened under, offered again next time.
+    workspace_name: String,
+    /// Patterns placed this session, for editing them as a whole.
+    placements: Placements,
+    trails: BirthTrails,
+    library: Library,
+    watcher: PopulationWatcher,
+    /// Population limits that pause the simulation when crossed.
+    alarm: Alarm,
+    overlay: Option<Overlay>,
+    /// Buttons and sliders for what the shortcuts do, shown with F1.
+    panel: Panel,
+    theme: Theme,
+    /// When the state was last recorded for the panic hook.
+    snapshot_at: Instant,
 }
 
-impl MainState {
-    /// Create a new game state.
-    pub fn new(_ctx: &mut Context) -> GameResult<MainState> {
-        let mut s = MainState {
-            grid: vec![vec![false; GRID_WIDTH]; GRID_HEIGHT],
-            next_grid: vec![vec![false; GRID_WIDTH]; GRID_HEIGHT],
-            paused: true, // Start in paused mode to allow pattern setup
-            update_delay: DEFAULT_UPDATE_DELAY,
-            change_update_delay: DEFAULT_UPDATE_DELAY,
+impl MainState {
+    /// Create a new game state, starting from `pattern` if one is given.
+    pub fn new(
+        ctx: &mut Context,
+        options: &Options,
+        config: &Config,
+        session: &Session,
+        pattern: Option<Pattern>,
+    ) -> GameResult<MainState> {
+        let seed = options.seed.unwrap_or_else(rand::random);
+        let mut s = MainState {
+            universe: Universe::new(session.grid_width, session.grid_height),
+            cell: config.cell_size(),
+            preferred_cell: config.cell_size(),
+            margin: (0.0, 0.0),
+            paused: true, // Start in paused mode to allow pattern setup
+            update_delay: tick::delay_from_ms(session.update_delay_ms),
+            tick: options.tick.create().unwrap_or_else(|e| {
+                error!(
+                    "cannot take ticks from {}, using the clock: {}",
+                    options.tick, e
+                );
+                Box::new(Clock::default())
+            }),
+            speed_step_percent: session.speed_step_percent,
+            view: session.view,
+            spacetime: SpaceTime::new(),
+            screensaver: options.screensaver,
+            screensaver_mouse_travel: 0.0,
+            presenting: options.present,
+            schedule: if options.screensaver {
+                config.speed_schedule.clone()
+            } else {
+                SpeedSchedule::default()
+            },
+            period: PeriodDetector::new(),
+            current_period: None,
+            background: match &options.background {
+                Some(path) => Some(Background::load(ctx, path, options.background_opacity)?),
+                None => None,
+            },
+            download: None,
+            catagolue: None,
+            clipboard: Clipboard::new(),
+            emitters: Emitters::new(&config.emitters),
+            history: History::new(config.history_depth.unwrap_or(history::DEFAULT_DEPTH)),
+            rewind: Rewind::new(config.rewind_depth.unwrap_or(rewind::DEFAULT_DEPTH)),
+            undo_generations: config.undo_generations,
+            engine: options.engine.create(),
+            cross_check: options
+                .cross_check
+                .map(|checking| CrossCheck::new(options.engine, checking)),
+            super_step: options.super_step,
+            osc: config.osc_target.as_deref().and_then(|target| {
+                OscSender::new(target)
+                    .inspect_err(|e| error!("cannot send OSC messages to {}: {}", target, e))
+                    .ok()
+            }),
+            midi: config.midi.as_ref().and_then(|midi| {
+                MidiSender::new(midi)
+                    .inspect_err(|e| error!("cannot send MIDI: {}", e))
+                    .ok()
+            }),
+            border: Border::new(&config.border),
+            census: Census::new(),
+            recorder: Recorder::new(),
+            gallery: Gallery::new(config.gallery_interval.unwrap_or(gallery::DEFAULT_INTERVAL)),
+            graph: PopulationGraph::new(),
+            heat: HeatLayer::new(config.heat_radius.unwrap_or(heat::DEFAULT_RADIUS)),
+            export_margin: config.export_margin.unwrap_or(export::DEFAULT_MARGIN),
+            cell_instances: InstanceArray::new(ctx, None),
+            metrics: config.metrics_address.as_deref().and_then(|address| {
+                MetricsServer::start(address)
+                    .inspect_err(|e| error!("cannot serve metrics at {}: {}", address, e))
+                    .ok()
+            }),
+            stream: config.stream_address.as_deref().and_then(|address| {
+                let interval = config
+                    .stream_keyframe_interval
+                    .unwrap_or(delta::DEFAULT_KEYFRAME_INTERVAL);
+                StreamServer::start(address, interval)
+                    .inspect_err(|e| error!("cannot stream at {}: {}", address, e))
+                    .ok()
+            }),
+            spectator: options.spectate.as_deref().and_then(|address| {
+                Spectator::connect(address)
+                    .inspect_err(|e| error!("cannot spectate {}: {}", address, e))
+                    .ok()
+            }),
+            perf: PerfOverlay::new(),
+            hud: Hud::new(),
+            lifespans: LifespanStats::new(session.grid_width, session.grid_height),
+            show_lifespans: false,
+            color_by_age: false,
+            fuzzy: FuzzyLife::new(config.fuzzy_smoothing.unwrap_or(fuzzy::DEFAULT_SMOOTHING)),
+            multistate: MultiState::new(),
+            ships: ShipDetector::new(),
+            tutorial: None,
+            puzzle: None,
+            achievements: Achievements::load(),
+            daily: None,
+            seeds: ChaCha8Rng::seed_from_u64(seed),
+            next_seed: seed,
+            soup_seed: None,
+            editor: Editor::new(),
+            editing: false,
+            annotations: Annotations::new(),
+            prompt: None,
+            measure: None,
+            pause_on_focus_loss: config.pause_on_focus_loss,
+            mouse: MouseBindings::new(&config.mouse),
+            dragging: None,
+            selection: None,
+            copied: None,
+            painting: None,
+            brush: Brush::SINGLE,
+            camera: Camera::default(),
+            following: false,
+            panning: None,
+            paused_by_focus_loss: false,
+            toasts: Toasts::new(),
+            seeding_menu: false,
+            seeding_symmetry: SoupSymmetry::C1,
+            rulebook_menu: false,
+            stamp_menu: false,
+            stamp: None,
+            rule_notes: None,
+            replaced_rule: None,
+            workspace_name: DEFAULT_WORKSPACE_NAME.to_string(),
+            placements: Placements::new(),
+            trails: BirthTrails::new(),
+            library: Library::new(),
+            watcher: PopulationWatcher::new(options.snapshot_peaks, options.snapshot_above),
+            alarm: Alarm::new(options.alarm),
+            overlay: None,
+            panel: Panel::new(ctx),
+            theme: session.theme,
+            snapshot_at: Instant::now(),
+        };
+
+        s.universe.set_toroidal(options.torus);
+        if let Some(rule) = options.rule {
+            s.universe.set_rule(rule);
+        }
+        if s.screensaver {
+            // Run a random soup straight away and keep the cursor out of the picture
+            ggez::input::mouse::set_cursor_hidden(ctx, true);
+            s.paused = false;
+            s.randomize();
+        } else if options.daily {
+            s.daily_soup();
+        } else if let Some(pattern) = pattern {
+            s.load_pattern(&pattern);
+        } else {
+            // Initialize the grid with a simple pattern (e.g., a glider)
+            s.universe.set(2, 1, true);
+            s.universe.set(3, 2, true);
+            s.universe.set(1, 3, true);
+            s.universe.set(2, 3, true);
+            s.universe.set(3, 3, true);
+        }
+        if let Some(path) = &options.workspace {
+            s.open_workspace(ctx, path)?;
+        }
+        if let Some(path) = &options.overlay {
+            match fs::read_to_string(path)
+                .map_err(|e| e.to_string())
+                .and_then(|text| Pattern::parse(&text))
+            {
+                Ok(pattern) => s.set_overlay(&pattern),
+                Err(e) => s.report_error(tr_with(Message::ErrorCannotRead, &[&path.display(), &e])),
+            }
+        }
+        s.fit_window(ctx)?;
+        if options.puzzle {
+            let mut puzzle = Puzzle::load(ctx)?;
+            puzzle.restart(&mut s.universe);
+            s.puzzle = Some(puzzle);
+            s.reset_history();
+        } else if !s.screensaver && !s.presenting && (options.tutorial || Tutorial::is_first_run())
+        {
+            s.tutorial = Some(Tutorial::new());
+        }
+
+        Ok(s)
+    }
+
+    /// Advance the universe by one generation and record it for the statistics.
+    fn update_grid(&mut self) {
+        if self.undo_generations {
+            self.remember();
+        }
+        // Keep the outgoing generation for the space-time view
+        self.spacetime.push(self.universe.grid());
+        // Fuzzy Life and the multi-state automata are no rules the checking engine could follow
+        let checked =
+            self.cross_check.is_some() && !self.fuzzy.enabled && !self.multistate.is_enabled();
+        let before = checked.then(|| self.universe.clone());
+        if self.multistate.is_enabled() {
+            let cells = self.multistate.step(&self.universe);
+            self.universe.jump(cells, 1);
+        } else if self.fuzzy.enabled {
+            let cells = self.fuzzy.step(&self.universe);
+            self.universe.jump(cells, 1);
+        } else {
+            self.engine.advance(&mut self.universe, 1);
+        }
+        if let Some(before) = before {
+            self.cross_check_generation(&before);
+        }
+        self.emitters.inject(&mut self.universe);
+        if let Some(osc) = &mut self.osc {
+            osc.send_generation(
+                self.universe.previous(),
+                self.universe.cells(),
+                self.universe.generation(),
+            );
+        }
+        if let Some(midi) = &mut self.midi {
+            midi.send_generation(self.universe.previous(), self.universe.cells());
+        }
+        if let Some(stream) = &mut self.stream {
+            stream.send_generation(self.universe.grid(), self.universe.generation());
+        }
+        self.lifespans
+            .observe(self.universe.previous_grid(), self.universe.grid());
+        if self.trails.enabled {
+            self.trails
+                .observe(self.universe.previous(), self.universe.cells());
+        }
+        let settled = self.current_period.is_some();
+        self.current_period = self.period.observe(self.universe.grid());
+        self.gallery
+            .observe(self.universe.grid(), self.universe.generation());
+        self.graph
+            .record(self.universe.generation(), self.universe.population());
+        self.rewind
+            .record(self.universe.grid(), self.universe.generation());
+        if !self.recorder.capture(self.universe.grid()) {
+            self.toasts.show(tr(Message::ToastRecordingFull));
+            self.toggle_recording();
+        }
+        if self.census.visible && !settled && self.current_period.is_some() {
+            self.take_census();
+        }
+        if !self.screensaver {
+            self.check_achievements();
+        }
+        if !self.alarm.limits.is_empty() {
+            if let Some(crossing) = self.alarm.observe(self.universe.population()) {
+                self.sound_alarm(crossing);
+            }
+        }
+        if self.watcher.is_enabled() {
+            if let Some(trigger) = self.watcher.observe(self.universe.population()) {
+                self.save_snapshot(trigger);
+            }
+        }
+    }
+
+    /// Go back some generations, paused there to look at them
+    fn rewind_generations(&mut self, steps: usize) {
+        let Some((cells, generation)) = self.rewind.rewind(steps) else {
+            self.toasts.show(tr(Message::ToastNothingToRewind));
+            return;
+        };
+        if (cells.len(), cells.first().map_or(0, Vec::len))
+            != (self.universe.height(), self.universe.width())
+        {
+            // The grid was resized since, so the generations kept no longer fit
+            self.rewind.clear();
+            self.toasts.show(tr(Message::ToastNothingToRewind));
+            return;
+        }
+        self.remember();
+        self.universe.load(&cells);
+        self.universe.set_generation(generation);
+        self.current_period = None;
+        self.paused = true;
+        self.toasts
+            .show(format!("{} {}", tr(Message::ToastRewound), generation));
+    }
+
+    /// Glide the camera towards the centroid of the live cells
+    fn follow_live_cells(&mut self, ctx: &Context) {
+        let (mut sum_x, mut sum_y, mut count) = (0.0, 0.0, 0usize);
+        for (y, row) in self.universe.cells().iter().enumerate() {
+            for (x, _) in row.iter().enumerate().filter(|(_, &alive)| alive) {
+                sum_x += x as f64;
+                sum_y += y as f64;
+                count += 1;
+            }
+        }
+        if count == 0 {
+            return;
+        }
+        let [x, y] = self.cell.point(
+            (sum_x / count as f64) as f32 + 0.5,
+            (sum_y / count as f64) as f32 + 0.5,
+        );
+        let (window_width, window_height) = ctx.gfx.drawable_size();
+        self.camera.follow(
+            x + self.margin.0,
+            y + self.margin.1,
+            window_width,
+            window_height,
+        );
+    }
+
+    /// Show the latest generation that came in from the stream being spectated
+    fn follow_stream(&mut self, ctx: &mut Context) -> GameResult {
+        let Some(spectator) = &mut self.spectator else {
+            return Ok(());
+        };
+        match spectator.poll() {
+            Some(Ok((cells, generation))) => {
+                let (width, height) = (cells.first().map_or(0, Vec::len), cells.len());
+                if (width, height) != (self.universe.width(), self.universe.height()) {
+                    self.resize_universe(ctx, width, height, Anchor::Center)?;
+                }
+                self.universe.load(&cells);
+                self.universe.set_generation(generation);
+            }
+            Some(Err(e)) => {
+                self.spectator = None;
+                self.report_error(tr_with(Message::ErrorStoppedSpectating, &[&e]));
+            }
+            None => (),
+        }
+        Ok(())
+    }
+
+    /// Stop at the first generation the checking engine works out differently
+    fn cross_check_generation(&mut self, before: &Universe) {
+        let Some(check) = &mut self.cross_check else {
+            return;
+        };
+        if !check.check(before, &self.universe, 1) {
+            return;
+        }
+        let (main, checking) = check.engines();
+        let generation = self.universe.generation();
+        warn!(
+            %main,
+            %checking,
+            generation,
+            cells = check.differences(),
+            "the engines disagree"
+        );
+        self.paused = true;
+        self.toasts.show(format!(
+            "{} {}: {} / {}",
+            tr(Message::ToastEnginesDisagree),
+            generation,
+            main,
+            checking
+        ));
+    }
+
+    /// Pause at a population limit, telling which one was crossed
+    fn sound_alarm(&mut self, crossing: Crossing) {
+        let population = self.universe.population();
+        let generation = self.universe.generation();
+        let (message, limit) = match crossing {
+            Crossing::Above(limit) => (Message::ToastAlarmAbove, limit),
+            Crossing::Below(limit) => (Message::ToastAlarmBelow, limit),
+        };
+        warn!(
+            generation,
+            population, limit, "the population alarm went off"
+        );
+        self.paused = true;
+        self.toasts.show(format!(
+            "{} {} ({}: {}, {}: {})",
+            tr(message),
+            limit,
+            tr(Message::Population),
+            population,
+            tr(Message::Generation),
+            generation
+        ));
+    }
+
+    /// Ask for the population limits of the alarm
+    fn open_alarm_dialog(&mut self) {
+        self.prompt = Some((
+            Prompt::new(
+                tr(Message::AlarmPrompt).to_string(),
+                self.alarm.limits.to_string(),
+            ),
+            PromptPurpose::Alarm,
+        ));
+    }
+
+    /// Save the grid at a moment the population watcher picked out
+    fn save_snapshot(&mut self, trigger: watcher::Trigger) {
+        let (generation, population) = (self.universe.generation(), self.universe.population());
+        let path = watcher::snapshot_path(trigger, generation, population);
+        match save::write_state(&path, self.universe.cells(), self.annotations.labels()) {
+            Ok(()) => info!(path = %path.display(), generation, population, "saved a snapshot"),
+            Err(e) => self.report_error(tr_with(Message::ErrorCannotSave, &[&path.display(), &e])),
+        }
+    }
+
+    /// Advance the running simulation by a generation and react to the outcome
+    fn run_generation(&mut self) {
+        self.timed("update/simulation", |s| s.update_grid());
+        if let Some(puzzle) = &mut self.puzzle {
+            puzzle.check(&self.universe);
+            // Stop as soon as the attempt is decided
+            self.paused = puzzle.status() != Status::Running;
+        }
+        if self.screensaver {
+            self.timed("update/screensaver", |s| s.reseed_if_settled());
+        }
+    }
+
+    /// Look for new discoveries in the generation that was just computed
+    fn check_achievements(&mut self) {
+        let generation = self.universe.generation();
+        self.achievements.observe_generation(
+            self.universe.population(),
+            generation,
+            self.current_period,
+        );
+        if generation.is_multiple_of(achievements::SAMPLE_INTERVAL) {
+            self.ships.scan(self.universe.cells());
+            self.achievements.observe_ships(
+                self.ships.count(Ship::Glider),
+                self.ships.count(Ship::Lwss),
+                generation,
+            );
+        }
+    }
+
+    /// Identify the objects on the grid for the census panel
+    fn take_census(&mut self) {
+        let started = Instant::now();
+        self.census
+            .take(self.universe.cells(), self.universe.rule());
+        debug!(elapsed = ?started.elapsed(), "took a census");
+    }
+
+    /// Start over with a fresh soup once the current one has died out or settled
+    fn reseed_if_settled(&mut self) {
+        let settled = self.current_period.is_some();
+        if settled || self.universe.population() == 0 {
+            self.randomize();
+        }
+    }
+
+    /// Forget everything recorded about earlier generations after the grid was replaced
+    fn reset_history(&mut self) {
+        self.universe.reset_generation();
+        self.spacetime.clear();
+        self.period.reset();
+        self.current_period = None;
+        self.achievements.reset();
+        self.daily = None;
+        self.annotations.clear();
+        self.lifespans.reset();
+        self.trails.clear();
+        self.watcher.reset();
+        self.rewind.clear();
+    }
+
+    /// The grid as it is now, to come back to
+    fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            cells: self.universe.cells().to_vec(),
+            generation: self.universe.generation(),
+        }
+    }
+
+    /// Keep the grid as it is now so that the change about to be made can be undone
+    fn remember(&mut self) {
+        let snapshot = self.snapshot();
+        self.history.record(snapshot);
+    }
+
+    /// Go back to the grid before the last change
+    fn undo(&mut self) {
+        match self.history.undo(self.snapshot()) {
+            Some(snapshot) => {
+                self.restore(snapshot);
+                self.toasts.show(tr(Message::ToastUndone));
+            }
+            None => self.toasts.show(tr(Message::ToastNothingToUndo)),
+        }
+    }
+
+    /// Make the last undone change again
+    fn redo(&mut self) {
+        match self.history.redo(self.snapshot()) {
+            Some(snapshot) => {
+                self.restore(snapshot);
+                self.toasts.show(tr(Message::ToastRedone));
+            }
+            None => self.toasts.show(tr(Message::ToastNothingToRedo)),
+        }
+    }
+
+    fn restore(&mut self, snapshot: Snapshot) {
+        self.universe.load(&snapshot.cells);
+        self.universe.set_generation(snapshot.generation);
+        // What was observed since no longer leads up to the grid
+        self.spacetime.clear();
+        self.period.reset();
+        self.current_period = None;
+        self.trails.clear();
+    }
+
+    /// Go back to a snapshot of the gallery, which can be undone like any other change
+    fn restore_from_gallery(&mut self, snapshot: Snapshot) {
+        if !self.history.is_enabled() {
+            self.toasts.show(tr(Message::ToastGalleryNeedsHistory));
+            return;
+        }
+        let generation = snapshot.generation;
+        self.remember();
+        self.restore(snapshot);
+        self.toasts.show(format!(
+            "{} {}",
+            tr(Message::ToastGalleryRestored),
+            generation
+        ));
+    }
+
+    /// Export the snapshots of the gallery as a strip of pictures
+    fn export_gallery(&mut self) {
+        let path = export::export_path("png");
+        match self.gallery.write_strip(&path, self.export_margin) {
+            Ok(()) => {
+                info!(path = %path.display(), "exported the gallery");
+                self.toast_path(Message::ToastExported, &path);
+            }
+            Err(e) => {
+                self.report_error(tr_with(Message::ErrorCannotExport, &[&path.display(), &e]))
+            }
+        }
+    }
+
+    /// Toggle the paused state
+    fn toggle_pause(&mut self) {
+        self.paused = !self.paused;
+        if self.paused {
+            // Notes would otherwise hold until the next generation
+            if let Some(midi) = &mut self.midi {
+                midi.silence();
+            }
+        } else {
+            // The demo of a built-in rule is underway, its description is in the way now
+            self.rule_notes = None;
+        }
+        self.tutorial_observe(if self.paused {
+            Action::Pause
+        } else {
+            Action::Resume
+        });
+    }
+
+    /// Make the edges wrap around, or bound the universe again
+    fn toggle_toroidal(&mut self) {
+        let toroidal = !self.universe.is_toroidal();
+        self.universe.set_toroidal(toroidal);
+        info!(toroidal, "switched the edges");
+        self.toasts.show(format!(
+            "{}: {}",
+            tr(Message::Edges),
+            tr(hud::edges_message(toroidal))
+        ));
+    }
+
+    /// Advance a single generation while paused
+    fn step_once(&mut self) {
+        if self.paused {
+            self.update_grid();
+            self.tutorial_observe(Action::Step);
+        }
+    }
+
+    /// Jump far ahead at once, starting the statistics over from there
+    fn super_step(&mut self) {
+        if !self.engine.skips_ahead(&self.universe) {
+            self.toasts.show(tr(Message::ToastCannotJump));
+            return;
+        }
+        self.remember();
+        let started = Instant::now();
+        let generation = self.universe.generation();
+        self.engine.advance(&mut self.universe, self.super_step);
+        info!(
+            generations = self.super_step,
+            elapsed = ?started.elapsed(),
+            "jumped ahead"
+        );
+        // The skipped generations were never seen, so nothing carries over but the count
+        self.reset_history();
+        self.universe.set_generation(generation + self.super_step);
+        self.toasts.show(format!(
+            "{}: +{}",
+            tr(Message::ToastJumpedAhead),
+            self.super_step
+        ));
+    }
+
+    /// Replace the grid with the bundled Gosper glider gun
+    fn load_glider_gun(&mut self) {
+        match Pattern::parse(GLIDER_GUN) {
+            Ok(pattern) => self.load_pattern(&pattern),
+            Err(e) => self.report_error(tr_with(Message::ErrorCannotLoadGun, &[&e])),
+        }
+        self.tutorial_observe(Action::LoadGun);
+    }
+
+    /// Start the tutorial, or close it and remember that it has been seen
+    fn toggle_tutorial(&mut self) {
+        if self.tutorial.take().is_some() {
+            Tutorial::mark_seen();
+        } else {
+            self.tutorial = Some(Tutorial::new());
+        }
+    }
+
+    /// Ask for the text of the label on a cell
+    fn edit_label(&mut self, x: usize, y: usize) {
+        if x >= self.universe.width() || y >= self.universe.height() {
+            return;
+        }
+        let text = self.annotations.get(x, y).unwrap_or_default().to_string();
+        let title = format!("{} ({}, {})", tr(Message::LabelPrompt), x, y);
+        self.prompt = Some((Prompt::new(title, text), PromptPurpose::Label(x, y)));
+    }
+
+    /// Pass a key to the open prompt and apply the text once it is confirmed
+    fn prompt_key(&mut self, ctx: &mut Context, key: Option<KeyCode>) -> GameResult {
+        let Some((prompt, purpose)) = &mut self.prompt else {
+            return Ok(());
+        };
+        match prompt.key(key) {
+            Outcome::Editing => (),
+            Outcome::Confirmed(text) => {
+                let purpose = *purpose;
+                self.prompt = None;
+                match purpose {
+                    PromptPurpose::Label(x, y) => self.annotations.set(x, y, text),
+                    PromptPurpose::Resize => match parse_size_and_anchor(&text) {
+                        Some((width, height, anchor)) => {
+                            self.resize_universe(ctx, width, height, anchor)?
+                        }
+                        None => self.report_error(tr(Message::ErrorExpectedSize).to_string()),
+                    },
+                    PromptPurpose::SaveWorkspace if !text.is_empty() => self.save_workspace(text),
+                    PromptPurpose::OpenWorkspace if !text.is_empty() => {
+                        self.open_workspace(ctx, &workspace::path_for(&text))?;
+                        self.workspace_name = text;
+                    }
+                    PromptPurpose::SaveWorkspace | PromptPurpose::OpenWorkspace => (),
+                    PromptPurpose::Rule => match Rule::parse(&text) {
+                        Ok(rule) => self.set_rule(rule),
+                        Err(e) => self.report_error(e),
+                    },
+                    PromptPurpose::Catagolue => self.open_from_catagolue(&text),
+                    PromptPurpose::Scatter => match text.trim().parse() {
+                        Ok(count) => self.scatter_stamp(count),
+                        Err(_) => self.report_error(tr_with(Message::ErrorNotCopies, &[&text])),
+                    },
+                    PromptPurpose::Alarm => match Limits::parse(&text) {
+                        Ok(limits) => {
+                            self.alarm.set_limits(limits);
+                            let message = if limits.is_empty() {
+                                tr(Message::ToastAlarmOff).to_string()
+                            } else {
+                                format!("{}: {}", tr(Message::ToastAlarmSet), limits)
+                            };
+                            self.toasts.show(message);
+                        }
+                        Err(e) => self.report_error(e),
+                    },
+                }
+            }
+            Outcome::Cancelled => self.prompt = None,
+        }
+        Ok(())
+    }
+
+    /// Ask for the new size of the universe and the corner to keep in place
+    fn open_resize_dialog(&mut self) {
+        let text = format!(
+            "{}x{} center",
+            self.universe.width(),
+            self.universe.height()
+        );
+        self.prompt = Some((
+            Prompt::new(tr(Message::ResizePrompt).to_string(), text),
+            PromptPurpose::Resize,
+        ));
+    }
+
+    /// Ask for a rule in B/S notation, starting from the current one
+    fn open_rule_dialog(&mut self) {
+        let text = self.universe.rule().to_string();
+        self.prompt = Some((
+            Prompt::new(tr(Message::RulePrompt).to_string(), text),
+            PromptPurpose::Rule,
+        ));
+    }
+
+    /// Ask for the apgcode of an object, or of a soup that produced it, to load
+    fn open_catagolue_dialog(&mut self) {
+        self.prompt = Some((
+            Prompt::new(tr(Message::CatagoluePrompt).to_string(), String::new()),
+            PromptPurpose::Catagolue,
+        ));
+    }
+
+    /// Place the object with an apgcode, or start fetching a random soup in
+    /// which Catagolue saw it after `soup`
+    fn open_from_catagolue(&mut self, text: &str) {
+        let (soup, code) = match text.strip_prefix("soup") {
+            Some(code) => (true, code.trim()),
+            None => (false, text),
+        };
+        if let Err(e) = catagolue::check_code(code) {
+            self.report_error(e);
+            return;
+        }
+        if !soup {
+            match catagolue::object(code) {
+                Ok(pattern) => {
+                    self.load_pattern(&pattern);
+                    self.toasts
+                        .show(format!("{} {}", tr(Message::ToastLoaded), code));
+                }
+                Err(e) => self.report_error(e),
+            }
+            return;
+        }
+        let url = catagolue::samples_url(code, self.universe.rule());
+        info!(url = %url, "fetching soups from Catagolue");
+        self.toasts
+            .show(format!("{} {}", tr(Message::ToastDownloading), url));
+        self.catagolue = Some((code.to_string(), Download::start(&url)));
+    }
+
+    /// Load one of the soups Catagolue listed once the list has arrived
+    fn finish_catagolue(&mut self) {
+        let Some(result) = self.catagolue.as_ref().and_then(|(_, d)| d.poll()) else {
+            return;
+        };
+        let (code, _) = self.catagolue.take().unwrap();
+        match result.map(|text| catagolue::pick_seed(&text)) {
+            Ok(Some(seed)) => {
+                info!(code = %code, seed = %seed, "loaded a soup from Catagolue");
+                self.load_pattern(&catagolue::soup(&seed));
+                self.toasts
+                    .show(format!("{} {}", tr(Message::ToastLoaded), seed));
+            }
+            Ok(None) => self.report_error(tr_with(
+                Message::ErrorNoSoups,
+                &[&code, &self.universe.rule()],
+            )),
+            Err(e) => self.report_error(tr_with(Message::ErrorCannotFetchSoups, &[&code, &e])),
+        }
+    }
+
+    /// Run the grid under another rule from the next generation on
+    fn set_rule(&mut self, rule: Rule) {
+        info!(from = %self.universe.rule(), to = %rule, "switched the rule");
+        self.universe.set_rule(rule);
+        self.multistate.set_automaton(None);
+        self.replaced_rule = None;
+        self.toasts
+            .show(format!("{} {}", tr(Message::ToastRuleChanged), rule));
+    }
+
+    /// Make the cells as large as the screen allows while staying square
+    fn fit_to_screen(&mut self, ctx: &Context) {
+        let (screen_width, screen_height) = ctx.gfx.drawable_size();
+        let size = (screen_width / self.universe.width() as f32)
+            .min(screen_height / self.universe.height() as f32);
+        self.cell = CellSize {
+            width: size,
+            height: size,
+        };
+        self.center_grid(ctx);
+    }
+
+    /// Scale the cells to fit the whole grid into a window resized by hand,
+    /// keeping the shape the configuration gives them
+    fn fit_to_window(&mut self, ctx: &Context) {
+        let (window_width, window_height) = ctx.gfx.drawable_size();
+        let preferred = self.preferred_cell;
+        let scale = (window_width / (self.universe.width() as f32 * preferred.width))
+            .min(window_height / (self.universe.height() as f32 * preferred.height));
+        self.cell = CellSize {
+            width: preferred.width * scale,
+            height: preferred.height * scale,
+        };
+        self.center_grid(ctx);
+    }
+
+    /// Split the room the grid leaves in the window evenly around it
+    fn center_grid(&mut self, ctx: &Context) {
+        let (window_width, window_height) = ctx.gfx.drawable_size();
+        self.margin = (
+            ((window_width - self.universe.width() as f32 * self.cell.width) / 2.0).max(0.0),
+            ((window_height - self.universe.height() as f32 * self.cell.height) / 2.0).max(0.0),
+        );
+    }
+
+    /// Handle a key in presentation mode, where only pause, step and speed work
+    fn presentation_key(&mut self, ctx: &mut Context, input: ggez::input::keyboard::KeyInput) {
+        match input.keycode {
+            Some(KeyCode::Space) => self.toggle_pause(),
+            Some(KeyCode::N | KeyCode::Period) => self.step_once(),
+            Some(KeyCode::Up) => self.increase_update_delay(),
+            Some(KeyCode::Down) => self.decrease_update_delay(),
+            Some(KeyCode::RShift) => self.reset_update_delay(),
+            Some(KeyCode::Right) => self.increase_update_delay_step(),
+            Some(KeyCode::Left) => self.decrease_update_delay_step(),
+            // A lone Escape is too easily hit by accident on stage
+            Some(KeyCode::Escape) if input.mods.contains(ggez::input::keyboard::KeyMods::SHIFT) => {
+                ctx.request_quit()
+            }
+            _ => (),
+        }
+    }
+
+    /// Draw the grid alone, centered on the screen
+    fn draw_presentation(&mut self, ctx: &mut Context, canvas: &mut Canvas) -> GameResult {
+        match self.view {
+            // Centered by the margin
+            ViewMode::Flat => self.draw_flat(ctx, canvas),
+            // The other views fill the window by themselves
+            _ => self.draw_view(ctx, canvas),
+        }
+    }
+
+    /// The part of the grid that is on screen, in unzoomed pixels
+    fn view_rect(&self, ctx: &Context) -> graphics::Rect {
+        let (window_width, window_height) = ctx.gfx.drawable_size();
+        let mut view = self.camera.view(window_width, window_height);
+        view.x -= self.margin.0;
+        view.y -= self.margin.1;
+        view
+    }
+
+    /// The cell under a point of the window, if it is on the grid
+    fn cell_under(&self, x: f32, y: f32) -> Option<(usize, usize)> {
+        let (x, y) = self.camera.world_at(x, y);
+        let (x, y) = (x - self.margin.0, y - self.margin.1);
+        let (cell_x, cell_y) = self.cell.cell_at(x, y);
+        let on_grid = x >= 0.0
+            && y >= 0.0
+            && cell_x < self.universe.width()
+            && cell_y < self.universe.height();
+        on_grid.then_some((cell_x, cell_y))
+    }
+
+    /// Grow or shrink the universe and the window, keeping the cells at the anchor
+    fn resize_universe(
+        &mut self,
+        ctx: &mut Context,
+        width: usize,
+        height: usize,
+        anchor: Anchor,
+    ) -> GameResult {
+        let width = width.clamp(MIN_GRID_SIZE, MAX_GRID_SIZE);
+        let height = height.clamp(MIN_GRID_SIZE, MAX_GRID_SIZE);
+        let offset = self.universe.resize(width, height, anchor);
+        info!(width, height, "resized the universe");
+        // Snapshots of another size cannot be restored
+        self.history.clear();
+        self.selection = None;
+        self.gallery.clear();
+        self.annotations.shift(offset, width, height);
+        // Statistics and history were recorded for the old dimensions
+        self.lifespans = LifespanStats::new(width, height);
+        self.spacetime.clear();
+        self.period.reset();
+        self.current_period = None;
+        self.achievements.reset();
+        if self.measure.is_some() {
+            self.measure = Some(Measure::new());
+        }
+        self.toasts.show(format!(
+            "{}: {}x{}",
+            tr(Message::ToastUniverse),
+            width,
+            height
+        ));
+        self.fit_window(ctx)
+    }
+
+    /// Size the window to the grid, shrinking the cells if it would not fit on the screen
+    fn fit_window(&mut self, ctx: &mut Context) -> GameResult {
+        if self.presenting {
+            self.fit_to_screen(ctx);
+            return Ok(());
+        }
+        let (width, height) = (self.universe.width() as f32, self.universe.height() as f32);
+        let preferred = self.preferred_cell;
+        let mut scale = 1.0;
+        if let Some(monitor) = ctx.gfx.window().current_monitor() {
+            // The monitor measures physical pixels, the window logical ones
+            let screen = monitor.size().to_logical::<f32>(monitor.scale_factor());
+            scale = (screen.width * SCREEN_SHARE / (width * preferred.width))
+                .min(screen.height * SCREEN_SHARE / (height * preferred.height))
+                .min(1.0);
+        }
+        if scale < 1.0 {
+            debug!(scale, "shrinking the cells to fit the screen");
+        }
+        self.cell = CellSize {
+            width: preferred.width * scale,
+            height: preferred.height * scale,
+        };
+        self.margin = (0.0, 0.0);
+        ctx.gfx
+            .set_drawable_size(width * self.cell.width, height * self.cell.height)
+    }
+
+    /// Handle a key in the pattern editor, which is never running
+    fn editor_key(&mut self, ctx: &mut Context, key: Option<KeyCode>) {
+        match key {
+            Some(KeyCode::M) => self.editor.cycle_symmetry(),
+            Some(KeyCode::C) => self.editor.clear(),
+            Some(KeyCode::Return | KeyCode::NumpadEnter) => {
+                // Try the pattern out in the sandbox straight away
+                let pattern = self.editor.pattern();
+                self.load_pattern(&pattern);
+                self.editing = false;
+                self.paused = false;
+            }
+            Some(KeyCode::E) => self.editing = false,
+            Some(KeyCode::F3) => self.perf.visible = !self.perf.visible,
+            Some(KeyCode::Escape) => ctx.request_quit(),
+            _ => (),
+        }
+    }
+
+    /// Handle a key in puzzle mode, where only the level itself can be changed
+    fn puzzle_key(&mut self, ctx: &mut Context, key: Option<KeyCode>) {
+        let Some(puzzle) = &mut self.puzzle else {
+            return;
+        };
+        match key {
+            Some(KeyCode::Space) => match puzzle.status() {
+                Status::Editing => {
+                    puzzle.run();
+                    self.paused = false;
+                }
+                Status::Running => self.toggle_pause(),
+                Status::Solved | Status::Failed => (),
+            },
+            Some(KeyCode::C) => {
+                // Try the level again
+                puzzle.restart(&mut self.universe);
+                self.paused = true;
+                self.reset_history();
+            }
+            Some(KeyCode::Return | KeyCode::NumpadEnter) => {
+                puzzle.advance(&mut self.universe);
+                self.paused = true;
+                self.reset_history();
+            }
+            Some(KeyCode::F3) => self.perf.visible = !self.perf.visible,
+            Some(KeyCode::Escape) => ctx.request_quit(),
+            _ => (),
+        }
+    }
+
+    /// Handle a key while the placement list is open, returning whether it was used
+    fn placements_key(&mut self, key: Option<KeyCode>) -> bool {
+        let universe = &mut self.universe;
+        match key {
+            Some(KeyCode::Up) => self.placements.select_previous(),
+            Some(KeyCode::Down) => self.placements.select_next(),
+            Some(KeyCode::Return | KeyCode::NumpadEnter) => self.placements.place_again(universe),
+            Some(KeyCode::Delete) => self.placements.delete(universe),
+            Some(KeyCode::R) => self.placements.rotate(universe),
+            Some(KeyCode::F) => self.placements.mirror(universe),
+            Some(KeyCode::O | KeyCode::Escape) => self.placements.visible = false,
+            _ => return false,
+        }
+        true
+    }
+
+    /// Let the tutorial know what the player just did
+    fn tutorial_observe(&mut self, action: Action) {
+        if let Some(tutorial) = &mut self.tutorial {
+            tutorial.observe(action);
+        }
+    }
+
+    /// Cycle through the flat, space-time and isometric views
+    fn cycle_view(&mut self) {
+        let (view, name) = match self.view {
+            ViewMode::Flat => (ViewMode::SpaceTime, Message::ViewSpaceTime),
+            ViewMode::SpaceTime => (ViewMode::Isometric, Message::ViewIsometric),
+            ViewMode::Isometric => (ViewMode::Flat, Message::ViewFlat),
+        };
+        self.view = view;
+        self.toasts.show(tr(name));
+    }
+
+    /// Lay out the panel and do what was asked for in it
+    fn update_panel(&mut self, ctx: &mut Context) -> GameResult {
+        let rule = match self.multistate.automaton() {
+            Some(automaton) => automaton.name().to_string(),
+            None => rulebook::RULES
+                .iter()
+                .find(|entry| {
+                    Rule::parse(entry.rule).is_ok_and(|rule| rule == self.universe.rule())
+                })
+                .map_or_else(
+                    || self.universe.rule().to_string(),
+                    |entry| entry.name.to_string(),
+                ),
+        };
+        let status = panel::Status {
+            paused: self.paused,
+            delay: self.update_delay,
+            rule,
+            width: self.universe.width(),
+            height: self.universe.height(),
+            theme: self.theme,
+        };
+        for command in self.panel.update(ctx, &status) {
+            match command {
+                Command::TogglePause => self.toggle_pause(),
+                Command::Step => self.step_once(),
+                Command::SetDelay(delay) => self.update_delay = delay,
+                Command::Rule(index) => self.switch_to_builtin_rule(index),
+                Command::Automaton(index) => self.switch_to_automaton(index),
+                Command::Resize(width, height) => {
+                    self.resize_universe(ctx, width, height, Anchor::Center)?
+                }
+                Command::Theme(theme) => self.theme = theme,
+                Command::Clear => self.clear_grid(),
+                Command::Randomize => self.randomize(),
+                Command::Quicksave => self.quicksave(),
+                Command::Quickload => self.quickload(),
+                Command::SaveWorkspace => self.open_workspace_dialog(PromptPurpose::SaveWorkspace),
+                Command::OpenWorkspace => self.open_workspace_dialog(PromptPurpose::OpenWorkspace),
+            }
+        }
+        Ok(())
+    }
+
+    /// Write the grid to the quicksave file
+    fn quicksave(&mut self) {
+        let path = save::quicksave_path();
+        match save::write_state(&path, self.universe.cells(), self.annotations.labels()) {
+            Ok(()) => {
+                info!(path = %path.display(), "saved the grid");
+                self.toast_path(Message::ToastSaved, &path);
+            }
+            Err(e) => self.report_error(tr_with(Message::ErrorCannotSave, &[&path.display(), &e])),
+        }
+    }
+
+    /// Restore the grid from the quicksave file
+    fn quickload(&mut self) {
+        let path = save::quicksave_path();
+        match save::read_state(&path) {
+            Ok(state) => {
+                // Copy what fits, so saves from differently sized grids still load
+                self.universe.load(&state.grid);
+                self.reset_history();
+                self.annotations.replace(state.labels);
+                info!(path = %path.display(), "loaded the grid");
+                self.toast_path(Message::ToastLoaded, &path);
+            }
+            Err(e) => self.report_error(tr_with(Message::ErrorCannotLoad, &[&path.display(), &e])),
+        }
+    }
+
+    /// Ask for a name to save the workspace under, or for the one to reopen
+    fn open_workspace_dialog(&mut self, purpose: PromptPurpose) {
+        let title = match purpose {
+            PromptPurpose::SaveWorkspace => Message::SaveWorkspacePrompt,
+            _ => Message::OpenWorkspacePrompt,
+        };
+        self.prompt = Some((
+            Prompt::new(tr(title).to_string(), self.workspace_name.clone()),
+            purpose,
+        ));
+    }
+
+    /// Save the grid with its history, labels, placements, rule and view
+    fn save_workspace(&mut self, name: String) {
+        let path = workspace::path_for(&name);
+        let workspace = Workspace {
+            version: workspace::VERSION,
+            rule: self.universe.rule().to_string(),
+            generation: self.universe.generation(),
+            toroidal: self.universe.is_toroidal(),
+            wrap_shift: self.universe.wrap_shift(),
+            view: self.view,
+            camera: self.camera,
+            update_delay_ms: self.update_delay.as_secs_f64() * 1000.0,
+            grid: Rows(self.universe.cells().to_vec()),
+            history: self
+                .spacetime
+                .layers()
+                .map(|layer| Rows(layer.to_rows()))
+                .collect(),
+            labels: self.annotations.labels().to_vec(),
+            placements: self.placements.entries().to_vec(),
+            frozen: self.universe.frozen().to_vec(),
+        };
+        match workspace.write(&path) {
+            Ok(()) => {
+                info!(path = %path.display(), "saved the workspace");
+                self.toast_path(Message::ToastSaved, &path);
+                self.workspace_name = name;
+            }
+            Err(e) => self.report_error(tr_with(Message::ErrorCannotSave, &[&path.display(), &e])),
+        }
+    }
+
+    /// Reopen a workspace exactly as it was saved, resizing the universe to fit
+    fn open_workspace(&mut self, ctx: &mut Context, path: &Path) -> GameResult {
+        let workspace = match Workspace::read(path) {
+            Ok(workspace) => workspace,
+            Err(e) => {
+                self.report_error(tr_with(Message::ErrorCannotOpen, &[&path.display(), &e]));
+                return Ok(());
+            }
+        };
+        let rule = match Rule::parse(&workspace.rule) {
+            Ok(rule) => rule,
+            Err(e) => {
+                self.report_error(tr_with(Message::ErrorCannotOpen, &[&path.display(), &e]));
+                return Ok(());
+            }
+        };
+        let (width, height) = workspace.size();
+        if (width, height) != (self.universe.width(), self.universe.height()) {
+            self.resize_universe(ctx, width, height, Anchor::Center)?;
+        }
+        self.universe.load(&workspace.grid.0);
+        self.reset_history();
+        self.universe.set_generation(workspace.generation);
+        self.universe.set_rule(rule);
+        self.replaced_rule = None;
+        self.universe.set_toroidal(workspace.toroidal);
+        self.universe.set_wrap_shift(workspace.wrap_shift);
+        self.view = workspace.view;
+        self.camera = workspace.camera;
+        self.update_delay = tick::delay_from_ms(workspace.update_delay_ms);
+        // Replay the history so the space-time view and the period detector pick up where they were
+        for layer in &workspace.history {
+            if (layer.0.len(), layer.0.first().map_or(0, Vec::len))
+                == (self.universe.height(), self.universe.width())
+            {
+                let layer =
+                    BitGrid::from_rows(&layer.0, self.universe.width(), self.universe.height());
+                self.spacetime.push(&layer);
+                self.period.observe(&layer);
+            }
+        }
+        self.current_period = self.period.observe(self.universe.grid());
+        self.annotations.replace(workspace.labels);
+        self.placements.replace(workspace.placements);
+        self.universe.set_frozen(workspace.frozen);
+        info!(path = %path.display(), "opened the workspace");
+        self.toast_path(Message::ToastLoaded, path);
+        Ok(())
+    }
+
+    /// Show a pattern, centered, over the grid for comparison
+    fn set_overlay(&mut self, pattern: &Pattern) {
+        let (left, top) = self.universe.centered(pattern);
+        self.overlay = Some(Overlay::new(pattern, left, top));
+    }
+
+    /// Compare the grid with the pattern on the clipboard
+    fn overlay_from_clipboard(&mut self) {
+        match self
+            .clipboard
+            .get_text()
+            .map_err(|e| e.to_string())
+            .and_then(|text| Pattern::parse(&text))
+        {
+            Ok(pattern) if !pattern.cells.is_empty() => self.set_overlay(&pattern),
+            Ok(_) => self.report_error(tr(Message::ErrorClipboardNoPattern).to_string()),
+            Err(e) => self.report_error(tr_with(Message::ErrorCannotReadClipboardPattern, &[&e])),
+        }
+    }
+
+    /// Load the next or previous pattern from `resources/patterns`
+    fn cycle_library(&mut self, ctx: &Context, forward: bool) {
+        match self.library.cycle(ctx, forward) {
+            Ok(Some((pattern, position, count))) => {
+                self.load_pattern(&pattern);
+                self.toasts.show(format!(
+                    "{} {} ({}/{})",
+                    tr(Message::ToastLoaded),
+                    pattern.name.as_deref().unwrap_or(tr(Message::Unnamed)),
+                    position,
+                    count
+                ));
+            }
+            Ok(None) => self.toasts.show(tr(Message::ToastNoPatterns)),
+            Err(e) => self.report_error(tr_with(Message::ErrorCannotLoadPattern, &[&e])),
+        }
+    }
+
+    /// Replace the grid with a pattern centered on it
+    fn load_pattern(&mut self, pattern: &Pattern) {
+        info!(
+            name = pattern.name.as_deref().unwrap_or("unnamed"),
+            width = pattern.width,
+            height = pattern.height,
+            cells = pattern.cells.len(),
+            "loaded a pattern"
+        );
+        if let Some(header) = &pattern.rule {
+            self.switch_to_pattern_rule(header);
+        }
+        self.remember();
+        self.universe.clear();
+        let (left, top) = self.universe.centered(pattern);
+        self.universe.place(pattern, left, top);
+        self.placements.record(pattern, left, top);
+        self.reset_history();
+    }
+
+    /// Run a pattern under the rule named in its header, which it is probably
+    /// meant for, and offer to switch back
+    fn switch_to_pattern_rule(&mut self, header: &str) {
+        let current = self.universe.rule();
+        match Rule::parse(header) {
+            Ok(rule) if rule == current => (),
+            Ok(rule) => {
+                info!(from = %current, to = %rule, "switched to the rule of the pattern");
+                self.universe.set_rule(rule);
+                self.replaced_rule = Some(current);
+                self.toasts.show(format!(
+                    "{} {} ({} {})",
+                    tr(Message::ToastRuleChanged),
+                    rule,
+                    tr(Message::ToastRuleRevert),
+                    current
+                ));
+            }
+            Err(e) => {
+                warn!("{}, keeping {}", e, current);
+                self.toasts
+                    .show(format!("{}: {}", tr(Message::ToastRuleUnsupported), header));
+            }
+        }
+    }
+
+    /// Go back to the rule a loaded pattern replaced
+    fn revert_rule(&mut self) {
+        if let Some(rule) = self.replaced_rule.take() {
+            info!(to = %rule, "reverted the rule");
+            self.universe.set_rule(rule);
+            self.toasts
+                .show(format!("{} {}", tr(Message::ToastRuleChanged), rule));
+        }
+    }
+
+    /// Start downloading the pattern whose URL is on the clipboard
+    fn open_url_from_clipboard(&mut self) {
+        let url = match self.clipboard.get_text() {
+            Ok(text) => text.trim().to_string(),
+            Err(e) => {
+                self.report_error(tr_with(Message::ErrorCannotReadClipboard, &[&e]));
+                return;
+            }
+        };
+        if !url.starts_with("http://") && !url.starts_with("https://") {
+            self.report_error(tr(Message::ErrorClipboardNoUrl).to_string());
+            return;
+        }
+        info!(url = %url, "downloading a pattern");
+        self.toasts
+            .show(format!("{} {}", tr(Message::ToastDownloading), url));
+        self.download = Some(Download::start(&url));
+    }
+
+    /// Load the downloaded pattern once it has arrived
+    fn finish_download(&mut self) {
+        let Some(result) = self.download.as_ref().and_then(Download::poll) else {
+            return;
+        };
+        let url = self.download.take().unwrap().url;
+        match result.and_then(|text| Pattern::parse(&text)) {
+            Ok(pattern) => {
+                self.load_pattern(&pattern);
+                self.toasts
+                    .show(format!("{} {}", tr(Message::ToastLoaded), url));
+            }
+            Err(e) => self.report_error(tr_with(Message::ErrorCannotOpen, &[&url, &e])),
+        }
+    }
+
+    /// Copy the live cells as `.`/`O` ASCII art for pasting into chats and forums
+    fn copy_ascii_art(&mut self) {
+        let Some(bounds) = self.universe.bounding_box() else {
+            return;
+        };
+        let art = export::ascii_art(self.universe.cells(), bounds);
+        match self.clipboard.set_text(art) {
+            Ok(()) => self.toasts.show(tr(Message::ToastCopied)),
+            Err(e) => self.report_error(tr_with(Message::ErrorCannotWriteClipboard, &[&e])),
+        }
+    }
+
+    /// Copy the live cells in one of the Life formats older archives use
+    fn copy_life(&mut self, encode: impl Fn(&Pattern, Rule) -> String) {
+        if self.universe.bounding_box().is_none() {
+            self.toasts.show(tr(Message::ToastNothingToCopy));
+            return;
+        }
+        let text = encode(&self.universe.to_pattern(), self.universe.rule());
+        match self.clipboard.set_text(text) {
+            Ok(()) => self.toasts.show(tr(Message::ToastCopied)),
+            Err(e) => self.report_error(tr_with(Message::ErrorCannotWriteClipboard, &[&e])),
+        }
+    }
+
+    /// Copy the live cells as RLE for pasting into Golly or the LifeWiki forums
+    fn copy_rle(&mut self) {
+        if self.universe.bounding_box().is_none() {
+            self.toasts.show(tr(Message::ToastNothingToCopy));
+            return;
+        }
+        let rle = self
+            .universe
+            .to_pattern()
+            .to_rle(&self.universe.rule().to_string());
+        match self.clipboard.set_text(rle) {
+            Ok(()) => self.toasts.show(tr(Message::ToastCopiedRle)),
+            Err(e) => self.report_error(tr_with(Message::ErrorCannotWriteClipboard, &[&e])),
+        }
+    }
+
+    /// Start capturing generations, or stop and save them as an animated GIF
+    fn toggle_recording(&mut self) {
+        if !self.recorder.is_recording() {
+            self.recorder.start(self.universe.cells());
+            info!("started recording");
+            self.toasts.show(tr(Message::ToastRecording));
+        } else if let Some(path) = self.recorder.stop(self.update_delay) {
+            self.toast_path(Message::ToastSavingRecording, &path);
+        }
+    }
+
+    /// Export the lifespan histogram as CSV
+    fn export_lifespans(&mut self) {
+        let path = export::export_path("lifespans.csv");
+        match self.lifespans.write_csv(&path) {
+            Ok(()) => {
+                info!(path = %path.display(), "exported the data");
+                self.toast_path(Message::ToastExported, &path);
+            }
+            Err(e) => {
+                self.report_error(tr_with(Message::ErrorCannotExport, &[&path.display(), &e]))
+            }
+        }
+    }
+
+    /// Export one full period of the oscillating grid as a seamlessly looping GIF
+    fn export_period_gif(&mut self) {
+        let Some(period) = self.current_period else {
+            self.toasts.show(tr(Message::ToastNoPeriod));
+            return;
+        };
+        // Run the period on a copy so the game carries on where it is
+        let mut universe = self.universe.clone();
+        let mut frames = Vec::with_capacity(period);
+        for _ in 0..period {
+            frames.push(universe.cells().to_vec());
+            universe.step();
+        }
+        let delay = (self.update_delay.as_millis() / 10).clamp(2, u16::MAX as u128) as u16;
+        let path = export::export_path("gif");
+        match export::write_gif(&path, &frames, delay) {
+            Ok(()) => {
+                info!(path = %path.display(), period, "exported a period as GIF");
+                self.toast_path(Message::ToastExported, &path);
+            }
+            Err(e) => {
+                self.report_error(tr_with(Message::ErrorCannotExport, &[&path.display(), &e]))
+            }
+        }
+    }
+
+    /// Export the live cells as source code to embed in other programs
+    fn export_source(&mut self, language: SourceLanguage) {
+        let Some(bounds) = self.universe.bounding_box() else {
+            return;
+        };
+        let path = export::export_path(language.extension());
+        let rule = self.universe.rule().to_string();
+        match export::write_source(&path, self.universe.cells(), bounds, &rule, language) {
+            Ok(()) => {
+                info!(path = %path.display(), "exported the pattern as source code");
+                self.toast_path(Message::ToastExported, &path);
+            }
+            Err(e) => {
+                self.report_error(tr_with(Message::ErrorCannotExport, &[&path.display(), &e]))
+            }
+        }
+    }
+
+    /// Export the live cells as an RLE file, cropped to them
+    fn export_rle(&mut self) {
+        if self.universe.bounding_box().is_none() {
+            self.toasts.show(tr(Message::ToastNothingToExport));
+            return;
+        }
+        let path = export::export_path("rle");
+        let pattern = self.universe.to_pattern();
+        let rule = self.universe.rule().to_string();
+        match export::write_rle(&path, &pattern, &rule, self.export_margin) {
+            Ok(()) => {
+                info!(path = %path.display(), "exported the pattern as RLE");
+                self.toast_path(Message::ToastExported, &path);
+            }
+            Err(e) => {
+                self.report_error(tr_with(Message::ErrorCannotExport, &[&path.display(), &e]))
+            }
+        }
+    }
+
+    /// Export the live cells as a picture, cropped to them
+    fn export_png(&mut self) {
+        if self.universe.bounding_box().is_none() {
+            self.toasts.show(tr(Message::ToastNothingToExport));
+            return;
+        }
+        let path = export::export_path("png");
+        match export::write_png(&path, self.universe.cells(), self.export_margin) {
+            Ok(()) => {
+                info!(path = %path.display(), "exported the pattern as a picture");
+                self.toast_path(Message::ToastExported, &path);
+            }
+            Err(e) => {
+                self.report_error(tr_with(Message::ErrorCannotExport, &[&path.display(), &e]))
+            }
+        }
+    }
+
+    /// Export the grid as JSON for external tools
+    fn export_json(&mut self) {
+        let path = export::export_path("json");
+        let (cells, generation) = (self.universe.cells(), self.universe.generation());
+        let rule = self.universe.rule().to_string();
+        match export::write_json(&path, cells, &rule, generation) {
+            Ok(()) => {
+                info!(path = %path.display(), "exported the data");
+                self.toast_path(Message::ToastExported, &path);
+            }
+            Err(e) => {
+                self.report_error(tr_with(Message::ErrorCannotExport, &[&path.display(), &e]))
+            }
+        }
+    }
+
+    /// Acknowledge that a file was written or read
+    fn toast_path(&mut self, message: Message, path: &Path) {
+        self.toasts
+            .show(format!("{} {}", tr(message), path.display()));
+    }
+
+    /// Tell the user that something went wrong, on screen and on stderr
+    fn report_error(&mut self, message: String) {
+        error!("{}", message);
+        self.toasts.show(message);
+    }
+
+    /// The generator of the next random soup, remembering its seed
+    fn soup_rng(&mut self) -> ChaCha8Rng {
+        let seed = self.next_seed;
+        self.next_seed = self.seeds.gen();
+        self.soup_seed = Some(seed);
+        info!(seed, "seeded a random soup");
+        if !self.screensaver {
+            self.toasts
+                .show(format!("{}: {}", tr(Message::ToastSeed), seed));
+        }
+        ChaCha8Rng::seed_from_u64(seed)
+    }
+
+    /// Copy the seed of the last random soup to the clipboard
+    fn copy_seed(&mut self) {
+        let Some(seed) = self.soup_seed else {
+            self.toasts.show(tr(Message::ToastNoSeed));
+            return;
+        };
+        match self.clipboard.set_text(seed.to_string()) {
+            Ok(()) => self
+                .toasts
+                .show(format!("{}: {}", tr(Message::ToastCopiedSeed), seed)),
+            Err(e) => self.report_error(tr_with(Message::ErrorCannotWriteClipboard, &[&e])),
+        }
+    }
+
+    /// Kill every cell, undoably
+    fn clear_grid(&mut self) {
+        self.remember();
+        self.universe.clear();
+        self.reset_history();
+    }
+
+    /// Set cells to a random state
+    fn randomize(&mut self) {
+        self.remember();
+        let mut rng = self.soup_rng();
+        self.universe.fill_random(0.5, &mut rng);
+        self.reset_history();
+    }
+
+    /// Fill the grid with noise shaped by a distribution, in the chosen symmetry
+    fn seed(&mut self, distribution: Distribution) {
+        self.remember();
+        let (width, height) = (self.universe.width(), self.universe.height());
+        let mut rng = self.soup_rng();
+        self.universe
+            .fill_random_with(|x, y| distribution.density(x, y, width, height), &mut rng);
+        let cells = seeding::symmetrize(self.universe.cells(), self.seeding_symmetry);
+        self.universe.load(&cells);
+        self.reset_history();
+    }
+
+    /// Handle a key while the seeding menu is open, seeding with the chosen distribution
+    fn seeding_key(&mut self, key: Option<KeyCode>) {
+        let choice = match key {
+            Some(KeyCode::Key1 | KeyCode::Numpad1) => Some(0),
+            Some(KeyCode::Key2 | KeyCode::Numpad2) => Some(1),
+            Some(KeyCode::Key3 | KeyCode::Numpad3) => Some(2),
+            Some(KeyCode::Key4 | KeyCode::Numpad4) => Some(3),
+            Some(KeyCode::Key5 | KeyCode::Numpad5) => Some(4),
+            Some(KeyCode::S) => {
+                // Constrain the soup to the next symmetry class
+                self.seeding_symmetry = self.seeding_symmetry.next();
+                return;
+            }
+            Some(KeyCode::Escape) => None,
+            // Keep the menu open until a choice is made
+            _ => return,
+        };
+        self.seeding_menu = false;
+        if let Some(index) = choice {
+            self.seed(DISTRIBUTIONS[index]);
+        }
+    }
+
+    /// Handle a key while the rule menu is open, switching to the chosen rule
+    fn rulebook_key(&mut self, key: Option<KeyCode>) {
+        let choice = match key {
+            Some(KeyCode::Key1 | KeyCode::Numpad1) => Some(0),
+            Some(KeyCode::Key2 | KeyCode::Numpad2) => Some(1),
+            Some(KeyCode::Key3 | KeyCode::Numpad3) => Some(2),
+            Some(KeyCode::Key4 | KeyCode::Numpad4) => Some(3),
+            Some(KeyCode::Key5 | KeyCode::Numpad5) => Some(4),
+            Some(KeyCode::Key6 | KeyCode::Numpad6) => Some(5),
+            Some(KeyCode::Key7 | KeyCode::Numpad7) => Some(6),
+            Some(KeyCode::Key8 | KeyCode::Numpad8) => Some(7),
+            Some(KeyCode::Escape) => None,
+            // Keep the menu open until a choice is made
+            _ => return,
+        };
+        self.rulebook_menu = false;
+        match choice {
+            Some(index) if index < rulebook::RULES.len() => self.switch_to_builtin_rule(index),
+            Some(index) => self.switch_to_automaton(index - rulebook::RULES.len()),
+            None => (),
+        }
+    }
+
+    /// Handle a key while the stamp menu is open, picking the chosen stamp
+    fn stamp_menu_key(&mut self, key: Option<KeyCode>) {
+        let choice = match key {
+            Some(KeyCode::Escape) => None,
+            _ => match stamps::index_for(key) {
+                Some(index) => Some(index),
+                // Keep the menu open until a choice is made
+                None => return,
+            },
+        };
+        self.stamp_menu = false;
+        if let Some(index) = choice {
+            self.pick_stamp(index);
+        }
+    }
+
+    /// Handle a key while a stamp is held, returning whether it was used
+    fn stamp_key(&mut self, key: Option<KeyCode>) -> bool {
+        match key {
+            Some(KeyCode::R) => self.stamp = self.stamp.as_ref().map(Pattern::rotated),
+            Some(KeyCode::S) => self.open_scatter_dialog(),
+            Some(KeyCode::Escape) => self.stamp = None,
+            _ => match stamps::index_for(key) {
+                Some(index) => self.pick_stamp(index),
+                None => return false,
+            },
+        }
+        true
+    }
+
+    /// Handle copying, cutting and pasting the selection, returning whether the key was used
+    fn selection_key(&mut self, input: ggez::input::keyboard::KeyInput) -> bool {
+        let ctrl = input.mods.contains(ggez::input::keyboard::KeyMods::CTRL);
+        match (input.keycode, self.selection) {
+            (Some(KeyCode::C), Some(region)) if ctrl => {
+                self.copied = Some(self.universe.copy_region(region));
+                self.toasts.show(tr(Message::ToastSelectionCopied));
+            }
+            (Some(KeyCode::X), Some(region)) if ctrl => {
+                self.copied = Some(self.universe.copy_region(region));
+                self.remember();
+                self.universe.clear_region(region);
+                self.toasts.show(tr(Message::ToastSelectionCut));
+            }
+            (Some(KeyCode::C), None) if ctrl => self.copy_rle(),
+            // Keep the plain key from opening the rulebook
+            (Some(KeyCode::X), None) if ctrl => self.toasts.show(tr(Message::ToastNothingSelected)),
+            (Some(KeyCode::Escape), Some(_)) => self.selection = None,
+            (Some(KeyCode::V), _) if ctrl => match &self.copied {
+                // Held like a stamp, so that the preview follows the cursor until a click
+                Some(pattern) => {
+                    self.stamp = Some(pattern.clone());
+                    self.selection = None;
+                }
+                None => self.toasts.show(tr(Message::ToastNothingToPaste)),
+            },
+            _ => return false,
+        }
+        true
+    }
+
+    /// Hold a built-in stamp, to place it with clicks
+    fn pick_stamp(&mut self, index: usize) {
+        let stamp = &STAMPS[index];
+        match stamp.pattern() {
+            Ok(pattern) => {
+                self.stamp = Some(pattern);
+                self.toasts
+                    .show(format!("{}: {}", tr(Message::Stamp), stamp.name));
+            }
+            Err(e) => self.report_error(tr_with(Message::ErrorCannotLoadStamp, &[&stamp.name, &e])),
+        }
+    }
+
+    /// Bring to life or kill the cells under the brush centered on a cell
+    fn paint(&mut self, center: (usize, usize), alive: bool) {
+        let (width, height) = (self.universe.width(), self.universe.height());
+        for (x, y) in self.brush.footprint(center, width, height) {
+            self.universe.set(x, y, alive);
+        }
+    }
+
+    /// Switch to the next or previous brush
+    fn cycle_brush(&mut self, forward: bool) {
+        self.brush = if forward {
+            self.brush.next()
+        } else {
+            self.brush.previous()
+        };
+        self.toasts.show(format!(
+            "{}: {}",
+            tr(Message::ToastBrush),
+            self.brush.describe()
+        ));
+    }
+
+    /// Ask how many copies of the held stamp to scatter
+    fn open_scatter_dialog(&mut self) {
+        self.prompt = Some((
+            Prompt::new(
+                tr(Message::ScatterPrompt).to_string(),
+                stamps::DEFAULT_SCATTER.to_string(),
+            ),
+            PromptPurpose::Scatter,
+        ));
+    }
+
+    /// Place copies of the held stamp at random free spots, in random orientations
+    fn scatter_stamp(&mut self, count: usize) {
+        let Some(pattern) = self.stamp.clone() else {
+            return;
+        };
+        self.remember();
+        // Not a soup, so it leaves the soup seed and its toast alone
+        let mut rng = rand::thread_rng();
+        let copies = stamps::scatter(&pattern, self.universe.cells(), count, &mut rng);
+        for (copy, left, top) in &copies {
+            self.universe.place(copy, *left, *top);
+            self.placements.record(copy, *left, *top);
+        }
+        info!(count, placed = copies.len(), "scattered copies of a stamp");
+        self.toasts.show(format!(
+            "{}: {} / {}",
+            tr(Message::ToastScattered),
+            copies.len(),
+            count
+        ));
+    }
+
+    /// Place the held stamp centered on a cell, over whatever is there
+    fn place_stamp(&mut self, cell: (usize, usize)) {
+        let Some(pattern) = self.stamp.take() else {
+            return;
+        };
+        self.remember();
+        let (left, top) = stamps::top_left(&pattern, cell);
+        self.universe.place(&pattern, left, top);
+        self.placements.record(&pattern, left, top);
+        self.stamp = Some(pattern);
+    }
+
+    /// Switch to a built-in rule and seed its demo, paused so that its
+    /// description can be read first
+    fn switch_to_builtin_rule(&mut self, index: usize) {
+        let entry = &rulebook::RULES[index];
+        let rule = match Rule::parse(entry.rule) {
+            Ok(rule) => rule,
+            Err(e) => {
+                return self.report_error(tr_with(Message::ErrorCannotSwitch, &[&entry.name, &e]))
+            }
+        };
+        info!(name = entry.name, rule = %rule, "switched to a built-in rule");
+        self.universe.set_rule(rule);
+        self.multistate.set_automaton(None);
+        self.replaced_rule = None;
+        match entry.demo {
+            Demo::Pattern(text) => match Pattern::parse(text) {
+                Ok(pattern) => self.load_pattern(&pattern),
+                Err(e) => self.report_error(tr_with(Message::ErrorCannotSeed, &[&entry.name, &e])),
+            },
+            Demo::Soup(distribution) => {
+                self.universe.clear();
+                self.seed(distribution);
+            }
+        }
+        self.paused = true;
+        self.rule_notes = Some(index);
+    }
+
+    /// Switch to a multi-state automaton and seed its demo in the middle,
+    /// paused so that it can be looked at first
+    fn switch_to_automaton(&mut self, index: usize) {
+        let automaton = AUTOMATA[index];
+        info!(
+            name = automaton.name(),
+            "switched to a multi-state automaton"
+        );
+        self.fuzzy.enabled = false;
+        self.multistate.set_automaton(Some(automaton));
+        self.rule_notes = None;
+        let demo = automaton.demo();
+        let (width, height) = (demo.iter().map(Vec::len).max().unwrap_or(0), demo.len());
+        let left = self.universe.width().saturating_sub(width) / 2;
+        let top = self.universe.height().saturating_sub(height) / 2;
+        self.universe.clear();
+        self.multistate.sync(self.universe.cells());
+        self.multistate.place(&demo, left, top);
+        self.universe.load(&self.multistate.cells());
+        self.reset_history();
+        self.paused = true;
+        self.toasts.show(format!(
+            "{}: {}",
+            automaton.name(),
+            tr(automaton.description())
+        ));
+    }
+
+    /// Cycle the state of the cell under the mouse in a multi-state automaton
+    fn cycle_state_under_mouse(&mut self, ctx: &Context) {
+        if !self.multistate.is_enabled() {
+            self.toasts.show(tr(Message::ToastNoMultiState));
+            return;
+        }
+        let position = ctx.mouse.position();
+        let Some((x, y)) = self.cell_under(position.x, position.y) else {
+            return;
+        };
+        self.remember();
+        self.multistate.sync(self.universe.cells());
+        let alive = self.multistate.cycle(x, y);
+        self.universe.set(x, y, alive);
+    }
+
+    /// Seed today's soup, which is the same for everyone on the same UTC day
+    fn daily_soup(&mut self) {
+        self.remember();
+        let date = Date::today();
+        self.universe.fill_random(0.5, &mut date.rng());
+        self.reset_history();
+        self.daily = Some(date);
+        info!(date = %date, "seeded the daily soup");
+    }
+
+    /// Set cells to a random state, but with a much lower probability of being alive
+    fn randomize_sparse(&mut self) {
+        self.remember();
+        let mut rng = self.soup_rng();
+        self.universe.fill_random(0.1, &mut rng);
+        self.reset_history();
+    }
+
+    /// Decrease the update delay step
+    fn decrease_update_delay_step(&mut self) {
+        self.speed_step_percent = self
+            .speed_step_percent
+            .saturating_sub(SPEED_STEP_CHANGE_PERCENT)
+            .max(MIN_SPEED_STEP_PERCENT);
+        self.toast_speed_step();
+    }
+
+    /// Increase the update delay step
+    fn increase_update_delay_step(&mut self) {
+        self.speed_step_percent =
+            (self.speed_step_percent + SPEED_STEP_CHANGE_PERCENT).min(MAX_SPEED_STEP_PERCENT);
+        self.toast_speed_step();
+    }
+
+    /// Factor the update delay is multiplied by to slow down, and divided by to speed up
+    fn speed_step_factor(&self) -> f64 {
+        1.0 + f64::from(self.speed_step_percent) / 100.0
+    }
+
+    /// Lengthen the update delay by the step, a fraction of the delay, so that
+    /// every speed is as many key presses away from the next
+    fn increase_update_delay(&mut self) {
+        let longer = self.update_delay.as_secs_f64() * self.speed_step_factor();
+        self.update_delay = Duration::try_from_secs_f64(longer).unwrap_or(self.update_delay);
+        self.toast_speed();
+    }
+
+    /// Shorten the update delay by the step, down to the shortest delay
+    fn decrease_update_delay(&mut self) {
+        let shorter = self.update_delay.as_secs_f64() * 1000.0 / self.speed_step_factor();
+        self.update_delay = tick::delay_from_ms(shorter);
+        self.toast_speed();
+    }
+
+    /// Reset update delay to default
+    fn reset_update_delay(&mut self) {
+        self.update_delay = DEFAULT_UPDATE_DELAY;
+        self.speed_step_percent = DEFAULT_SPEED_STEP_PERCENT;
+        self.toast_speed();
+    }
+
+    /// Show how visible the background image is now
+    fn toast_background_opacity(&mut self) {
+        if let Some(background) = &self.background {
+            let percent = (background.opacity() * 100.0).round();
+            self.toasts
+                .show(format!("{}: {}%", tr(Message::ToastBackground), percent));
+        }
+    }
+
+    /// Switch fuzzy Life on or off, starting over from the dead and live cells
+    fn toggle_fuzzy(&mut self) {
+        self.fuzzy.enabled = !self.fuzzy.enabled;
+        self.fuzzy.clear();
+        if self.fuzzy.enabled {
+            self.multistate.set_automaton(None);
+        }
+        let message = if self.fuzzy.enabled {
+            Message::ToastFuzzyOn
+        } else {
+            Message::ToastFuzzyOff
+        };
+        self.toasts.show(tr(message));
+    }
+
+    fn toast_smoothing(&mut self) {
+        let percent = (self.fuzzy.smoothing() * 100.0).round();
+        self.toasts
+            .show(format!("{}: {}%", tr(Message::ToastSmoothing), percent));
+    }
+
+    /// Show the speed the simulation runs at now
+    fn toast_speed(&mut self) {
+        self.toasts.show(format!(
+            "{}: {:.1} {}",
+            tr(Message::ToastSpeed),
+            1.0 / self.update_delay.as_secs_f64(),
+            tr(Message::GensPerSecond)
+        ));
+    }
+
+    /// Show how much the speed keys change the update delay
+    fn toast_speed_step(&mut self) {
+        self.toasts.show(format!(
+            "{}: {}%",
+            tr(Message::ToastSpeedStep),
+            self.speed_step_percent
+        ));
+    }
+
+    /// The settings to restore on the next launch
+    fn session(&self) -> Session {
+        Session {
+            grid_width: self.universe.width(),
+            grid_height: self.universe.height(),
+            update_delay_ms: self.update_delay.as_secs_f64() * 1000.0,
+            speed_step_percent: self.speed_step_percent,
+            view: self.view,
+            theme: self.theme,
+        }
+    }
+
+    /// Copy the grid and settings aside, to be saved by the panic hook
+    fn record_snapshot(&mut self) {
+        let state = State {
+            grid: self.universe.cells().to_vec(),
+            labels: self.annotations.labels().to_vec(),
         };
+        emergency::record(state, self.session());
+        self.snapshot_at = Instant::now();
+    }
 
-        // Initialize the grid with a simple pattern (e.g., a glider)
-        s.grid[1][2] = true;
-        s.grid[2][3] = true;
-        s.grid[3][1] = true;
-        s.grid[3][2] = true;
-        s.grid[3][3] = true;
+    /// Approximate memory used by the larger buffers, for the performance overlay
+    fn memory_usage(&self) -> Vec<(&'static str, usize)> {
+        vec![
+            (tr(Message::MemoryGrid), self.universe.memory_usage()),
+            (tr(Message::MemoryHistory), self.spacetime.memory_usage()),
+            (tr(Message::MemoryPeriod), self.period.memory_usage()),
+            (tr(Message::MemoryLifespans), self.lifespans.memory_usage()),
+            (tr(Message::MemoryRewind), self.rewind.memory_usage()),
+        ]
+    }
 
-        Ok(s)
+    /// Run part of the game loop and record how long it took in the performance overlay
+    fn timed<T>(&mut self, system: &'static str, f: impl FnOnce(&mut MainState) -> T) -> T {
+        let started = Instant::now();
+        let result = f(self);
+        self.perf.record(system, started.elapsed());
+        result
     }
 
-    /// Count the live neighbors of a cell.
-    fn live_neighbor_count(&self, x: usize, y: usize) -> usize {
-        let mut count = 0;
-        // Check the 3x3 grid around the cell
-        // The following code wraps around the edges of the grid.
-        // This is a common technique in Game of Life implementations.
-        // However, it is not the only way to handle the edges.
-        // Infact, the more consistent way is to ignore the edges, because the Game of Life is played on an infinite grid.
-        let xs = [x.wrapping_sub(1), x, x + 1];
-        let ys = [y.wrapping_sub(1), y, y + 1];
-
-        for &i in &ys {
-            if i >= GRID_HEIGHT {
-                continue;
-            }
-            for &j in &xs {
-                if j >= GRID_WIDTH || (i == y && j == x) {
-                    continue;
-                }
-                if self.grid[i][j] {
-                    count += 1;
-                }
+    /// Draw the sandbox in the current view, with the panels that belong to it
+    fn draw_view(&mut self, ctx: &mut Context, canvas: &mut Canvas) -> GameResult {
+        match self.view {
+            ViewMode::Flat => self.draw_flat(ctx, canvas)?,
+            ViewMode::SpaceTime => self.timed("draw/spacetime", |s| {
+                s.spacetime.draw(ctx, canvas, s.universe.grid())
+            }),
+            ViewMode::Isometric => self.timed("draw/isometric", |s| {
+                isometric::draw(ctx, canvas, s.universe.cells())
+            }),
+        }
+        if self.show_lifespans {
+            self.timed("draw/lifespans", |s| s.lifespans.draw(ctx, canvas))?;
+        }
+        if let Some(tutorial) = &self.tutorial {
+            tutorial.draw(ctx, canvas)?;
+        }
+        if let Some(puzzle) = &self.puzzle {
+            puzzle.draw(ctx, canvas, self.universe.generation())?;
+        }
+        if let Some(date) = self.daily {
+            self.draw_daily_label(ctx, canvas, date)?;
+        }
+        if let Some(index) = self.rule_notes.filter(|_| self.paused) {
+            let entry = &rulebook::RULES[index];
+            // Only while the demo still runs under the rule it was seeded for
+            if Rule::parse(entry.rule).is_ok_and(|rule| rule == self.universe.rule()) {
+                rulebook::draw_notes(ctx, canvas, entry)?;
             }
         }
-
-        count
+        if self.hud.visible {
+            let status = hud::Status {
+                generation: self.universe.generation(),
+                population: self.universe.population(),
+                gens_per_second: 1.0 / self.update_delay.as_secs_f64(),
+                paused: self.paused,
+                toroidal: self.universe.is_toroidal(),
+            };
+            self.hud.draw(ctx, canvas, &status)?;
+        }
+        Ok(())
     }
 
-    /// Update the grid based on Game of Life rules.
-    fn update_grid(&mut self) {
-        for y in 0..GRID_HEIGHT {
-            for x in 0..GRID_WIDTH {
-                let live_neighbors = self.live_neighbor_count(x, y);
-                self.next_grid[y][x] = match (self.grid[y][x], live_neighbors) {
-                    // Rule 1: Any live cell with two or three live neighbours survives.
-                    (true, 2) | (true, 3) => true,
-                    // Rule 2: Any dead cell with three live neighbours becomes a live cell.
-                    (false, 3) => true,
-                    // Rule 3: All other live cells die in the next generation. Similarly, all other dead cells stay dead.
-                    _ => false,
-                };
+    /// Draw the current generation top-down.
+    fn draw_flat(&mut self, ctx: &mut Context, canvas: &mut Canvas) -> GameResult {
+        canvas.set_screen_coordinates(self.view_rect(ctx));
+        self.timed("draw/background", |s| {
+            if let Some(background) = &s.background {
+                background.draw(canvas, s.universe.width(), s.universe.height(), s.cell);
+            }
+        });
+        if self.heat.visible {
+            self.timed("draw/heat", |s| {
+                s.heat.draw(ctx, canvas, &s.universe, s.cell)
+            })?;
+        }
+        self.timed("draw/cells", |s| s.draw_cells(canvas));
+        if self.trails.enabled {
+            self.timed("draw/trails", |s| s.trails.draw(ctx, canvas, s.cell))?;
+        }
+        if self.universe.is_toroidal() {
+            self.draw_wrap_ghosts(ctx, canvas)?;
+        }
+        if self.emitters.enabled && !self.emitters.is_empty() {
+            self.emitters.draw(ctx, canvas, self.cell)?;
+        }
+        if !self.universe.frozen().is_empty() {
+            self.draw_frozen(ctx, canvas)?;
+        }
+        if let Some(check) = &self.cross_check {
+            check.draw(ctx, canvas, self.cell)?;
+        }
+        if let Some(overlay) = self.overlay.as_ref().filter(|overlay| overlay.visible) {
+            overlay.draw(ctx, canvas, self.cell, self.universe.cells())?;
+        }
+        self.annotations.draw(ctx, canvas, self.cell)?;
+        if let Some(measure) = &self.measure {
+            measure.draw(ctx, canvas, self.cell)?;
+        }
+        if self.placements.visible {
+            self.placements.draw_outline(ctx, canvas, self.cell)?;
+        }
+        if let Some((action, start, _)) = self.dragging {
+            let position = ctx.mouse.position();
+            if let Some(end) = self.cell_under(position.x, position.y) {
+                if matches!(action, MouseAction::Freeze | MouseAction::Select) {
+                    let region = Region::spanning(start, end);
+                    mouse::draw_region_preview(ctx, canvas, self.cell, region)?;
+                } else {
+                    mouse::draw_line_preview(ctx, canvas, self.cell, start, end)?;
+                }
+            }
+        }
+        if let Some(region) = self.selection {
+            mouse::draw_selection(ctx, canvas, self.cell, region)?;
+        }
+        if let Some(pattern) = &self.stamp {
+            let position = ctx.mouse.position();
+            if let Some(cell) = self.cell_under(position.x, position.y) {
+                let corner = stamps::top_left(pattern, cell);
+                stamps::draw_preview(ctx, canvas, self.cell, pattern, corner)?;
+            }
+        } else if self.brush != Brush::SINGLE && self.dragging.is_none() {
+            let position = ctx.mouse.position();
+            if let Some(cell) = self.cell_under(position.x, position.y) {
+                let size = (self.universe.width(), self.universe.height());
+                self.brush
+                    .draw_footprint(ctx, canvas, self.cell, cell, size)?;
             }
         }
+        if self.ships.enabled {
+            self.timed("draw/ships", |s| s.draw_ships(ctx, canvas))?;
+        }
+        let grid = self.cell.rect(
+            0.0,
+            0.0,
+            self.universe.width() as f32,
+            self.universe.height() as f32,
+        );
+        self.border.draw(ctx, canvas, self.view_rect(ctx), grid)?;
 
-        // Swap grids for next iteration
-        std::mem::swap(&mut self.grid, &mut self.next_grid);
+        // Everything else stays put while the grid is zoomed and panned
+        let (window_width, window_height) = ctx.gfx.drawable_size();
+        canvas.set_screen_coordinates(graphics::Rect::new(0.0, 0.0, window_width, window_height));
+        if self.placements.visible {
+            self.placements.draw_list(ctx, canvas)?;
+        }
+        if let Some(overlay) = self.overlay.as_ref().filter(|overlay| overlay.visible) {
+            overlay.draw_summary(canvas, self.universe.cells());
+        }
+        Ok(())
     }
 
-    /// Toggle the paused state
-    fn toggle_pause(&mut self) {
-        self.paused = !self.paused;
+    /// Show the date of the daily soup and how far it has come, for sharing
+    fn draw_daily_label(&self, ctx: &mut Context, canvas: &mut Canvas, date: Date) -> GameResult {
+        let label = graphics::Text::new(format!(
+            "{} {}  {}: {}  {}: {}",
+            tr(Message::DailySoup),
+            date,
+            tr(Message::Generation),
+            self.universe.generation(),
+            tr(Message::Population),
+            self.universe.population()
+        ));
+        let size = label.measure(ctx)?;
+        let (window_width, window_height) = ctx.gfx.drawable_size();
+        canvas.draw(
+            &label,
+            graphics::DrawParam::default()
+                .dest([window_width - size.x - 8.0, window_height - size.y - 8.0])
+                .color(Color::CYAN),
+        );
+        Ok(())
     }
 
-    /// Toggle the state of a cell at a given position
-    fn toggle_cell(&mut self, x: usize, y: usize) {
-        if x < GRID_WIDTH && y < GRID_HEIGHT {
-            self.grid[y][x] = !self.grid[y][x];
+    /// Mark the seam of a torus and show, along each edge, faint ghosts of the live
+    /// cells at the opposite edge, which are neighbors of the cells next to them.
+    fn draw_wrap_ghosts(&self, ctx: &mut Context, canvas: &mut Canvas) -> GameResult {
+        let (width, height) = (self.universe.width(), self.universe.height());
+        let (right, bottom) = (width as f32, height as f32);
+        let cells = self.universe.cells();
+        let (first, last) = (&cells[0], &cells[height - 1]);
+        let mut rects = Vec::new();
+        for (x, (&top, &bottom_alive)) in first.iter().zip(last).enumerate() {
+            let column = x as f32;
+            if bottom_alive {
+                rects.push(self.cell.rect(column, 0.0, 1.0, WRAP_GHOST_DEPTH));
+            }
+            if top {
+                let y = bottom - WRAP_GHOST_DEPTH;
+                rects.push(self.cell.rect(column, y, 1.0, WRAP_GHOST_DEPTH));
+            }
+        }
+        for (y, row) in cells.iter().enumerate() {
+            let line = y as f32;
+            if row[width - 1] {
+                rects.push(self.cell.rect(0.0, line, WRAP_GHOST_DEPTH, 1.0));
+            }
+            if row[0] {
+                let x = right - WRAP_GHOST_DEPTH;
+                rects.push(self.cell.rect(x, line, WRAP_GHOST_DEPTH, 1.0));
+            }
+        }
+
+        let mut ghosts = MeshBuilder::new();
+        for rect in rects {
+            ghosts.rectangle(DrawMode::fill(), rect, WRAP_GHOST_COLOR)?;
         }
+        ghosts.rectangle(
+            DrawMode::stroke(2.0),
+            self.cell.rect(0.0, 0.0, right, bottom),
+            WRAP_SEAM_COLOR,
+        )?;
+        canvas.draw(
+            &Mesh::from_data(ctx, ghosts.build()),
+            graphics::DrawParam::default(),
+        );
+        Ok(())
     }
 
-    /// Set cells to a random state
-    fn randomize(&mut self) {
-        for y in 0..GRID_HEIGHT {
-            for x in 0..GRID_WIDTH {
-                self.grid[y][x] = random();
-            }
+    /// Tint the frozen regions, whose cells keep their state.
+    fn draw_frozen(&self, ctx: &mut Context, canvas: &mut Canvas) -> GameResult {
+        let mut builder = MeshBuilder::new();
+        for region in self.universe.frozen() {
+            let rect = self.cell.rect(
+                region.left as f32,
+                region.top as f32,
+                region.width as f32,
+                region.height as f32,
+            );
+            builder.rectangle(DrawMode::fill(), rect, FROZEN_COLOR)?;
+            builder.rectangle(DrawMode::stroke(1.0), rect, FROZEN_OUTLINE_COLOR)?;
         }
+        canvas.draw(
+            &Mesh::from_data(ctx, builder.build()),
+            graphics::DrawParam::default(),
+        );
+        Ok(())
     }
 
-    /// Set cells to a random state, but with a much lower probability of being alive
-    fn randomize_sparse(&mut self) {
-        for y in 0..GRID_HEIGHT {
-            for x in 0..GRID_WIDTH {
-                self.grid[y][x] = random::<f32>() < 0.1;
-            }
+    /// Outline detected ships and show how many there are.
+    fn draw_ships(&self, ctx: &mut Context, canvas: &mut Canvas) -> GameResult {
+        self.ships.draw(ctx, canvas, self.cell)?;
+        let counts = graphics::Text::new(format!(
+            "{}: {}  {}: {}",
+            tr(Message::Gliders),
+            self.ships.count(Ship::Glider),
+            tr(Message::Lwss),
+            self.ships.count(Ship::Lwss)
+        ));
+        canvas.draw(
+            &counts,
+            graphics::DrawParam::default()
+                .dest([8.0, 8.0])
+                .color(Color::YELLOW),
+        );
+        Ok(())
+    }
+
+    /// Draw one rectangle per live cell.
+    fn draw_cells(&mut self, canvas: &mut Canvas) {
+        // One instance of the white unit square per live cell, all drawn in a single call
+        let cell = self.cell;
+        if let Some(automaton) = self.multistate.automaton() {
+            self.multistate.sync(self.universe.cells());
+            // Every cell that is not empty, in the color of its state
+            self.cell_instances
+                .set(
+                    self.multistate
+                        .states()
+                        .iter()
+                        .enumerate()
+                        .flat_map(|(y, row)| {
+                            row.iter()
+                                .enumerate()
+                                .filter(|(_, &state)| state != multistate::EMPTY)
+                                .map(move |(x, &state)| {
+                                    graphics::DrawParam::default()
+                                        .dest(cell.point(x as f32, y as f32))
+                                        .scale([cell.width, cell.height])
+                                        .color(automaton.color(state))
+                                })
+                        }),
+                );
+            canvas.draw(&self.cell_instances, graphics::DrawParam::default());
+            return;
         }
+        if self.fuzzy.enabled && !self.fuzzy.states().is_empty() {
+            // Every cell that is not quite dead, as bright as it is alive
+            self.cell_instances
+                .set(self.fuzzy.states().iter().enumerate().flat_map(|(y, row)| {
+                    row.iter()
+                        .enumerate()
+                        .filter(|(_, &state)| state > FUZZY_VISIBLE)
+                        .map(move |(x, &state)| {
+                            graphics::DrawParam::default()
+                                .dest(cell.point(x as f32, y as f32))
+                                .scale([cell.width, cell.height])
+                                .color(Color::new(state, state, state, 1.0))
+                        })
+                }));
+            canvas.draw(&self.cell_instances, graphics::DrawParam::default());
+            return;
+        }
+        let (lifespans, color_by_age, alive) =
+            (&self.lifespans, self.color_by_age, self.theme.cells());
+        self.cell_instances.set(
+            self.universe
+                .cells()
+                .iter()
+                .enumerate()
+                .flat_map(|(y, row)| {
+                    row.iter()
+                        .enumerate()
+                        .filter(|(_, &alive)| alive)
+                        .map(move |(x, _)| {
+                            let color = if color_by_age {
+                                lifespan::age_color(lifespans.age(x, y))
+                            } else {
+                                alive
+                            };
+                            graphics::DrawParam::default()
+                                .dest(cell.point(x as f32, y as f32))
+                                .scale([cell.width, cell.height])
+                                .color(color)
+                        })
+                }),
+        );
+        canvas.draw(&self.cell_instances, graphics::DrawParam::default());
     }
+}
 
-    /// Decrease the update delay step
-    fn decrease_update_delay_step(&mut self) {
-        if self.change_update_delay > Duration::from_millis(10) {
-            self.change_update_delay -= Duration::from_millis(10);
+impl EventHandler for MainState {
+    fn update(&mut self, _ctx: &mut Context) -> GameResult {
+        let started = Instant::now();
+        let generation = self.universe.generation();
+        self.timed("update/download", |s| s.finish_download());
+        self.timed("update/catagolue", |s| s.finish_catagolue());
+        self.timed("update/spectate", |s| s.follow_stream(_ctx))?;
+        if let Err(e) = self.alarm.play_due(_ctx) {
+            warn!("cannot play the alarm: {}", e);
+        }
+        match self.recorder.poll() {
+            Some(Ok(path)) => {
+                info!(path = %path.display(), "saved a recording");
+                self.toast_path(Message::ToastExported, &path);
+            }
+            Some(Err(e)) => self.report_error(tr_with(Message::ErrorCannotSaveRecording, &[&e])),
+            None => (),
+        }
+        if self.panel.visible && !self.presenting && !self.editing {
+            self.timed("update/panel", |s| s.update_panel(_ctx))?;
+        }
+        if self.tutorial.as_ref().is_some_and(Tutorial::is_over) {
+            self.tutorial = None;
+            Tutorial::mark_seen();
+        }
+        // Spectators take their generations from the stream alone
+        if self.paused || self.editing || self.spectator.is_some() {
+            self.tick.reset();
+        } else {
+            // Frames keep coming at full rate; generations follow the tick source
+            self.tick.frame(_ctx.time.delta());
+            let stepping = Instant::now();
+            for _ in 0..MAX_STEPS_PER_FRAME {
+                if stepping.elapsed() >= STEP_BUDGET {
+                    break;
+                }
+                if let Some(delay) = self.schedule.delay_at(self.universe.generation()) {
+                    self.update_delay = delay;
+                }
+                if self.paused || !self.tick.take(self.update_delay) {
+                    break;
+                }
+                self.run_generation();
+            }
+            self.tick.end_frame(self.update_delay);
+        }
+        if self.following && self.view == ViewMode::Flat {
+            self.timed("update/follow", |s| s.follow_live_cells(_ctx));
         }
+        if self.ships.enabled {
+            self.timed("update/ships", |s| s.ships.scan(s.universe.cells()));
+        }
+        if !self.screensaver && self.snapshot_at.elapsed() >= SNAPSHOT_INTERVAL {
+            self.timed("update/snapshot", |s| s.record_snapshot());
+        }
+        if let Some(metrics) = &mut self.metrics {
+            // Loading a pattern goes back to generation 0, which is not a step
+            let generations = self.universe.generation().saturating_sub(generation);
+            metrics.observe(&self.universe, self.paused, _ctx.time.delta(), generations);
+        }
+        self.perf.record("update", started.elapsed());
+        Ok(())
     }
 
-    /// Increase the update delay step
-    fn increase_update_delay_step(&mut self) {
-        if self.change_update_delay < Duration::from_millis(100) {
-            self.change_update_delay += Duration::from_millis(10);
+    fn draw(&mut self, ctx: &mut Context) -> GameResult {
+        let started = Instant::now();
+        let mut canvas = Canvas::from_frame(ctx, self.theme.background());
+
+        if self.presenting {
+            self.draw_presentation(ctx, &mut canvas)?;
+            self.timed("draw/present", |_| canvas.finish(ctx))?;
+            self.perf.record("draw", started.elapsed());
+            return Ok(());
+        }
+        if self.editing {
+            self.timed("draw/editor", |s| s.editor.draw(ctx, &mut canvas))?;
+        } else {
+            self.draw_view(ctx, &mut canvas)?;
+        }
+        if self.perf.visible {
+            let memory = self.memory_usage();
+            self.perf.set_memory(memory);
+        }
+        self.achievements.draw(ctx, &mut canvas)?;
+        if self.census.visible {
+            self.census.draw(ctx, &mut canvas)?;
+        }
+        if self.gallery.visible {
+            self.gallery.draw(ctx, &mut canvas)?;
         }
+        if self.graph.visible {
+            self.graph.draw(ctx, &mut canvas)?;
+        }
+        self.recorder.draw_indicator(ctx, &mut canvas)?;
+        self.alarm.draw_flash(ctx, &mut canvas)?;
+        if self.seeding_menu {
+            seeding::draw_menu(ctx, &mut canvas, self.seeding_symmetry)?;
+        }
+        if self.rulebook_menu {
+            rulebook::draw_menu(ctx, &mut canvas)?;
+        }
+        if self.stamp_menu {
+            stamps::draw_menu(ctx, &mut canvas)?;
+        }
+        if self.panel.visible && !self.editing {
+            self.panel.draw(&mut canvas);
+        }
+        if let Some((prompt, _)) = &self.prompt {
+            prompt.draw(ctx, &mut canvas)?;
+        }
+        self.toasts.draw(ctx, &mut canvas)?;
+        self.timed("draw/overlay", |s| s.perf.draw(ctx, &mut canvas))?;
+
+        self.timed("draw/present", |_| canvas.finish(ctx))?;
+        self.perf.record("draw", started.elapsed());
+        Ok(())
     }
 
-    /// Increase the update delay
-    fn increase_update_delay(&mut self) {
-        self.update_delay += self.change_update_delay;
+    fn mouse_button_down_event(
+        &mut self,
+        _ctx: &mut Context,
+        button: MouseButton,
+        x: f32,
+        y: f32,
+    ) -> GameResult {
+        if self.screensaver {
+            _ctx.request_quit();
+            return Ok(());
+        }
+        if self.prompt.is_some() || self.presenting || self.panel.wants_pointer() {
+            return Ok(());
+        }
+        if self.editing {
+            if button == MouseButton::Left {
+                self.editor.toggle_at(_ctx, x, y);
+            }
+            return Ok(());
+        }
+        if self.stamp.is_some() && matches!(button, MouseButton::Left | MouseButton::Right) {
+            if button == MouseButton::Right {
+                self.stamp = None;
+            } else if let Some(cell) = self.cell_under(x, y) {
+                self.place_stamp(cell);
+            }
+            return Ok(());
+        }
+        if self.gallery.visible && button == MouseButton::Left {
+            if let Some(snapshot) = self.gallery.snapshot_at(_ctx, x, y) {
+                self.restore_from_gallery(snapshot);
+                return Ok(());
+            }
+        }
+        let action = self.mouse.action(button, _ctx.keyboard.active_mods());
+        if action == MouseAction::Pan {
+            self.panning = Some(button);
+            return Ok(());
+        }
+        let Some((grid_x, grid_y)) = self.cell_under(x, y) else {
+            return Ok(());
+        };
+        match action {
+            MouseAction::Toggle => {
+                if let Some(puzzle) = &mut self.puzzle {
+                    puzzle.toggle(&mut self.universe, grid_x, grid_y);
+                } else if let Some(measure) = &mut self.measure {
+                    measure.click(grid_x, grid_y);
+                } else if self.placements.visible {
+                    // Move the selected pattern to the cell
+                    self.placements.move_to(&mut self.universe, grid_x, grid_y);
+                } else {
+                    self.remember();
+                    // Keep painting whatever the first cell became, so crossing a cell twice does not flip it back
+                    let alive = if self.brush == Brush::SINGLE {
+                        self.universe.toggle(grid_x, grid_y)
+                    } else {
+                        let alive = !self.universe.cells()[grid_y][grid_x];
+                        self.paint((grid_x, grid_y), alive);
+                        alive
+                    };
+                    self.painting = Some((alive, (grid_x, grid_y), button));
+                    self.tutorial_observe(Action::ToggleCell);
+                }
+            }
+            MouseAction::Erase if self.puzzle.is_none() => {
+                self.remember();
+                self.paint((grid_x, grid_y), false);
+                self.painting = Some((false, (grid_x, grid_y), button));
+            }
+            MouseAction::Fill if self.puzzle.is_none() => {
+                self.remember();
+                self.universe.flood_fill(grid_x, grid_y);
+            }
+            MouseAction::Label if self.puzzle.is_none() => {
+                self.edit_label(grid_x, grid_y);
+            }
+            MouseAction::Line | MouseAction::Freeze if self.puzzle.is_none() => {
+                self.dragging = Some((action, (grid_x, grid_y), button));
+            }
+            MouseAction::Select if self.puzzle.is_none() => {
+                if self.paused {
+                    self.dragging = Some((action, (grid_x, grid_y), button));
+                } else {
+                    self.toasts.show(tr(Message::ToastPauseToSelect));
+                }
+            }
+            _ => {}
+        }
+        Ok(())
     }
 
-    /// Decrease the update delay if it is greater than the minimum delay
-    fn decrease_update_delay(&mut self) {
-        if self.update_delay > DEFAULT_UPDATE_DELAY
-            && (self.update_delay - self.change_update_delay) > DEFAULT_UPDATE_DELAY
+    fn mouse_button_up_event(
+        &mut self,
+        _ctx: &mut Context,
+        button: MouseButton,
+        x: f32,
+        y: f32,
+    ) -> GameResult {
+        if self.panning == Some(button) {
+            self.panning = None;
+        }
+        if self
+            .painting
+            .is_some_and(|(_, _, paint_button)| paint_button == button)
         {
-            self.update_delay -= self.change_update_delay;
+            self.painting = None;
+        }
+        let Some((action, start, drag_button)) = self.dragging else {
+            return Ok(());
+        };
+        if button != drag_button {
+            return Ok(());
+        }
+        self.dragging = None;
+        let Some(end) = self.cell_under(x, y) else {
+            return Ok(());
+        };
+        if action == MouseAction::Freeze {
+            // A click without dragging thaws the region under it
+            if start != end || !self.universe.thaw_at(end.0, end.1) {
+                self.universe.freeze(Region::spanning(start, end));
+            }
+        } else if action == MouseAction::Select {
+            self.selection = Some(Region::spanning(start, end));
+        } else {
+            self.remember();
+            for (cell_x, cell_y) in mouse::line(start, end) {
+                self.universe.set(cell_x, cell_y, true);
+            }
         }
+        Ok(())
     }
 
-    /// Reset update delay to default
-    fn reset_update_delay(&mut self) {
-        self.update_delay = DEFAULT_UPDATE_DELAY;
-        self.change_update_delay = DEFAULT_UPDATE_DELAY;
+    fn quit_event(&mut self, _ctx: &mut Context) -> GameResult<bool> {
+        if !self.screensaver {
+            match self.session().save() {
+                Ok(()) => debug!("saved the session"),
+                Err(e) => error!("cannot save the session: {}", e),
+            }
+        }
+        // Go ahead and quit
+        Ok(false)
     }
-}
 
-impl EventHandler for MainState {
-    fn update(&mut self, _ctx: &mut Context) -> GameResult {
-        if !self.paused {
-            self.update_grid();
-            timer::sleep(self.update_delay);
+    fn resize_event(&mut self, ctx: &mut Context, width: f32, height: f32) -> GameResult {
+        self.panel.resize(width, height);
+        if self.presenting {
+            self.fit_to_screen(ctx);
+        } else {
+            self.fit_to_window(ctx);
         }
-
         Ok(())
     }
 
-    fn draw(&mut self, ctx: &mut Context) -> GameResult {
-        let mut canvas = Canvas::from_frame(ctx, Color::BLACK);
-
-        for y in 0..GRID_HEIGHT {
-            for x in 0..GRID_WIDTH {
-                if self.grid[y][x] {
-                    let rect = Rect::new(
-                        x as f32 * CELL_SIZE,
-                        y as f32 * CELL_SIZE,
-                        CELL_SIZE,
-                        CELL_SIZE,
-                    );
-
-                    let cell =
-                        Mesh::new_rectangle(ctx, graphics::DrawMode::fill(), rect, Color::WHITE)?;
-                    canvas.draw(&cell, graphics::DrawParam::default());
-                }
-            }
+    fn focus_event(&mut self, _ctx: &mut Context, gained: bool) -> GameResult {
+        if !self.pause_on_focus_loss || self.screensaver {
+            return Ok(());
         }
+        if !gained && !self.paused {
+            self.paused = true;
+            self.paused_by_focus_loss = true;
+        } else if gained && self.paused_by_focus_loss {
+            self.paused = false;
+            self.paused_by_focus_loss = false;
+        }
+        Ok(())
+    }
 
-        canvas.finish(ctx)
+    fn text_input_event(&mut self, _ctx: &mut Context, character: char) -> GameResult {
+        if let Some((prompt, _)) = &mut self.prompt {
+            prompt.type_char(character);
+        } else {
+            self.panel.type_char(character);
+        }
+        Ok(())
     }
 
-    fn mouse_button_down_event(
+    fn mouse_motion_event(
         &mut self,
-        _ctx: &mut Context,
-        button: ggez::input::mouse::MouseButton,
+        ctx: &mut Context,
         x: f32,
         y: f32,
+        dx: f32,
+        dy: f32,
     ) -> GameResult {
-        if button == ggez::input::mouse::MouseButton::Left {
-            let grid_x = (x / CELL_SIZE) as usize;
-            let grid_y = (y / CELL_SIZE) as usize;
-            self.toggle_cell(grid_x, grid_y);
+        if self.screensaver {
+            // Ignore jitter and the synthetic motion some platforms send on startup
+            self.screensaver_mouse_travel += dx.hypot(dy);
+            if self.screensaver_mouse_travel > SCREENSAVER_MOUSE_THRESHOLD {
+                ctx.request_quit();
+            }
+        }
+        if self.panning.is_some() {
+            // Panning by hand takes over from following
+            self.following = false;
+            self.camera.pan(dx, dy);
+        }
+        if let Some((alive, last, button)) = self.painting {
+            if let Some(cell) = self.cell_under(x, y).filter(|&cell| cell != last) {
+                // Fill in the cells skipped by a fast drag
+                for center in mouse::line(last, cell) {
+                    self.paint(center, alive);
+                }
+                self.painting = Some((alive, cell, button));
+            }
+        }
+        Ok(())
+    }
+
+    fn mouse_wheel_event(&mut self, ctx: &mut Context, x: f32, y: f32) -> GameResult {
+        if self.screensaver {
+            ctx.request_quit();
+            return Ok(());
+        }
+        self.panel.scroll(x, y);
+        if self.presenting
+            || self.editing
+            || self.view != ViewMode::Flat
+            || self.panel.wants_pointer()
+        {
+            return Ok(());
         }
+        // Zoom around the cursor, one step per notch of the wheel
+        let position = ctx.mouse.position();
+        self.camera
+            .zoom_at(ZOOM_STEP.powf(y), position.x, position.y);
         Ok(())
     }
 
@@ -210,26 +2915,126 @@ impl EventHandler for MainState {
         input: ggez::input::keyboard::KeyInput,
         _repeated: bool,
     ) -> GameResult {
-        use ggez::input::keyboard::KeyCode;
+        if self.screensaver {
+            // Any key ends the screensaver
+            _ctx.request_quit();
+            return Ok(());
+        }
+        if self.presenting {
+            self.presentation_key(_ctx, input);
+            return Ok(());
+        }
+        if self.prompt.is_some() {
+            return self.prompt_key(_ctx, input.keycode);
+        }
+        if self.panel.wants_keyboard() {
+            // Typed into a field of the panel
+            return Ok(());
+        }
+        if self.puzzle.is_some() {
+            self.puzzle_key(_ctx, input.keycode);
+            return Ok(());
+        }
+        if self.editing {
+            self.editor_key(_ctx, input.keycode);
+            return Ok(());
+        }
+        if self.seeding_menu {
+            self.seeding_key(input.keycode);
+            return Ok(());
+        }
+        if self.rulebook_menu {
+            self.rulebook_key(input.keycode);
+            return Ok(());
+        }
+        if self.stamp_menu {
+            self.stamp_menu_key(input.keycode);
+            return Ok(());
+        }
+        if self.placements.visible && self.placements_key(input.keycode) {
+            return Ok(());
+        }
+        if self.stamp.is_some() && self.stamp_key(input.keycode) {
+            return Ok(());
+        }
+        if self.selection_key(input) {
+            return Ok(());
+        }
+        if let Some(index) = stamps::index_for(input.keycode) {
+            self.pick_stamp(index);
+            return Ok(());
+        }
         match input.keycode {
             Some(KeyCode::Space) => {
                 self.toggle_pause();
             }
+            Some(KeyCode::F) if input.mods.contains(ggez::input::keyboard::KeyMods::CTRL) => {
+                // Keep the live cells in the middle of the window
+                self.following = !self.following;
+                let message = if self.following {
+                    Message::ToastFollowing
+                } else {
+                    Message::ToastNotFollowing
+                };
+                self.toasts.show(tr(message));
+            }
+            Some(KeyCode::F) if input.mods.contains(ggez::input::keyboard::KeyMods::SHIFT) => {
+                // Switch between the rule as it is and fuzzy Life
+                self.toggle_fuzzy();
+            }
+            Some(KeyCode::F) => {
+                // Choose a pattern to stamp
+                self.stamp_menu = true;
+            }
+            Some(KeyCode::C) if input.mods.contains(ggez::input::keyboard::KeyMods::SHIFT) => {
+                // Load an object or soup from Catagolue
+                self.open_catagolue_dialog();
+            }
             Some(KeyCode::C) => {
-                // Clear the grid
-                self.grid = vec![vec![false; GRID_WIDTH]; GRID_HEIGHT];
+                self.clear_grid();
             }
             Some(KeyCode::Escape) => {
                 // Quit the game
                 _ctx.request_quit();
             }
+            Some(KeyCode::P) if input.mods.contains(ggez::input::keyboard::KeyMods::CTRL) => {
+                // Copy the seed of the last random soup
+                self.copy_seed();
+            }
             Some(KeyCode::P) => {
-                // Randomize the grid
-                self.randomize();
+                if input.mods.contains(ggez::input::keyboard::KeyMods::SHIFT) {
+                    // Seed the daily soup
+                    self.daily_soup();
+                } else {
+                    // Randomize the grid
+                    self.randomize();
+                }
             }
             Some(KeyCode::R) => {
-                // Randomize the grid sparsely
-                self.randomize_sparse();
+                if input.mods.contains(ggez::input::keyboard::KeyMods::SHIFT) {
+                    // Choose how to seed the grid
+                    self.seeding_menu = true;
+                } else {
+                    // Randomize the grid sparsely
+                    self.randomize_sparse();
+                }
+            }
+            Some(key @ (KeyCode::Up | KeyCode::Down | KeyCode::Left | KeyCode::Right))
+                if input.mods.contains(ggez::input::keyboard::KeyMods::CTRL) =>
+            {
+                // Pan the view
+                let (dx, dy) = match key {
+                    KeyCode::Up => (0.0, PAN_STEP),
+                    KeyCode::Down => (0.0, -PAN_STEP),
+                    KeyCode::Left => (PAN_STEP, 0.0),
+                    _ => (-PAN_STEP, 0.0),
+                };
+                self.following = false;
+                self.camera.pan(dx, dy);
+            }
+            Some(KeyCode::Home) => {
+                // Show the whole grid again
+                self.camera.reset();
             }
             Some(KeyCode::Up) => {
                 // Increase the update delay
@@ -251,8 +3056,296 @@ impl EventHandler for MainState {
                 // Decrease the update delay step
                 self.decrease_update_delay_step();
             }
+            Some(KeyCode::X) => {
+                if input.mods.contains(ggez::input::keyboard::KeyMods::SHIFT) {
+                    // Type in any Life-like rule
+                    self.open_rule_dialog();
+                } else {
+                    // Switch to a built-in rule with a demo
+                    self.rulebook_menu = true;
+                }
+            }
+            Some(KeyCode::Q) => {
+                // Pause or resume the configured emitters
+                self.emitters.enabled = !self.emitters.enabled;
+            }
+            Some(KeyCode::B) if input.mods.contains(ggez::input::keyboard::KeyMods::CTRL) => {
+                // Scrub back one generation, or ten with Shift
+                let shift = input.mods.contains(ggez::input::keyboard::KeyMods::SHIFT);
+                self.rewind_generations(if shift { REWIND_JUMP } else { 1 });
+            }
+            Some(KeyCode::B) if input.mods.contains(ggez::input::keyboard::KeyMods::SHIFT) => {
+                // Color the cells by how long they have been alive
+                self.color_by_age = !self.color_by_age;
+            }
+            Some(KeyCode::B) => {
+                // Color births by the direction they came from
+                self.trails.enabled = !self.trails.enabled;
+                self.trails.clear();
+            }
+            Some(KeyCode::Z) if input.mods.contains(ggez::input::keyboard::KeyMods::SHIFT) => {
+                // Cycle the state of the cell under the mouse, e.g. to put an electron on a wire
+                self.cycle_state_under_mouse(_ctx);
+            }
+            Some(KeyCode::Z) if input.mods.contains(ggez::input::keyboard::KeyMods::CTRL) => {
+                // Undo the last edit, or generation if those are recorded too
+                self.undo();
+            }
+            Some(KeyCode::Y) if input.mods.contains(ggez::input::keyboard::KeyMods::CTRL) => {
+                // Redo what was undone last
+                self.redo();
+            }
+            Some(KeyCode::Y) => {
+                if input.mods.contains(ggez::input::keyboard::KeyMods::SHIFT) {
+                    // Compare the grid with the pattern on the clipboard
+                    self.overlay_from_clipboard();
+                } else if let Some(overlay) = &mut self.overlay {
+                    // Show or hide the comparison
+                    overlay.visible = !overlay.visible;
+                }
+            }
+            Some(KeyCode::D) => {
+                // Detect and highlight gliders and lightweight spaceships
+                self.ships.enabled = !self.ships.enabled;
+            }
+            Some(KeyCode::F9) => {
+                // Start or stop recording an animated GIF
+                self.toggle_recording();
+            }
+            Some(KeyCode::F2) => {
+                // Show or hide the generation, population and speed
+                self.hud.visible = !self.hud.visible;
+            }
+            Some(KeyCode::F1) => {
+                // Show the buttons and sliders for what the shortcuts do
+                self.panel.visible = !self.panel.visible;
+            }
+            Some(KeyCode::F3) => {
+                // Show or hide the performance overlay
+                self.perf.visible = !self.perf.visible;
+            }
+            Some(KeyCode::PageUp) if input.mods.contains(ggez::input::keyboard::KeyMods::SHIFT) => {
+                // Smooth fuzzy Life further towards a continuous automaton
+                self.fuzzy.increase_smoothing();
+                self.toast_smoothing();
+            }
+            Some(KeyCode::PageDown)
+                if input.mods.contains(ggez::input::keyboard::KeyMods::SHIFT) =>
+            {
+                // Bring fuzzy Life closer to the rule as it is
+                self.fuzzy.decrease_smoothing();
+                self.toast_smoothing();
+            }
+            Some(KeyCode::PageUp) => {
+                // Make the background image more visible
+                if let Some(background) = &mut self.background {
+                    background.increase_opacity();
+                    self.toast_background_opacity();
+                }
+            }
+            Some(KeyCode::PageDown) => {
+                // Make the background image less visible
+                if let Some(background) = &mut self.background {
+                    background.decrease_opacity();
+                    self.toast_background_opacity();
+                }
+            }
+            Some(KeyCode::S) => {
+                if input.mods.contains(ggez::input::keyboard::KeyMods::SHIFT) {
+                    // Save everything as a named workspace
+                    self.open_workspace_dialog(PromptPurpose::SaveWorkspace);
+                } else {
+                    // Save the grid
+                    self.quicksave();
+                }
+            }
+            Some(KeyCode::L) => {
+                if input.mods.contains(ggez::input::keyboard::KeyMods::SHIFT) {
+                    // Reopen a named workspace
+                    self.open_workspace_dialog(PromptPurpose::OpenWorkspace);
+                } else {
+                    // Load the saved grid
+                    self.quickload();
+                }
+            }
+            Some(KeyCode::A) if input.mods.contains(ggez::input::keyboard::KeyMods::SHIFT) => {
+                // Copy the live cells as a Life 1.06 coordinate list
+                self.copy_life(|pattern, _| pattern.to_life106());
+            }
+            Some(KeyCode::A) if input.mods.contains(ggez::input::keyboard::KeyMods::CTRL) => {
+                // Copy the live cells in Life 1.05
+                self.copy_life(Pattern::to_life105);
+            }
+            Some(KeyCode::A) => {
+                // Copy the live cells as ASCII art
+                self.copy_ascii_art();
+            }
+            Some(KeyCode::H) if input.mods.contains(ggez::input::keyboard::KeyMods::ALT) => {
+                // Show or hide how many live cells surround every cell
+                self.heat.visible = !self.heat.visible;
+            }
+            Some(KeyCode::H) => {
+                if input.mods.contains(ggez::input::keyboard::KeyMods::SHIFT) {
+                    // Export the lifespan histogram
+                    self.export_lifespans();
+                } else {
+                    // Show or hide the lifespan histogram
+                    self.show_lifespans = !self.show_lifespans;
+                }
+            }
+            Some(KeyCode::I) if input.mods.contains(ggez::input::keyboard::KeyMods::SHIFT) => {
+                // Export the live cells as a picture
+                self.export_png();
+            }
+            Some(KeyCode::I) => {
+                // Export the detected period as a looping animation
+                self.export_period_gif();
+            }
+            Some(KeyCode::J) => {
+                let mods = input.mods;
+                if mods.contains(ggez::input::keyboard::KeyMods::SHIFT) {
+                    // Export the pattern as a Rust slice
+                    self.export_source(SourceLanguage::Rust);
+                } else if mods.contains(ggez::input::keyboard::KeyMods::CTRL) {
+                    // Export the pattern as a C array
+                    self.export_source(SourceLanguage::C);
+                } else {
+                    // Export the grid as JSON
+                    self.export_json();
+                }
+            }
+            Some(KeyCode::U) => {
+                // Open the pattern URL on the clipboard
+                self.open_url_from_clipboard();
+            }
+            Some(KeyCode::V) => {
+                // Cycle through the available views
+                self.cycle_view();
+            }
+            Some(KeyCode::N) if input.mods.contains(ggez::input::keyboard::KeyMods::SHIFT) => {
+                // Jump far ahead with the HashLife engine
+                self.super_step();
+            }
+            Some(KeyCode::N | KeyCode::Period) => {
+                // Advance one generation while paused
+                self.step_once();
+            }
+            Some(KeyCode::Tab) => {
+                // Cycle through the pattern files
+                let forward = !input.mods.contains(ggez::input::keyboard::KeyMods::SHIFT);
+                self.cycle_library(_ctx, forward);
+            }
+            Some(KeyCode::G) if input.mods.contains(ggez::input::keyboard::KeyMods::SHIFT) => {
+                // Show or hide the snapshots taken every few generations
+                self.gallery.visible = !self.gallery.visible;
+            }
+            Some(KeyCode::G) if input.mods.contains(ggez::input::keyboard::KeyMods::CTRL) => {
+                // Export the snapshots side by side
+                self.export_gallery();
+            }
+            Some(KeyCode::G) if input.mods.contains(ggez::input::keyboard::KeyMods::ALT) => {
+                // Show or hide the population of the last few hundred generations
+                self.graph.visible = !self.graph.visible;
+            }
+            Some(KeyCode::G) => {
+                // Load the Gosper glider gun
+                self.load_glider_gun();
+            }
+            Some(KeyCode::T) => {
+                if input.mods.contains(ggez::input::keyboard::KeyMods::SHIFT) {
+                    // Switch between bounded edges and edges that wrap around
+                    self.toggle_toroidal();
+                } else {
+                    // Start or close the tutorial
+                    self.toggle_tutorial();
+                }
+            }
+            Some(KeyCode::E) if input.mods.contains(ggez::input::keyboard::KeyMods::SHIFT) => {
+                // Export the live cells as an RLE file
+                self.export_rle();
+            }
+            Some(KeyCode::E) => {
+                // Switch to the pattern editor
+                self.editing = true;
+            }
+            Some(KeyCode::M) => {
+                // Start or stop measuring offsets between cells
+                self.measure = match self.measure {
+                    Some(_) => None,
+                    None => Some(Measure::new()),
+                };
+            }
+            Some(KeyCode::RBracket)
+                if input.mods.contains(ggez::input::keyboard::KeyMods::SHIFT) =>
+            {
+                // Paint with the next brush
+                self.cycle_brush(true);
+            }
+            Some(KeyCode::LBracket)
+                if input.mods.contains(ggez::input::keyboard::KeyMods::SHIFT) =>
+            {
+                // Paint with the previous brush
+                self.cycle_brush(false);
+            }
+            Some(KeyCode::RBracket) => {
+                // Grow the universe around its center
+                let (width, height) = (self.universe.width(), self.universe.height());
+                self.resize_universe(
+                    _ctx,
+                    width + RESIZE_STEP,
+                    height + RESIZE_STEP,
+                    Anchor::Center,
+                )?;
+            }
+            Some(KeyCode::LBracket) => {
+                // Shrink the universe around its center
+                let (width, height) = (self.universe.width(), self.universe.height());
+                let (width, height) = (
+                    width.saturating_sub(RESIZE_STEP),
+                    height.saturating_sub(RESIZE_STEP),
+                );
+                self.resize_universe(_ctx, width, height, Anchor::Center)?;
+            }
+            Some(KeyCode::W) if input.mods.contains(ggez::input::keyboard::KeyMods::SHIFT) => {
+                // Set the population limits that pause the simulation
+                self.open_alarm_dialog();
+            }
+            Some(KeyCode::W) => {
+                // Choose the size of the universe
+                self.open_resize_dialog();
+            }
+            Some(KeyCode::O) => {
+                // Show the patterns placed this session
+                self.placements.visible = true;
+            }
+            Some(KeyCode::Back) => {
+                // Undo the rule change of the last loaded pattern
+                self.revert_rule();
+            }
+            Some(KeyCode::K) if input.mods.contains(ggez::input::keyboard::KeyMods::SHIFT) => {
+                // Show or hide the census of the objects on the grid
+                self.census.visible = !self.census.visible;
+                if self.census.visible {
+                    self.take_census();
+                }
+            }
+            Some(KeyCode::K) => {
+                // Show or hide the discovery log
+                self.achievements.visible = !self.achievements.visible;
+            }
             _ => (),
         }
         Ok(())
     }
 }
+
+/// Parse a size such as `160x120`, optionally followed by an anchor such as `top-left`.
+fn parse_size_and_anchor(text: &str) -> Option<(usize, usize, Anchor)> {
+    let mut words = text.split_whitespace();
+    let (width, height) = words.next()?.split_once('x')?;
+    let anchor = match words.next() {
+        Some(name) => Anchor::from_name(name)?,
+        None => Anchor::Center,
+    };
+    Some((width.parse().ok()?, height.parse().ok()?, anchor))
+}
@@ -0,0 +1,96 @@
+use ggez::graphics::{Canvas, Color, DrawMode, DrawParam, Mesh, Rect, Text};
+use ggez::{Context, GameResult};
+use std::collections::VecDeque;
+
+use crate::locale::{tr, Message};
+
+// Generations the graph spans, one sample each, the oldest scrolling off the left.
+const SAMPLES: usize = 300;
+const WIDTH: f32 = 300.0;
+const HEIGHT: f32 = 90.0;
+// Distance of the graph from the window corner and padding around it.
+const MARGIN: f32 = 8.0;
+const PADDING: f32 = 6.0;
+const LINE_COLOR: Color = Color::new(0.3, 0.9, 0.4, 1.0);
+
+/// The population of the last few hundred generations, drawn as a scrolling
+/// line chart in the bottom left corner.
+pub struct PopulationGraph {
+    /// Generation and population of each sample, oldest first.
+    samples: VecDeque<(u64, usize)>,
+    pub visible: bool,
+}
+
+impl PopulationGraph {
+    pub fn new() -> PopulationGraph {
+        PopulationGraph {
+            samples: VecDeque::with_capacity(SAMPLES),
+            visible: false,
+        }
+    }
+
+    /// Add the population of a generation, first dropping the samples from
+    /// that generation on if the universe went back, e.g. after an undo or a reset.
+    pub fn record(&mut self, generation: u64, population: usize) {
+        while self
+            .samples
+            .back()
+            .is_some_and(|&(sampled, _)| sampled >= generation)
+        {
+            self.samples.pop_back();
+        }
+        if self.samples.len() == SAMPLES {
+            self.samples.pop_front();
+        }
+        self.samples.push_back((generation, population));
+    }
+
+    /// Draw the chart, scaled from 0 to the highest population it shows.
+    pub fn draw(&self, ctx: &mut Context, canvas: &mut Canvas) -> GameResult {
+        let highest = self.samples.iter().map(|&(_, population)| population).max();
+        let label = Text::new(format!(
+            "{}: {}",
+            tr(Message::PopulationGraph),
+            highest.unwrap_or(0)
+        ));
+        let label_height = label.measure(ctx)?.y;
+        let (_, window_height) = ctx.gfx.drawable_size();
+        let panel = Rect::new(
+            MARGIN,
+            window_height - MARGIN - HEIGHT - label_height - 3.0 * PADDING,
+            WIDTH + 2.0 * PADDING,
+            HEIGHT + label_height + 3.0 * PADDING,
+        );
+        let backdrop =
+            Mesh::new_rectangle(ctx, DrawMode::fill(), panel, Color::new(0.0, 0.0, 0.0, 0.7))?;
+        canvas.draw(&backdrop, DrawParam::default());
+        canvas.draw(
+            &label,
+            DrawParam::default()
+                .dest([panel.x + PADDING, panel.y + PADDING])
+                .color(LINE_COLOR),
+        );
+
+        if self.samples.len() < 2 {
+            return Ok(());
+        }
+        // At least 1 so that an empty universe draws a flat line at the bottom
+        let highest = highest.unwrap_or(0).max(1) as f32;
+        let bottom = panel.bottom() - PADDING;
+        let step = WIDTH / (SAMPLES - 1) as f32;
+        let points: Vec<[f32; 2]> = self
+            .samples
+            .iter()
+            .enumerate()
+            .map(|(i, &(_, population))| {
+                [
+                    panel.x + PADDING + i as f32 * step,
+                    bottom - population as f32 / highest * HEIGHT,
+                ]
+            })
+            .collect();
+        let line = Mesh::new_line(ctx, &points, 1.5, LINE_COLOR)?;
+        canvas.draw(&line, DrawParam::default());
+        Ok(())
+    }
+}
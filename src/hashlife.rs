@@ -0,0 +1,430 @@
+use std::collections::HashMap;
+
+use crate::engine::Engine;
+use crate::rule::Rule;
+use crate::universe::Universe;
+
+// Nodes kept before the cache is rebuilt with only those of the current pattern.
+const NODE_LIMIT: usize = 1 << 22;
+// The two leaves: a single dead and a single live cell.
+const DEAD: u32 = 0;
+const ALIVE: u32 = 1;
+// Level of the smallest tree built, eight cells wide.
+const MIN_LEVEL: u8 = 3;
+
+/// A square of cells split into four squares half as wide.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+struct Quad {
+    nw: u32,
+    ne: u32,
+    sw: u32,
+    se: u32,
+}
+
+#[derive(Clone, Copy)]
+struct Node {
+    quad: Quad,
+    /// The square is 2^level cells wide.
+    level: u8,
+    population: u64,
+}
+
+/// Every distinct square met so far, each stored once, and the futures already
+/// worked out for them.
+struct Store {
+    rule: Rule,
+    nodes: Vec<Node>,
+    ids: HashMap<Quad, u32>,
+    /// Center of a node 2^j generations later, by node and j.
+    futures: HashMap<(u32, u8), u32>,
+    /// The empty node of each level.
+    empty: Vec<u32>,
+}
+
+impl Store {
+    fn new(rule: Rule) -> Store {
+        let leaf = |population| Node {
+            quad: Quad {
+                nw: DEAD,
+                ne: DEAD,
+                sw: DEAD,
+                se: DEAD,
+            },
+            level: 0,
+            population,
+        };
+        Store {
+            rule,
+            nodes: vec![leaf(0), leaf(1)],
+            ids: HashMap::new(),
+            futures: HashMap::new(),
+            empty: vec![DEAD],
+        }
+    }
+
+    fn node(&self, id: u32) -> Node {
+        self.nodes[id as usize]
+    }
+
+    /// The node made of four nodes of the same level.
+    fn join(&mut self, nw: u32, ne: u32, sw: u32, se: u32) -> u32 {
+        let quad = Quad { nw, ne, sw, se };
+        if let Some(&id) = self.ids.get(&quad) {
+            return id;
+        }
+        let population = [nw, ne, sw, se].iter().fold(0u64, |sum, &child| {
+            sum.saturating_add(self.node(child).population)
+        });
+        let id = self.nodes.len() as u32;
+        self.nodes.push(Node {
+            quad,
+            level: self.node(nw).level + 1,
+            population,
+        });
+        self.ids.insert(quad, id);
+        id
+    }
+
+    fn empty(&mut self, level: u8) -> u32 {
+        while self.empty.len() <= level as usize {
+            let smaller = self.empty[self.empty.len() - 1];
+            let empty = self.join(smaller, smaller, smaller, smaller);
+            self.empty.push(empty);
+        }
+        self.empty[level as usize]
+    }
+
+    /// The middle of a node, half as wide.
+    fn centre(&mut self, id: u32) -> u32 {
+        let quad = self.node(id).quad;
+        let [nw, ne, sw, se] =
+            [quad.nw, quad.ne, quad.sw, quad.se].map(|child| self.node(child).quad);
+        self.join(nw.se, ne.sw, sw.ne, se.nw)
+    }
+
+    /// A node twice as wide with `id` in its middle and dead cells around it.
+    fn expand(&mut self, id: u32) -> u32 {
+        let node = self.node(id);
+        let (quad, empty) = (node.quad, self.empty(node.level - 1));
+        let nw = self.join(empty, empty, empty, quad.nw);
+        let ne = self.join(empty, empty, quad.ne, empty);
+        let sw = self.join(empty, quad.sw, empty, empty);
+        let se = self.join(quad.se, empty, empty, empty);
+        self.join(nw, ne, sw, se)
+    }
+
+    /// Whether the cell at `(x, y)` inside a node is alive.
+    fn alive_at(&self, id: u32, x: usize, y: usize) -> bool {
+        let node = self.node(id);
+        if node.level == 0 {
+            return id == ALIVE;
+        }
+        let half = 1 << (node.level - 1);
+        let child = match (x < half, y < half) {
+            (true, true) => node.quad.nw,
+            (false, true) => node.quad.ne,
+            (true, false) => node.quad.sw,
+            (false, false) => node.quad.se,
+        };
+        self.alive_at(child, x % half, y % half)
+    }
+
+    /// The middle of a node of level 2 one generation later, worked out cell by cell.
+    fn step_base(&mut self, id: u32) -> u32 {
+        let grid: [[bool; 4]; 4] =
+            std::array::from_fn(|y| std::array::from_fn(|x| self.alive_at(id, x, y)));
+        let next = |x: usize, y: usize| {
+            let neighbors = (y - 1..=y + 1)
+                .flat_map(|ny| (x - 1..=x + 1).map(move |nx| (nx, ny)))
+                .filter(|&(nx, ny)| (nx, ny) != (x, y) && grid[ny][nx])
+                .count();
+            let alive = if grid[y][x] {
+                self.rule.survives(neighbors)
+            } else {
+                self.rule.born(neighbors)
+            };
+            if alive {
+                ALIVE
+            } else {
+                DEAD
+            }
+        };
+        let [nw, ne, sw, se] = [next(1, 1), next(2, 1), next(1, 2), next(2, 2)];
+        self.join(nw, ne, sw, se)
+    }
+
+    /// The middle of a node of level k, 2^j generations later, where j is at
+    /// most k - 2.
+    fn future(&mut self, id: u32, j: u8) -> u32 {
+        let node = self.node(id);
+        if node.population == 0 {
+            return self.empty(node.level - 1);
+        }
+        if let Some(&result) = self.futures.get(&(id, j)) {
+            return result;
+        }
+        let result = if node.level == 2 {
+            self.step_base(id)
+        } else {
+            let quad = node.quad;
+            let [a, b, c, d] =
+                [quad.nw, quad.ne, quad.sw, quad.se].map(|child| self.node(child).quad);
+            // Nine overlapping squares half as wide, row by row
+            let squares = [
+                quad.nw,
+                self.join(a.ne, b.nw, a.se, b.sw),
+                quad.ne,
+                self.join(a.sw, a.se, c.nw, c.ne),
+                self.join(a.se, b.sw, c.ne, d.nw),
+                self.join(b.sw, b.se, d.nw, d.ne),
+                quad.sw,
+                self.join(c.ne, d.nw, c.se, d.sw),
+                quad.se,
+            ];
+            // At full speed both halves of the way are jumped, otherwise only the second
+            let full = j + 2 == node.level;
+            let r = squares.map(|square| {
+                if full {
+                    self.future(square, j - 1)
+                } else {
+                    self.centre(square)
+                }
+            });
+            let second = if full { j - 1 } else { j };
+            let quarters = [
+                self.join(r[0], r[1], r[3], r[4]),
+                self.join(r[1], r[2], r[4], r[5]),
+                self.join(r[3], r[4], r[6], r[7]),
+                self.join(r[4], r[5], r[7], r[8]),
+            ]
+            .map(|quarter| self.future(quarter, second));
+            self.join(quarters[0], quarters[1], quarters[2], quarters[3])
+        };
+        self.futures.insert((id, j), result);
+        result
+    }
+
+    /// The node of `level` whose top left corner is the cell `(x, y)` of the grid.
+    fn build(&mut self, grid: &[Vec<bool>], x: usize, y: usize, level: u8) -> u32 {
+        let width = grid.first().map_or(0, Vec::len);
+        if x >= width || y >= grid.len() {
+            return self.empty(level);
+        }
+        if level == 0 {
+            return if grid[y][x] { ALIVE } else { DEAD };
+        }
+        let half = 1 << (level - 1);
+        let nw = self.build(grid, x, y, level - 1);
+        let ne = self.build(grid, x + half, y, level - 1);
+        let sw = self.build(grid, x, y + half, level - 1);
+        let se = self.build(grid, x + half, y + half, level - 1);
+        self.join(nw, ne, sw, se)
+    }
+
+    /// Bring the live cells of a node with its top left corner at `(x, y)` to
+    /// life in the grid, leaving out those beyond its edges.
+    fn write(&self, id: u32, x: i64, y: i64, grid: &mut [Vec<bool>]) {
+        let node = self.node(id);
+        let size = 1i64 << node.level;
+        let (width, height) = (grid.first().map_or(0, Vec::len) as i64, grid.len() as i64);
+        if node.population == 0 || x >= width || y >= height || x + size <= 0 || y + size <= 0 {
+            return;
+        }
+        if node.level == 0 {
+            grid[y as usize][x as usize] = true;
+            return;
+        }
+        let half = size / 2;
+        self.write(node.quad.nw, x, y, grid);
+        self.write(node.quad.ne, x + half, y, grid);
+        self.write(node.quad.sw, x, y + half, grid);
+        self.write(node.quad.se, x + half, y + half, grid);
+    }
+
+    /// The same node in another store.
+    fn copy(&mut self, from: &Store, id: u32, copies: &mut HashMap<u32, u32>) -> u32 {
+        if id == DEAD || id == ALIVE {
+            return id;
+        }
+        if let Some(&copy) = copies.get(&id) {
+            return copy;
+        }
+        let quad = from.node(id).quad;
+        let [nw, ne, sw, se] =
+            [quad.nw, quad.ne, quad.sw, quad.se].map(|child| self.copy(from, child, copies));
+        let copy = self.join(nw, ne, sw, se);
+        copies.insert(id, copy);
+        copy
+    }
+}
+
+/// The pattern as a quadtree, on a plane without edges.
+struct Tree {
+    store: Store,
+    root: u32,
+    /// Position of the top left corner of the root, in cells of the grid.
+    origin: (i64, i64),
+    /// The grid as it was last written back, to tell whether it was edited since.
+    written: Vec<Vec<bool>>,
+}
+
+impl Tree {
+    fn new(grid: &[Vec<bool>], rule: Rule) -> Tree {
+        let mut store = Store::new(rule);
+        let size = grid.len().max(grid.first().map_or(0, Vec::len));
+        let level = (size.next_power_of_two().trailing_zeros() as u8).max(MIN_LEVEL);
+        let root = store.build(grid, 0, 0, level);
+        Tree {
+            store,
+            root,
+            origin: (0, 0),
+            written: grid.to_vec(),
+        }
+    }
+
+    /// Jump 2^j generations ahead.
+    fn advance(&mut self, j: u8) {
+        // Pad the pattern with dead cells until nothing can grow out of the
+        // middle that is kept, at the speed of light
+        loop {
+            let root = self.store.node(self.root);
+            if root.level >= j + 3 {
+                let centre = self.store.centre(self.root);
+                let inner = self.store.centre(centre);
+                if self.store.node(inner).population == root.population {
+                    break;
+                }
+            }
+            self.root = self.store.expand(self.root);
+            let shift = 1i64 << (root.level - 1);
+            self.origin = (self.origin.0 - shift, self.origin.1 - shift);
+        }
+        let level = self.store.node(self.root).level;
+        self.root = self.store.future(self.root, j);
+        let shift = 1i64 << (level - 2);
+        self.origin = (self.origin.0 + shift, self.origin.1 + shift);
+    }
+
+    /// Start the cache over with just the nodes of the current pattern.
+    fn compact(&mut self) {
+        let mut store = Store::new(self.store.rule);
+        self.root = store.copy(&self.store, self.root, &mut HashMap::new());
+        self.store = store;
+    }
+
+    /// The cells inside a grid of the given size.
+    fn window(&self, width: usize, height: usize) -> Vec<Vec<bool>> {
+        let mut grid = vec![vec![false; width]; height];
+        self.store
+            .write(self.root, self.origin.0, self.origin.1, &mut grid);
+        grid
+    }
+}
+
+/// Gosper's HashLife: the pattern is kept as a quadtree of shared squares, and
+/// the future of every square worked out once, so that repetitive patterns can
+/// be jumped millions of generations ahead.
+///
+/// It runs on a plane without edges, with the grid as a window onto it: cells
+/// that leave the grid go on evolving out of sight until the grid is edited.
+/// Universes with edges that wrap around, frozen regions or rules with births
+/// on 0 neighbors are worked out generation by generation instead.
+pub struct HashLife {
+    tree: Option<Tree>,
+}
+
+impl HashLife {
+    pub fn new() -> HashLife {
+        HashLife { tree: None }
+    }
+}
+
+impl Engine for HashLife {
+    fn advance(&mut self, universe: &mut Universe, generations: u64) {
+        if !self.skips_ahead(universe) {
+            self.tree = None;
+            universe.advance(generations);
+            return;
+        }
+        let rule = universe.rule();
+        let mut tree = match self.tree.take() {
+            Some(tree) if tree.store.rule == rule && tree.written == universe.cells() => tree,
+            _ => Tree::new(universe.cells(), rule),
+        };
+        for j in (0..u64::BITS as u8).filter(|&j| generations & (1 << j) != 0) {
+            tree.advance(j);
+        }
+        if tree.store.nodes.len() > NODE_LIMIT {
+            tree.compact();
+        }
+        tree.written = tree.window(universe.width(), universe.height());
+        universe.jump(tree.written.clone(), generations);
+        self.tree = Some(tree);
+    }
+
+    fn skips_ahead(&self, universe: &Universe) -> bool {
+        !universe.is_toroidal() && universe.frozen().is_empty() && !universe.rule().born(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaCha8Rng;
+
+    use super::HashLife;
+    use crate::engine::Engine;
+    use crate::universe::Universe;
+
+    /// A soup in the middle of a universe large enough that, growing at most a
+    /// cell a generation, it does not reach the edges within `margin` generations.
+    fn soup_in_the_middle(rng: &mut ChaCha8Rng, margin: usize) -> Universe {
+        let size = 32;
+        let mut universe = Universe::new(size + 2 * margin + 2, size + 2 * margin + 2);
+        for y in 0..size {
+            for x in 0..size {
+                if rng.gen_bool(0.5) {
+                    universe.set(margin + 1 + x, margin + 1 + y, true);
+                }
+            }
+        }
+        universe
+    }
+
+    #[test]
+    fn matches_packed_away_from_the_edges() {
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+        for _ in 0..20 {
+            let mut hashlife = soup_in_the_middle(&mut rng, 64);
+            let mut packed = hashlife.clone();
+            let generations = rng.gen_range(1..=64);
+            HashLife::new().advance(&mut hashlife, generations);
+            packed.advance(generations);
+            assert_eq!(
+                hashlife.cells(),
+                packed.cells(),
+                "{} generations",
+                generations
+            );
+            assert_eq!(hashlife.generation(), packed.generation());
+        }
+    }
+
+    #[test]
+    fn matches_packed_one_generation_at_a_time() {
+        let mut rng = ChaCha8Rng::seed_from_u64(2);
+        let mut hashlife = soup_in_the_middle(&mut rng, 40);
+        let mut packed = hashlife.clone();
+        let mut engine = HashLife::new();
+        for generation in 1..=40 {
+            engine.advance(&mut hashlife, 1);
+            packed.step();
+            assert_eq!(
+                hashlife.cells(),
+                packed.cells(),
+                "generation {}",
+                generation
+            );
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use std::fmt::Write as _;
+use std::fs;
+use std::io::{self, Write};
+use std::time::Instant;
+
+use crate::cli::Options;
+use crate::daily::Date;
+use crate::pattern::Pattern;
+use crate::rule::Rule;
+use crate::session::Session;
+use crate::universe::Universe;
+
+/// Run the universe the options describe for `generations` without a window
+/// and report how it ended up, to stdout or to the `--output` file.
+pub fn run(options: &Options, generations: u64, pattern: Option<Pattern>) -> io::Result<()> {
+    // The size of the last session would make runs differ from machine to machine
+    let session = Session::default().overridden(options);
+    let mut universe = Universe::new(session.grid_width, session.grid_height);
+    universe.set_toroidal(options.torus);
+    // A rule given on the command line wins over the one in the pattern's header
+    let header = pattern.as_ref().and_then(|p| p.rule.as_deref());
+    if let Some(rule) = options.rule {
+        universe.set_rule(rule);
+    } else if let Some(header) = header {
+        universe.set_rule(Rule::parse(header).map_err(io::Error::other)?);
+    }
+    if options.daily {
+        universe.fill_random(0.5, &mut Date::today().rng());
+    } else if let Some(pattern) = &pattern {
+        let (left, top) = universe.centered(pattern);
+        universe.place(pattern, left, top);
+    } else {
+        // The glider the game starts with
+        for (x, y) in [(2, 1), (3, 2), (1, 3), (2, 3), (3, 3)] {
+            universe.set(x, y, true);
+        }
+    }
+
+    let start = Instant::now();
+    options.engine.create().advance(&mut universe, generations);
+    let seconds = start.elapsed().as_secs_f64();
+
+    let mut report = String::new();
+    let _ = writeln!(report, "generations: {}", universe.generation());
+    let _ = writeln!(report, "population: {}", universe.population());
+    match universe.bounding_box() {
+        Some((left, top, right, bottom)) => {
+            let _ = writeln!(
+                report,
+                "bounding box: {}x{} at ({}, {})",
+                right - left + 1,
+                bottom - top + 1,
+                left,
+                top
+            );
+        }
+        None => report.push_str("bounding box: none\n"),
+    }
+    let _ = writeln!(
+        report,
+        "seconds: {:.3} ({:.0} generations per second)",
+        seconds,
+        generations as f64 / seconds.max(f64::EPSILON)
+    );
+    if options.rle {
+        report.push_str(&universe.to_pattern().to_rle(&universe.rule().to_string()));
+    }
+    match &options.output {
+        Some(path) => fs::write(path, report),
+        None => io::stdout().write_all(report.as_bytes()),
+    }
+}
@@ -0,0 +1,97 @@
+use ggez::graphics::{Canvas, DrawParam, Image, ImageFormat, Sampler};
+use ggez::{Context, GameResult};
+
+use crate::render::CellSize;
+use crate::universe::Universe;
+
+/// Radius of the neighborhood summed when the configuration does not say
+/// otherwise, the one the rules go by.
+pub const DEFAULT_RADIUS: usize = 1;
+/// Largest radius summed, beyond which working out the layer gets slow.
+pub const MAX_RADIUS: usize = 5;
+// Colors of the fewest and the most live cells around a cell, and how opaque
+// the layer is at its hottest.
+const COLD: [f32; 3] = [0.1, 0.2, 0.7];
+const HOT: [f32; 3] = [1.0, 0.35, 0.0];
+const MAX_ALPHA: f32 = 0.65;
+
+/// A heat map under the cells of how many live cells surround each one,
+/// showing where blobs are about to grow or shrink.
+pub struct HeatLayer {
+    radius: usize,
+    pub visible: bool,
+    /// The cells the picture was worked out from, to know when to redo it.
+    cells: Vec<Vec<bool>>,
+    image: Option<Image>,
+}
+
+impl HeatLayer {
+    /// Sum the live cells up to `radius` cells away, at most `MAX_RADIUS`.
+    pub fn new(radius: usize) -> HeatLayer {
+        HeatLayer {
+            radius: radius.clamp(1, MAX_RADIUS),
+            visible: false,
+            cells: Vec::new(),
+            image: None,
+        }
+    }
+
+    /// Draw the layer over the grid, working it out again if the cells changed.
+    pub fn draw(
+        &mut self,
+        ctx: &mut Context,
+        canvas: &mut Canvas,
+        universe: &Universe,
+        cell: CellSize,
+    ) -> GameResult {
+        if self.image.is_none() || self.cells != universe.cells() {
+            self.cells = universe.cells().to_vec();
+            self.image = Some(self.picture(ctx, universe));
+        }
+        let Some(image) = &self.image else {
+            return Ok(());
+        };
+        // Keep the cells crisp instead of blurring them when scaled up
+        canvas.set_sampler(Sampler::nearest_clamp());
+        canvas.draw(image, DrawParam::default().scale([cell.width, cell.height]));
+        canvas.set_default_sampler();
+        Ok(())
+    }
+
+    /// A pixel per cell colored by the live cells around it, the edges wrapping
+    /// around as the universe's do.
+    fn picture(&self, ctx: &Context, universe: &Universe) -> Image {
+        let radius = self.radius as isize;
+        let most = ((2 * radius + 1).pow(2) - 1) as f32;
+        let (width, height) = (universe.width(), universe.height());
+        let cells = universe.cells();
+        let mut pixels = Vec::with_capacity(width * height * 4);
+        for y in 0..height {
+            for x in 0..width {
+                let sum = (-radius..=radius)
+                    .flat_map(|dy| (-radius..=radius).map(move |dx| (dx, dy)))
+                    .filter(|&offset| offset != (0, 0))
+                    .filter_map(|(dx, dy)| universe.offset(x, y, dx, dy))
+                    .filter(|&(nx, ny)| cells[ny][nx])
+                    .count();
+                if sum == 0 {
+                    pixels.extend_from_slice(&[0, 0, 0, 0]);
+                    continue;
+                }
+                let heat = sum as f32 / most;
+                for (cold, hot) in COLD.iter().zip(HOT) {
+                    pixels.push(((cold + (hot - cold) * heat) * 255.0) as u8);
+                }
+                let alpha = MAX_ALPHA * (0.3 + 0.7 * heat);
+                pixels.push((alpha * 255.0) as u8);
+            }
+        }
+        Image::from_pixels(
+            ctx,
+            &pixels,
+            ImageFormat::Rgba8UnormSrgb,
+            width as u32,
+            height as u32,
+        )
+    }
+}
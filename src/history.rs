@@ -0,0 +1,68 @@
+use std::collections::VecDeque;
+
+/// Snapshots kept for undoing when the configuration does not say otherwise.
+pub const DEFAULT_DEPTH: usize = 100;
+
+/// The grid as it was at some point, to go back to.
+pub struct Snapshot {
+    pub cells: Vec<Vec<bool>>,
+    pub generation: u64,
+}
+
+/// Earlier and undone states of the grid, with a bounded number of snapshots
+/// so that memory stays bounded on large grids.
+pub struct History {
+    /// Oldest first.
+    undo: VecDeque<Snapshot>,
+    /// Most recently undone last.
+    redo: Vec<Snapshot>,
+    depth: usize,
+}
+
+impl History {
+    pub fn new(depth: usize) -> History {
+        History {
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+            depth,
+        }
+    }
+
+    /// Whether snapshots are kept at all.
+    pub fn is_enabled(&self) -> bool {
+        self.depth > 0
+    }
+
+    /// Remember the grid before it changes, forgetting what was undone and the
+    /// oldest snapshot once there are too many.
+    pub fn record(&mut self, snapshot: Snapshot) {
+        if self.depth == 0 {
+            return;
+        }
+        if self.undo.len() == self.depth {
+            self.undo.pop_front();
+        }
+        self.undo.push_back(snapshot);
+        self.redo.clear();
+    }
+
+    /// The state to go back to, keeping `current` to redo.
+    pub fn undo(&mut self, current: Snapshot) -> Option<Snapshot> {
+        let previous = self.undo.pop_back()?;
+        self.redo.push(current);
+        Some(previous)
+    }
+
+    /// The state undone last, keeping `current` to undo again.
+    pub fn redo(&mut self, current: Snapshot) -> Option<Snapshot> {
+        let next = self.redo.pop()?;
+        self.undo.push_back(current);
+        Some(next)
+    }
+
+    /// Forget everything, e.g. when the grid changes size.
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+}
@@ -0,0 +1,80 @@
+use ggez::graphics::{Canvas, Color, DrawMode, DrawParam, Mesh, Rect, Text};
+use ggez::{Context, GameResult};
+
+use crate::locale::{tr, Message};
+
+const MARGIN: f32 = 8.0;
+const PADDING: f32 = 6.0;
+
+/// What the simulation is doing, as shown in the heads-up display.
+pub struct Status {
+    pub generation: u64,
+    pub population: usize,
+    pub gens_per_second: f64,
+    pub paused: bool,
+    /// Whether the edges wrap around.
+    pub toroidal: bool,
+}
+
+/// The generation, the population, the speed, whether the simulation is paused
+/// and whether the edges wrap around, in a panel at the top of the window.
+pub struct Hud {
+    pub visible: bool,
+}
+
+impl Hud {
+    pub fn new() -> Hud {
+        Hud { visible: true }
+    }
+
+    pub fn draw(&self, ctx: &mut Context, canvas: &mut Canvas, status: &Status) -> GameResult {
+        let text = Text::new(format!(
+            "{}: {}  {}: {}  {:.1} {}  {}  {}: {}",
+            tr(Message::Generation),
+            status.generation,
+            tr(Message::Population),
+            status.population,
+            status.gens_per_second,
+            tr(Message::GensPerSecond),
+            tr(if status.paused {
+                Message::HudPaused
+            } else {
+                Message::HudRunning
+            }),
+            tr(Message::Edges),
+            tr(edges_message(status.toroidal))
+        ));
+        let size = text.measure(ctx)?;
+        let (window_width, _) = ctx.gfx.drawable_size();
+        let panel = Rect::new(
+            (window_width - size.x) / 2.0 - PADDING,
+            MARGIN,
+            size.x + 2.0 * PADDING,
+            size.y + 2.0 * PADDING,
+        );
+        let backdrop =
+            Mesh::new_rectangle(ctx, DrawMode::fill(), panel, Color::new(0.0, 0.0, 0.0, 0.6))?;
+        canvas.draw(&backdrop, DrawParam::default());
+        let color = if status.paused {
+            Color::YELLOW
+        } else {
+            Color::WHITE
+        };
+        canvas.draw(
+            &text,
+            DrawParam::default()
+                .dest([panel.x + PADDING, panel.y + PADDING])
+                .color(color),
+        );
+        Ok(())
+    }
+}
+
+/// How the edges of the universe behave, in words.
+pub fn edges_message(toroidal: bool) -> Message {
+    if toroidal {
+        Message::EdgesWrap
+    } else {
+        Message::EdgesBounded
+    }
+}
@@ -0,0 +1,78 @@
+use ggez::graphics::{Canvas, Color};
+use ggez::Context;
+
+use crate::render::{shade, QuadBatch};
+
+// Fraction of a tile's footprint covered by the raised cell, leaving a gap between neighbors.
+const TILE_SCALE: f32 = 0.8;
+// Height of a raised cell relative to the tile half-height.
+const TILE_LIFT: f32 = 0.9;
+const FLOOR_COLOR: Color = Color::new(0.12, 0.12, 0.15, 1.0);
+const TILE_COLOR: Color = Color::new(0.95, 0.9, 0.75, 1.0);
+
+/// Draw the grid in an isometric projection with live cells as raised tiles.
+pub fn draw(ctx: &mut Context, canvas: &mut Canvas, grid: &[Vec<bool>]) {
+    let (window_width, window_height) = ctx.gfx.drawable_size();
+    let (w, h) = (grid.first().map_or(0, Vec::len) as f32, grid.len() as f32);
+    let span = w + h;
+    // A tile is twice as wide as it is tall
+    let half_width = (window_width / span).min(2.0 * window_height / span);
+    let half_height = half_width / 2.0;
+    let lift = half_height * TILE_LIFT;
+
+    // Center the diamond-shaped board in the window
+    let origin_x = (window_width - span * half_width) / 2.0 + h * half_width;
+    let origin_y = (window_height - span * half_height) / 2.0;
+    // Map grid corner coordinates onto the screen
+    let project = |u: f32, v: f32| {
+        [
+            origin_x + (u - v) * half_width,
+            origin_y + (u + v) * half_height,
+        ]
+    };
+
+    let mut batch = QuadBatch::new();
+    batch.push(
+        [
+            project(0.0, 0.0),
+            project(w, 0.0),
+            project(w, h),
+            project(0.0, h),
+        ],
+        FLOOR_COLOR,
+    );
+
+    // Row-major order already paints tiles from back to front
+    let inset = (1.0 - TILE_SCALE) / 2.0;
+    for (y, row) in grid.iter().enumerate() {
+        for (x, &alive) in row.iter().enumerate() {
+            if !alive {
+                continue;
+            }
+            let (u0, v0) = (x as f32 + inset, y as f32 + inset);
+            let (u1, v1) = (u0 + TILE_SCALE, v0 + TILE_SCALE);
+            let raise = |[sx, sy]: [f32; 2]| [sx, sy - lift];
+            let (top, right, bottom, left) = (
+                project(u0, v0),
+                project(u1, v0),
+                project(u1, v1),
+                project(u0, v1),
+            );
+
+            batch.push(
+                [raise(left), raise(bottom), bottom, left],
+                shade(TILE_COLOR, 0.7),
+            );
+            batch.push(
+                [raise(bottom), raise(right), right, bottom],
+                shade(TILE_COLOR, 0.5),
+            );
+            batch.push(
+                [raise(top), raise(right), raise(bottom), raise(left)],
+                TILE_COLOR,
+            );
+        }
+    }
+
+    batch.draw(ctx, canvas);
+}
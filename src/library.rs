@@ -0,0 +1,69 @@
+use ggez::{Context, GameResult};
+use std::io::Read;
+use std::path::PathBuf;
+
+use crate::pattern::Pattern;
+
+// Resource directory scanned for pattern files.
+const PATTERN_DIR: &str = "/patterns";
+
+/// The RLE and plaintext patterns in `resources/patterns`, cycled through in
+/// file name order.
+pub struct Library {
+    /// File of the pattern loaded last.
+    current: Option<PathBuf>,
+}
+
+impl Library {
+    pub fn new() -> Library {
+        Library { current: None }
+    }
+
+    /// Read the pattern after the current one, or before it if `forward` is false.
+    ///
+    /// The directory is listed again every time, so files dropped into it show
+    /// up without a restart. Returns the pattern with its position and the
+    /// number of patterns, or `None` if the directory has none.
+    pub fn cycle(
+        &mut self,
+        ctx: &Context,
+        forward: bool,
+    ) -> GameResult<Option<(Pattern, usize, usize)>> {
+        let mut paths: Vec<PathBuf> = ctx
+            .fs
+            .read_dir(PATTERN_DIR)?
+            .filter(|path| {
+                path.extension()
+                    .is_some_and(|e| e == "rle" || e == "cells" || e == "lif" || e == "life")
+            })
+            .collect();
+        paths.sort();
+        if paths.is_empty() {
+            return Ok(None);
+        }
+        let current = self
+            .current
+            .as_ref()
+            .and_then(|current| paths.iter().position(|path| path == current));
+        let index = match (current, forward) {
+            (None, true) => 0,
+            (None, false) => paths.len() - 1,
+            (Some(i), true) => (i + 1) % paths.len(),
+            (Some(i), false) => (i + paths.len() - 1) % paths.len(),
+        };
+        let path = &paths[index];
+        self.current = Some(path.clone());
+
+        let mut text = String::new();
+        ctx.fs.open(path)?.read_to_string(&mut text)?;
+        let mut pattern = Pattern::parse(&text).map_err(|e| {
+            ggez::GameError::ResourceLoadError(format!("{}: {}", path.display(), e))
+        })?;
+        if pattern.name.is_none() {
+            pattern.name = path
+                .file_stem()
+                .map(|name| name.to_string_lossy().into_owned());
+        }
+        Ok(Some((pattern, index + 1, paths.len())))
+    }
+}
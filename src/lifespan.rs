@@ -0,0 +1,170 @@
+use ggez::graphics::{Canvas, Color, DrawMode, DrawParam, Mesh, MeshBuilder, Rect, Text};
+use ggez::{Context, GameResult};
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use crate::bitgrid::BitGrid;
+use crate::locale::{tr, Message};
+
+/// Number of histogram buckets; bucket `i` counts lifespans in `2^i..2^(i+1)`.
+pub const BUCKETS: usize = 12;
+// Size of the bar chart.
+const BAR_WIDTH: f32 = 28.0;
+const CHART_HEIGHT: f32 = 160.0;
+const MARGIN: f32 = 8.0;
+// Cells fade from the first color at birth to the second at OLD_AGE generations.
+const NEWBORN_COLOR: Color = Color::new(1.0, 1.0, 1.0, 1.0);
+const OLD_COLOR: Color = Color::new(0.05, 0.15, 0.75, 1.0);
+const OLD_AGE: u32 = 256;
+
+/// Histogram of how many generations cells stayed alive before dying.
+///
+/// Different rules and soups leave clearly different fingerprints here.
+pub struct LifespanStats {
+    ages: Vec<Vec<u32>>,
+    histogram: [u64; BUCKETS],
+}
+
+impl LifespanStats {
+    /// Create empty statistics for a grid of the given size.
+    pub fn new(width: usize, height: usize) -> LifespanStats {
+        LifespanStats {
+            ages: vec![vec![0; width]; height],
+            histogram: [0; BUCKETS],
+        }
+    }
+
+    /// Age the cells that survived a generation and record the ones that died.
+    ///
+    /// Only live cells are visited, so a sparse universe costs little.
+    pub fn observe(&mut self, before: &BitGrid, after: &BitGrid) {
+        for (x, y) in before.live_cells() {
+            if !after.get(x, y) {
+                // Cells drawn by hand have no recorded birth and count as newborn
+                let age = &mut self.ages[y][x];
+                let lifespan = (*age).max(1);
+                let bucket = (lifespan.ilog2() as usize).min(BUCKETS - 1);
+                self.histogram[bucket] += 1;
+                *age = 0;
+            }
+        }
+        for (x, y) in after.live_cells() {
+            let age = &mut self.ages[y][x];
+            *age = if before.get(x, y) {
+                age.saturating_add(1)
+            } else {
+                1
+            };
+        }
+    }
+
+    /// Generations the cell at `(x, y)` has been alive without a break; cells
+    /// drawn by hand since the last generation count as 0.
+    pub fn age(&self, x: usize, y: usize) -> u32 {
+        self.ages
+            .get(y)
+            .and_then(|row| row.get(x))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Approximate number of bytes held by the statistics.
+    pub fn memory_usage(&self) -> usize {
+        self.ages
+            .iter()
+            .map(|row| row.capacity() * 4)
+            .sum::<usize>()
+            + BUCKETS * 8
+    }
+
+    /// Forget all ages and recorded lifespans.
+    pub fn reset(&mut self) {
+        for row in &mut self.ages {
+            row.fill(0);
+        }
+        self.histogram = [0; BUCKETS];
+    }
+
+    /// Draw the histogram as a bar chart in the bottom left corner.
+    pub fn draw(&self, ctx: &mut Context, canvas: &mut Canvas) -> GameResult {
+        let (_, window_height) = ctx.gfx.drawable_size();
+        let baseline = window_height - 2.0 * MARGIN - 12.0;
+        let panel = Rect::new(
+            0.0,
+            baseline - CHART_HEIGHT - 4.0 * MARGIN,
+            2.0 * MARGIN + BAR_WIDTH * BUCKETS as f32,
+            CHART_HEIGHT + 6.0 * MARGIN + 12.0,
+        );
+        let backdrop =
+            Mesh::new_rectangle(ctx, DrawMode::fill(), panel, Color::new(0.0, 0.0, 0.0, 0.7))?;
+        canvas.draw(&backdrop, DrawParam::default());
+        canvas.draw(
+            &Text::new(tr(Message::LifespanChart)),
+            DrawParam::default().dest([MARGIN, panel.y + MARGIN / 2.0]),
+        );
+
+        // Scale logarithmically, short-lived cells outnumber long-lived ones by orders of magnitude
+        let highest = self.histogram.iter().copied().max().unwrap_or(0);
+        let scale = |count: u64| (count as f32).ln_1p() / (highest as f32).ln_1p() * CHART_HEIGHT;
+        let mut bars = MeshBuilder::new();
+        for (i, &count) in self.histogram.iter().enumerate() {
+            let x = MARGIN + i as f32 * BAR_WIDTH;
+            if count > 0 {
+                let height = scale(count);
+                bars.rectangle(
+                    DrawMode::fill(),
+                    Rect::new(x + 2.0, baseline - height, BAR_WIDTH - 4.0, height),
+                    Color::new(0.3, 0.7, 1.0, 1.0),
+                )?;
+            }
+            let mut label = Text::new(bucket_bounds(i).0.to_string());
+            label.set_scale(10.0);
+            canvas.draw(&label, DrawParam::default().dest([x + 2.0, baseline + 4.0]));
+        }
+        if highest > 0 {
+            canvas.draw(&Mesh::from_data(ctx, bars.build()), DrawParam::default());
+        }
+        Ok(())
+    }
+
+    /// Write the histogram as CSV with the bounds of each bucket.
+    pub fn write_csv(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut out = BufWriter::new(File::create(path)?);
+        writeln!(out, "min_generations,max_generations,deaths")?;
+        for (i, count) in self.histogram.iter().enumerate() {
+            let (low, high) = bucket_bounds(i);
+            match high {
+                Some(high) => writeln!(out, "{},{},{}", low, high, count)?,
+                None => writeln!(out, "{},,{}", low, count)?,
+            }
+        }
+        out.flush()
+    }
+}
+
+/// Smallest and largest lifespan counted in a bucket; the last bucket is open ended.
+pub fn bucket_bounds(bucket: usize) -> (u32, Option<u32>) {
+    let low = 1 << bucket;
+    if bucket + 1 == BUCKETS {
+        (low, None)
+    } else {
+        (low, Some((low << 1) - 1))
+    }
+}
+
+/// Color of a cell of the given age, on a logarithmic scale so that the first
+/// few generations stand apart as much as the oldest.
+pub fn age_color(age: u32) -> Color {
+    let t = ((age.max(1) as f32).log2() / (OLD_AGE as f32).log2()).min(1.0);
+    let mix = |from: f32, to: f32| from + (to - from) * t;
+    Color::new(
+        mix(NEWBORN_COLOR.r, OLD_COLOR.r),
+        mix(NEWBORN_COLOR.g, OLD_COLOR.g),
+        mix(NEWBORN_COLOR.b, OLD_COLOR.b),
+        1.0,
+    )
+}
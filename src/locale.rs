@@ -0,0 +1,663 @@
+use std::env;
+use std::fmt;
+use std::sync::OnceLock;
+
+// Language chosen at startup; English until then.
+static LANGUAGE: OnceLock<Language> = OnceLock::new();
+
+/// Languages the interface is translated into.
+#[derive(Clone, Copy)]
+pub enum Language {
+    English,
+    German,
+}
+
+/// Every piece of text shown in the window.
+#[derive(Clone, Copy)]
+pub enum Message {
+    WindowTitle,
+    Gliders,
+    Lwss,
+    Fps,
+    FrameTime,
+    Memory,
+    MemoryGrid,
+    MemoryHistory,
+    MemoryPeriod,
+    MemoryLifespans,
+    MemoryRewind,
+    LifespanChart,
+    TutorialPlace,
+    TutorialResume,
+    TutorialPause,
+    TutorialStep,
+    TutorialGun,
+    TutorialWatch,
+    TutorialDone,
+    TutorialSkip,
+    PuzzleLevel,
+    PuzzleCells,
+    Generation,
+    PuzzleEditing,
+    PuzzleSolved,
+    PuzzleFailed,
+    PuzzleComplete,
+    AchievementsTitle,
+    AchievementUnlocked,
+    AchievementGeneration,
+    AchievementFirstGlider,
+    AchievementFirstLwss,
+    AchievementGunBuilt,
+    AchievementPeriod15,
+    AchievementPeriod30,
+    AchievementCrowded,
+    AchievementMarathon,
+    DailySoup,
+    Population,
+    EditorTitle,
+    EditorKeys,
+    SymmetryNone,
+    SymmetryLeftRight,
+    SymmetryTopBottom,
+    SymmetryFourWay,
+    SymmetryRotational,
+    LabelPrompt,
+    MeasureHint,
+    MeasureDiagonal,
+    ResizePrompt,
+    RulePrompt,
+    CatagoluePrompt,
+    ToastSaved,
+    ToastLoaded,
+    ToastExported,
+    ToastCopied,
+    ToastRecording,
+    ToastRecordingFull,
+    ToastSavingRecording,
+    ToastCopiedRle,
+    ToastNothingToCopy,
+    ToastNothingToExport,
+    ToastSeed,
+    ToastCopiedSeed,
+    ToastNoSeed,
+    ToastSelectionCopied,
+    ToastSelectionCut,
+    ToastNothingToPaste,
+    ToastNothingSelected,
+    ToastPauseToSelect,
+    ToastDownloading,
+    ToastSpeed,
+    ToastSpeedStep,
+    GensPerSecond,
+    HudPaused,
+    HudRunning,
+    Edges,
+    Stamp,
+    CensusTitle,
+    CensusOthers,
+    CensusUnidentified,
+    CensusEmpty,
+    GalleryEmpty,
+    ToastGalleryRestored,
+    ToastGalleryNeedsHistory,
+    StampsTitle,
+    StampsHelp,
+    EdgesWrap,
+    EdgesBounded,
+    ToastUniverse,
+    ToastBackground,
+    ErrorCannotRead,
+    ErrorStoppedSpectating,
+    ErrorCannotSave,
+    ErrorCannotExport,
+    ErrorCannotLoad,
+    ErrorCannotOpen,
+    ErrorCannotLoadGun,
+    ErrorExpectedSize,
+    ErrorNotCopies,
+    ErrorNoSoups,
+    ErrorCannotFetchSoups,
+    ErrorClipboardNoPattern,
+    ErrorCannotReadClipboardPattern,
+    ErrorCannotLoadPattern,
+    ErrorCannotReadClipboard,
+    ErrorClipboardNoUrl,
+    ErrorCannotWriteClipboard,
+    ErrorCannotLoadStamp,
+    ErrorCannotSwitch,
+    ErrorCannotSeed,
+    ErrorCannotSaveRecording,
+    /// The recording indicator, with the number of frames.
+    Recording,
+    PanelTitle,
+    PanelPlay,
+    PanelPause,
+    PanelStep,
+    PanelDelay,
+    PanelRule,
+    PanelResize,
+    PanelTheme,
+    PanelClear,
+    PanelRandomize,
+    PanelSave,
+    PanelLoad,
+    PanelSaveWorkspace,
+    PanelOpenWorkspace,
+    ToastNothingToRewind,
+    ToastRewound,
+    ScatterPrompt,
+    ToastScattered,
+    ToastBrush,
+    BrushSquare,
+    BrushCircle,
+    PopulationGraph,
+    AlarmPrompt,
+    ToastAlarmAbove,
+    ToastAlarmBelow,
+    ToastAlarmSet,
+    ToastAlarmOff,
+    ToastFollowing,
+    ToastNotFollowing,
+    ToastFuzzyOn,
+    ToastFuzzyOff,
+    ToastSmoothing,
+    ViewFlat,
+    ViewSpaceTime,
+    ViewIsometric,
+    SeedingTitle,
+    SeedUniform,
+    SeedDisk,
+    SeedRing,
+    SeedStripes,
+    SeedGradient,
+    SeedingSymmetry,
+    SeedingCancel,
+    ToastRuleChanged,
+    ToastRuleRevert,
+    ToastRuleUnsupported,
+    PlacementsTitle,
+    PlacementsEmpty,
+    PlacementsKeys,
+    Unnamed,
+    Mirrored,
+    ToastNoPeriod,
+    ToastUndone,
+    ToastRedone,
+    ToastNothingToUndo,
+    ToastNothingToRedo,
+    ToastJumpedAhead,
+    ToastCannotJump,
+    ToastEnginesDisagree,
+    RulebookTitle,
+    RulebookStart,
+    RuleLife,
+    RuleHighLife,
+    RuleSeeds,
+    RuleDayAndNight,
+    RuleLifeWithoutDeath,
+    RuleReplicator,
+    RuleDiamoeba,
+    RuleWireworld,
+    ToastNoMultiState,
+    SaveWorkspacePrompt,
+    OpenWorkspacePrompt,
+    ToastNoPatterns,
+    Overlay,
+    OverlayMissing,
+    OverlayExtra,
+}
+
+impl Language {
+    /// Match a language code or a locale such as `de_DE.UTF-8`.
+    pub fn from_code(code: &str) -> Option<Language> {
+        let language = code.split(['_', '-', '.']).next()?.to_ascii_lowercase();
+        match language.as_str() {
+            "en" => Some(Language::English),
+            "de" => Some(Language::German),
+            _ => None,
+        }
+    }
+
+    /// The language of the system locale, if it is one we have.
+    pub fn from_environment() -> Option<Language> {
+        // Same precedence as gettext
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|name| env::var(name).ok())
+            .find(|value| !value.is_empty())
+            .and_then(|value| Language::from_code(&value))
+    }
+}
+
+/// Choose the language of the interface; only the first call has an effect.
+pub fn init(language: Language) {
+    let _ = LANGUAGE.set(language);
+}
+
+/// Translate a message into the chosen language.
+pub fn tr(message: Message) -> &'static str {
+    match LANGUAGE.get().copied().unwrap_or(Language::English) {
+        Language::English => english(message),
+        Language::German => german(message),
+    }
+}
+
+/// Translate a message with `{}` placeholders, filling them with `values` in turn.
+pub fn tr_with(message: Message, values: &[&dyn fmt::Display]) -> String {
+    let mut text = String::new();
+    for (i, part) in tr(message).split("{}").enumerate() {
+        if let Some(value) = i.checked_sub(1).and_then(|i| values.get(i)) {
+            text.push_str(&value.to_string());
+        }
+        text.push_str(part);
+    }
+    text
+}
+
+fn english(message: Message) -> &'static str {
+    match message {
+        Message::WindowTitle => "Krida - Game of Life",
+        Message::Gliders => "Gliders",
+        Message::Lwss => "LWSS",
+        Message::Fps => "FPS",
+        Message::FrameTime => "Frame",
+        Message::Memory => "Memory",
+        Message::MemoryGrid => "grid",
+        Message::MemoryHistory => "history",
+        Message::MemoryPeriod => "period detector",
+        Message::MemoryLifespans => "lifespans",
+        Message::MemoryRewind => "rewind",
+        Message::LifespanChart => "Lifespan (generations)",
+        Message::TutorialPlace => "Click on the grid to bring cells to life",
+        Message::TutorialResume => "Press Space to start the simulation",
+        Message::TutorialPause => "Press Space again to pause it",
+        Message::TutorialStep => "While paused, press N to advance a single generation",
+        Message::TutorialGun => "Press G to load the Gosper glider gun",
+        Message::TutorialWatch => "Press Space and watch it fire gliders",
+        Message::TutorialDone => "That's all! Press T to run the tutorial again",
+        Message::TutorialSkip => "T: close the tutorial",
+        Message::PuzzleLevel => "Level",
+        Message::PuzzleCells => "Cells",
+        Message::Generation => "Generation",
+        Message::PuzzleEditing => "Place your cells, then press Space",
+        Message::PuzzleSolved => "Solved! Press Enter for the next level",
+        Message::PuzzleFailed => "Not this time. Press C to try again",
+        Message::PuzzleComplete => "Solved! That was the last level",
+        Message::AchievementsTitle => "Discoveries",
+        Message::AchievementUnlocked => "New discovery",
+        Message::AchievementGeneration => "generation",
+        Message::AchievementFirstGlider => "First glider spotted",
+        Message::AchievementFirstLwss => "First lightweight spaceship spotted",
+        Message::AchievementGunBuilt => "First gun built",
+        Message::AchievementPeriod15 => "Period-15 oscillator found",
+        Message::AchievementPeriod30 => "Period-30 oscillator found",
+        Message::AchievementCrowded => "Population over 5000",
+        Message::AchievementMarathon => "10 000 generations in one run",
+        Message::DailySoup => "Daily soup",
+        Message::Population => "Population",
+        Message::EditorTitle => "Pattern editor",
+        Message::EditorKeys => {
+            "Click: toggle  M: symmetry  C: clear  Enter: test run in the sandbox  E: back to the sandbox"
+        }
+        Message::SymmetryNone => "no symmetry",
+        Message::SymmetryLeftRight => "mirrored left to right",
+        Message::SymmetryTopBottom => "mirrored top to bottom",
+        Message::SymmetryFourWay => "mirrored both ways",
+        Message::SymmetryRotational => "rotated by half a turn",
+        Message::LabelPrompt => "Label for cell (Enter: save, empty: remove, Escape: cancel)",
+        Message::MeasureHint => "Measuring: click two cells (M: stop measuring)",
+        Message::MeasureDiagonal => "diagonal",
+        Message::ToastSaved => "Saved to",
+        Message::ToastLoaded => "Loaded",
+        Message::ToastExported => "Exported to",
+        Message::ToastCopied => "Copied the live cells to the clipboard",
+        Message::ToastRecording => "Recording; press F9 again to stop and save a GIF",
+        Message::ToastRecordingFull => "The recording is as long as it gets",
+        Message::ToastSavingRecording => "Saving the recording to",
+        Message::ToastCopiedRle => "Copied the live cells to the clipboard as RLE",
+        Message::ToastNothingToCopy => "There are no live cells to copy",
+        Message::ToastNothingToExport => "There are no live cells to export",
+        Message::ToastSeed => "Seed",
+        Message::ToastCopiedSeed => "Copied the seed to the clipboard",
+        Message::ToastNoSeed => "There has been no random soup yet",
+        Message::ToastSelectionCopied => "Copied the selection; paste it with Ctrl+V",
+        Message::ToastSelectionCut => "Cut the selection; paste it with Ctrl+V",
+        Message::ToastNothingToPaste => "Nothing to paste; select cells with Ctrl+Shift+drag and copy them first",
+        Message::ToastNothingSelected => "Nothing selected; drag with Ctrl+Shift over cells while paused",
+        Message::ToastPauseToSelect => "Pause to select cells",
+        Message::ToastDownloading => "Downloading",
+        Message::ToastSpeed => "Speed",
+        Message::ToastSpeedStep => "Speed step",
+        Message::GensPerSecond => "gens/s",
+        Message::HudPaused => "paused",
+        Message::HudRunning => "running",
+        Message::Edges => "Edges",
+        Message::Stamp => "Stamp",
+        Message::CensusTitle => "Census",
+        Message::CensusOthers => "others",
+        Message::CensusUnidentified => "unidentified",
+        Message::CensusEmpty => "No objects",
+        Message::GalleryEmpty => "No snapshots yet; one is taken every few generations",
+        Message::ToastGalleryRestored => "Back at generation",
+        Message::ToastGalleryNeedsHistory => "Going back to a snapshot needs history_depth above 0",
+        Message::StampsTitle => "Stamp a pattern",
+        Message::StampsHelp => "Then R: rotate, click: place, right click or Escape: done",
+        Message::EdgesWrap => "wrap around",
+        Message::EdgesBounded => "bounded",
+        Message::ToastUniverse => "Universe",
+        Message::ToastBackground => "Background",
+        Message::ErrorCannotRead => "cannot read {}: {}",
+        Message::ErrorStoppedSpectating => "stopped spectating: {}",
+        Message::ErrorCannotSave => "cannot save {}: {}",
+        Message::ErrorCannotExport => "cannot export {}: {}",
+        Message::ErrorCannotLoad => "cannot load {}: {}",
+        Message::ErrorCannotOpen => "cannot open {}: {}",
+        Message::ErrorCannotLoadGun => "cannot load the glider gun: {}",
+        Message::ErrorExpectedSize => "expected a size like `160x120 top-left`",
+        Message::ErrorNotCopies => "`{}` is not a number of copies",
+        Message::ErrorNoSoups => "Catagolue lists no soups with {} under {}",
+        Message::ErrorCannotFetchSoups => "cannot fetch soups with {}: {}",
+        Message::ErrorClipboardNoPattern => "the clipboard does not hold a pattern",
+        Message::ErrorCannotReadClipboardPattern => "cannot read a pattern from the clipboard: {}",
+        Message::ErrorCannotLoadPattern => "cannot load a pattern: {}",
+        Message::ErrorCannotReadClipboard => "cannot read the clipboard: {}",
+        Message::ErrorClipboardNoUrl => "the clipboard does not hold an http(s) URL",
+        Message::ErrorCannotWriteClipboard => "cannot write to the clipboard: {}",
+        Message::ErrorCannotLoadStamp => "cannot load the {} stamp: {}",
+        Message::ErrorCannotSwitch => "cannot switch to {}: {}",
+        Message::ErrorCannotSeed => "cannot seed {}: {}",
+        Message::ErrorCannotSaveRecording => "cannot save the recording: {}",
+        Message::Recording => "REC {}",
+        Message::PanelTitle => "Controls",
+        Message::PanelPlay => "Play",
+        Message::PanelPause => "Pause",
+        Message::PanelStep => "Step",
+        Message::PanelDelay => "Delay",
+        Message::PanelRule => "Rule",
+        Message::PanelResize => "Resize",
+        Message::PanelTheme => "Theme",
+        Message::PanelClear => "Clear",
+        Message::PanelRandomize => "Randomize",
+        Message::PanelSave => "Quicksave",
+        Message::PanelLoad => "Quickload",
+        Message::PanelSaveWorkspace => "Save workspace…",
+        Message::PanelOpenWorkspace => "Open workspace…",
+        Message::ToastNothingToRewind => "No earlier generations to rewind to",
+        Message::ToastRewound => "Rewound to generation",
+        Message::ScatterPrompt => "Copies to scatter across the grid",
+        Message::ToastScattered => "Scattered copies",
+        Message::ToastBrush => "Brush",
+        Message::BrushSquare => "square",
+        Message::BrushCircle => "circle",
+        Message::PopulationGraph => "Population, highest",
+        Message::AlarmPrompt => "Alarm limits (e.g. >10000 <50, empty for none)",
+        Message::ToastAlarmAbove => "Alarm: the population rose above",
+        Message::ToastAlarmBelow => "Alarm: the population dropped below",
+        Message::ToastAlarmSet => "Alarm set",
+        Message::ToastAlarmOff => "Alarm off",
+        Message::ToastFollowing => "Following the live cells",
+        Message::ToastNotFollowing => "No longer following the live cells",
+        Message::ToastFuzzyOn => "Fuzzy Life: cells between dead and alive; Shift+Page Up/Down to smooth",
+        Message::ToastFuzzyOff => "Back to dead and alive cells",
+        Message::ToastSmoothing => "Smoothing",
+        Message::ViewFlat => "Flat view",
+        Message::ViewSpaceTime => "Space-time view",
+        Message::ViewIsometric => "Isometric view",
+        Message::SeedingTitle => "Seed the grid with",
+        Message::SeedUniform => "uniform noise",
+        Message::SeedDisk => "a disk of noise",
+        Message::SeedRing => "a ring of noise",
+        Message::SeedStripes => "stripes of noise",
+        Message::SeedGradient => "a gradient of density from left to right",
+        Message::SeedingSymmetry => "symmetry",
+        Message::SeedingCancel => "Escape: cancel",
+        Message::ToastRuleChanged => "Rule changed to",
+        Message::ToastRuleRevert => "Backspace: back to",
+        Message::ToastRuleUnsupported => "Unsupported rule",
+        Message::PlacementsTitle => "Placed patterns",
+        Message::PlacementsEmpty => "Nothing placed yet",
+        Message::PlacementsKeys => {
+            "Up/Down: select  Enter: place again  Delete: remove\nR: rotate  F: mirror  Click: move  O: close"
+        }
+        Message::Unnamed => "unnamed",
+        Message::Mirrored => "mirrored",
+        Message::SaveWorkspacePrompt => "Save the workspace as",
+        Message::OpenWorkspacePrompt => "Open the workspace",
+        Message::ToastNoPatterns => "No .rle or .cells files in resources/patterns",
+        Message::Overlay => "Blueprint",
+        Message::OverlayMissing => "missing",
+        Message::OverlayExtra => "extra",
+        Message::RulebookTitle => "Switch to the rule",
+        Message::RulebookStart => "Press Space to run the demo, or X for another rule",
+        Message::RuleLife => "Conway's Game of Life: a dead cell with 3 live neighbors is born, a live cell with 2 or 3 survives. The R-pentomino shown takes over a thousand generations to settle.",
+        Message::RuleHighLife => "Like Life, but cells are also born with 6 neighbors. Famous for its replicator, shown here, which copies itself again and again.",
+        Message::RuleSeeds => "Cells are born with exactly 2 neighbors and never survive. Almost any seed, even the two cells shown, explodes into chaos.",
+        Message::RuleDayAndNight => "Live and dead cells behave the same way, so patterns of dead cells on live ground act like live cells on dead ground. Soups settle into large blobs of either.",
+        Message::RuleLifeWithoutDeath => "Cells are born like in Life and never die. Seeds such as the acorn shown grow into ever expanding blots fringed with ladders.",
+        Message::RuleReplicator => "Cells are born and survive with an odd number of neighbors. Every pattern, like the three cells shown, keeps making copies of itself.",
+        Message::RuleDiamoeba => "Soups grow into large diamond-shaped amoebas with chaotic edges, which slowly grow and shrink.",
+        Message::RuleWireworld => "Electrons run along wires of conductor, drawn like live cells; Shift+Z over a wire puts an electron on it. Heads become tails and tails conductor again, which is enough to build logic gates and whole computers.",
+        Message::ToastNoMultiState => "Only cells of a multi-state automaton such as Wireworld have states to cycle",
+        Message::ToastUndone => "Undone",
+        Message::ToastRedone => "Redone",
+        Message::ToastNothingToUndo => "Nothing to undo",
+        Message::ToastNothingToRedo => "Nothing to redo",
+        Message::ToastJumpedAhead => "Jumped ahead",
+        Message::ToastCannotJump => {
+            "Jumping ahead needs --engine hashlife, bounded edges, no frozen regions and no B0 rule"
+        }
+        Message::ToastEnginesDisagree => "The engines disagree in generation",
+        Message::ToastNoPeriod => "No period detected yet; let the pattern settle first",
+        Message::RulePrompt => "Rule in B/S notation, e.g. B36/S23 for HighLife",
+        Message::CatagoluePrompt => "apgcode to load, e.g. xq4_153, or soup xq4_153 for a soup from Catagolue in which it turned up",
+        Message::ResizePrompt => {
+            "Universe size and the part that stays in place: center, top-left, top-right, bottom-left or bottom-right"
+        }
+    }
+}
+
+fn german(message: Message) -> &'static str {
+    match message {
+        Message::WindowTitle => "Krida - Spiel des Lebens",
+        Message::Gliders => "Gleiter",
+        Message::Lwss => "LWSS",
+        Message::Fps => "Bilder/s",
+        Message::FrameTime => "Bild",
+        Message::Memory => "Speicher",
+        Message::MemoryGrid => "Gitter",
+        Message::MemoryHistory => "Verlauf",
+        Message::MemoryPeriod => "Periodenerkennung",
+        Message::MemoryLifespans => "Lebensdauern",
+        Message::MemoryRewind => "Zurückspulen",
+        Message::LifespanChart => "Lebensdauer (Generationen)",
+        Message::TutorialPlace => "Klicke auf das Gitter, um Zellen zum Leben zu erwecken",
+        Message::TutorialResume => "Drücke die Leertaste, um die Simulation zu starten",
+        Message::TutorialPause => "Drücke die Leertaste erneut, um sie anzuhalten",
+        Message::TutorialStep => "Drücke im angehaltenen Zustand N für eine einzelne Generation",
+        Message::TutorialGun => "Drücke G, um die Gosper-Gleiterkanone zu laden",
+        Message::TutorialWatch => "Drücke die Leertaste und sieh zu, wie sie Gleiter abfeuert",
+        Message::TutorialDone => "Das war's! Mit T startet das Tutorial erneut",
+        Message::TutorialSkip => "T: Tutorial schließen",
+        Message::PuzzleLevel => "Level",
+        Message::PuzzleCells => "Zellen",
+        Message::Generation => "Generation",
+        Message::PuzzleEditing => "Setze deine Zellen und drücke dann die Leertaste",
+        Message::PuzzleSolved => "Gelöst! Weiter zum nächsten Level mit Enter",
+        Message::PuzzleFailed => "Diesmal nicht. Neuer Versuch mit C",
+        Message::PuzzleComplete => "Gelöst! Das war das letzte Level",
+        Message::AchievementsTitle => "Entdeckungen",
+        Message::AchievementUnlocked => "Neue Entdeckung",
+        Message::AchievementGeneration => "Generation",
+        Message::AchievementFirstGlider => "Erster Gleiter gesichtet",
+        Message::AchievementFirstLwss => "Erstes leichtes Raumschiff gesichtet",
+        Message::AchievementGunBuilt => "Erste Kanone gebaut",
+        Message::AchievementPeriod15 => "Oszillator mit Periode 15 gefunden",
+        Message::AchievementPeriod30 => "Oszillator mit Periode 30 gefunden",
+        Message::AchievementCrowded => "Mehr als 5000 Zellen",
+        Message::AchievementMarathon => "10 000 Generationen in einem Lauf",
+        Message::DailySoup => "Tagessuppe",
+        Message::Population => "Population",
+        Message::EditorTitle => "Mustereditor",
+        Message::EditorKeys => {
+            "Klick: umschalten  M: Symmetrie  C: leeren  Enter: im Sandkasten testen  E: zurück zum Sandkasten"
+        }
+        Message::SymmetryNone => "keine Symmetrie",
+        Message::SymmetryLeftRight => "links-rechts gespiegelt",
+        Message::SymmetryTopBottom => "oben-unten gespiegelt",
+        Message::SymmetryFourWay => "in beide Richtungen gespiegelt",
+        Message::SymmetryRotational => "um eine halbe Drehung gedreht",
+        Message::LabelPrompt => "Beschriftung der Zelle (Enter: speichern, leer: entfernen, Escape: abbrechen)",
+        Message::MeasureHint => "Messen: klicke auf zwei Zellen (M: Messen beenden)",
+        Message::MeasureDiagonal => "Diagonale",
+        Message::ToastSaved => "Gespeichert unter",
+        Message::ToastLoaded => "Geladen:",
+        Message::ToastExported => "Exportiert nach",
+        Message::ToastCopied => "Lebende Zellen in die Zwischenablage kopiert",
+        Message::ToastRecording => "Aufnahme läuft; F9 beendet sie und speichert ein GIF",
+        Message::ToastRecordingFull => "Die Aufnahme hat ihre Höchstlänge erreicht",
+        Message::ToastSavingRecording => "Speichere die Aufnahme unter",
+        Message::ToastCopiedRle => "Lebende Zellen als RLE in die Zwischenablage kopiert",
+        Message::ToastNothingToCopy => "Es gibt keine lebenden Zellen zum Kopieren",
+        Message::ToastNothingToExport => "Es gibt keine lebenden Zellen zum Exportieren",
+        Message::ToastSeed => "Startwert",
+        Message::ToastCopiedSeed => "Startwert in die Zwischenablage kopiert",
+        Message::ToastNoSeed => "Es gab noch keine Zufallssuppe",
+        Message::ToastSelectionCopied => "Auswahl kopiert; füge sie mit Strg+V ein",
+        Message::ToastSelectionCut => "Auswahl ausgeschnitten; füge sie mit Strg+V ein",
+        Message::ToastNothingToPaste => "Nichts zum Einfügen; wähle erst Zellen mit Strg+Umschalt+Ziehen aus und kopiere sie",
+        Message::ToastNothingSelected => "Nichts ausgewählt; ziehe im Pausenmodus mit Strg+Umschalt über Zellen",
+        Message::ToastPauseToSelect => "Zum Auswählen pausieren",
+        Message::ToastDownloading => "Lade herunter:",
+        Message::ToastSpeed => "Geschwindigkeit",
+        Message::ToastSpeedStep => "Schrittweite",
+        Message::GensPerSecond => "Gen./s",
+        Message::HudPaused => "angehalten",
+        Message::HudRunning => "läuft",
+        Message::Edges => "Ränder",
+        Message::Stamp => "Stempel",
+        Message::CensusTitle => "Zählung",
+        Message::CensusOthers => "weitere",
+        Message::CensusUnidentified => "nicht erkannt",
+        Message::CensusEmpty => "Keine Objekte",
+        Message::GalleryEmpty => "Noch keine Schnappschüsse; alle paar Generationen wird einer gemacht",
+        Message::ToastGalleryRestored => "Zurück bei Generation",
+        Message::ToastGalleryNeedsHistory => "Zurückgehen zu einem Schnappschuss braucht history_depth über 0",
+        Message::StampsTitle => "Ein Muster stempeln",
+        Message::StampsHelp => "Dann R: drehen, Klick: setzen, Rechtsklick oder Escape: fertig",
+        Message::EdgesWrap => "verbunden",
+        Message::EdgesBounded => "begrenzt",
+        Message::ToastUniverse => "Universum",
+        Message::ToastBackground => "Hintergrund",
+        Message::ErrorCannotRead => "{} kann nicht gelesen werden: {}",
+        Message::ErrorStoppedSpectating => "Zuschauen beendet: {}",
+        Message::ErrorCannotSave => "{} kann nicht gespeichert werden: {}",
+        Message::ErrorCannotExport => "{} kann nicht exportiert werden: {}",
+        Message::ErrorCannotLoad => "{} kann nicht geladen werden: {}",
+        Message::ErrorCannotOpen => "{} kann nicht geöffnet werden: {}",
+        Message::ErrorCannotLoadGun => "Die Gleiterkanone kann nicht geladen werden: {}",
+        Message::ErrorExpectedSize => "Erwartet wird eine Größe wie `160x120 top-left`",
+        Message::ErrorNotCopies => "`{}` ist keine Anzahl von Kopien",
+        Message::ErrorNoSoups => "Catagolue führt keine Suppen mit {} unter {}",
+        Message::ErrorCannotFetchSoups => "Suppen mit {} können nicht abgerufen werden: {}",
+        Message::ErrorClipboardNoPattern => "Die Zwischenablage enthält kein Muster",
+        Message::ErrorCannotReadClipboardPattern => {
+            "Aus der Zwischenablage kann kein Muster gelesen werden: {}"
+        }
+        Message::ErrorCannotLoadPattern => "Das Muster kann nicht geladen werden: {}",
+        Message::ErrorCannotReadClipboard => "Die Zwischenablage kann nicht gelesen werden: {}",
+        Message::ErrorClipboardNoUrl => "Die Zwischenablage enthält keine http(s)-URL",
+        Message::ErrorCannotWriteClipboard => {
+            "In die Zwischenablage kann nicht geschrieben werden: {}"
+        }
+        Message::ErrorCannotLoadStamp => "Der Stempel {} kann nicht geladen werden: {}",
+        Message::ErrorCannotSwitch => "Wechsel zu {} nicht möglich: {}",
+        Message::ErrorCannotSeed => "{} kann nicht gesät werden: {}",
+        Message::ErrorCannotSaveRecording => "Die Aufnahme kann nicht gespeichert werden: {}",
+        Message::Recording => "AUFN {}",
+        Message::PanelTitle => "Steuerung",
+        Message::PanelPlay => "Start",
+        Message::PanelPause => "Pause",
+        Message::PanelStep => "Schritt",
+        Message::PanelDelay => "Verzögerung",
+        Message::PanelRule => "Regel",
+        Message::PanelResize => "Größe ändern",
+        Message::PanelTheme => "Farbschema",
+        Message::PanelClear => "Leeren",
+        Message::PanelRandomize => "Zufall",
+        Message::PanelSave => "Schnellspeichern",
+        Message::PanelLoad => "Schnell laden",
+        Message::PanelSaveWorkspace => "Arbeitsbereich speichern…",
+        Message::PanelOpenWorkspace => "Arbeitsbereich öffnen…",
+        Message::ToastNothingToRewind => "Keine früheren Generationen zum Zurückspulen",
+        Message::ToastRewound => "Zurückgespult zu Generation",
+        Message::ScatterPrompt => "Kopien, die über das Gitter verstreut werden",
+        Message::ToastScattered => "Verstreute Kopien",
+        Message::ToastBrush => "Pinsel",
+        Message::BrushSquare => "Quadrat",
+        Message::BrushCircle => "Kreis",
+        Message::PopulationGraph => "Population, höchste",
+        Message::AlarmPrompt => "Alarmgrenzen (z. B. >10000 <50, leer für keine)",
+        Message::ToastAlarmAbove => "Alarm: die Population stieg über",
+        Message::ToastAlarmBelow => "Alarm: die Population fiel unter",
+        Message::ToastAlarmSet => "Alarm gesetzt",
+        Message::ToastAlarmOff => "Alarm aus",
+        Message::ToastFollowing => "Die Ansicht folgt den lebenden Zellen",
+        Message::ToastNotFollowing => "Die Ansicht folgt den lebenden Zellen nicht mehr",
+        Message::ToastFuzzyOn => "Unscharfes Leben: Zellen zwischen tot und lebendig; Umschalt+Bild auf/ab glättet",
+        Message::ToastFuzzyOff => "Wieder nur tote und lebende Zellen",
+        Message::ToastSmoothing => "Glättung",
+        Message::ViewFlat => "Flache Ansicht",
+        Message::ViewSpaceTime => "Raumzeit-Ansicht",
+        Message::ViewIsometric => "Isometrische Ansicht",
+        Message::SeedingTitle => "Das Gitter füllen mit",
+        Message::SeedUniform => "gleichmäßigem Rauschen",
+        Message::SeedDisk => "einer Scheibe aus Rauschen",
+        Message::SeedRing => "einem Ring aus Rauschen",
+        Message::SeedStripes => "Streifen aus Rauschen",
+        Message::SeedGradient => "einer von links nach rechts zunehmenden Dichte",
+        Message::SeedingSymmetry => "Symmetrie",
+        Message::SeedingCancel => "Escape: abbrechen",
+        Message::ToastRuleChanged => "Regel geändert zu",
+        Message::ToastRuleRevert => "Rücktaste: zurück zu",
+        Message::ToastRuleUnsupported => "Nicht unterstützte Regel",
+        Message::PlacementsTitle => "Platzierte Muster",
+        Message::PlacementsEmpty => "Noch nichts platziert",
+        Message::PlacementsKeys => {
+            "Hoch/Runter: auswählen  Enter: erneut platzieren  Entf: entfernen\nR: drehen  F: spiegeln  Klick: verschieben  O: schließen"
+        }
+        Message::Unnamed => "unbenannt",
+        Message::Mirrored => "gespiegelt",
+        Message::SaveWorkspacePrompt => "Arbeitsbereich speichern als",
+        Message::OpenWorkspacePrompt => "Arbeitsbereich öffnen",
+        Message::ToastNoPatterns => "Keine .rle- oder .cells-Dateien in resources/patterns",
+        Message::Overlay => "Bauplan",
+        Message::OverlayMissing => "fehlend",
+        Message::OverlayExtra => "überzählig",
+        Message::RulebookTitle => "Zur Regel wechseln",
+        Message::RulebookStart => "Leertaste startet die Vorführung, X wählt eine andere Regel",
+        Message::RuleLife => "Conways Spiel des Lebens: Eine tote Zelle mit 3 lebenden Nachbarn wird geboren, eine lebende mit 2 oder 3 überlebt. Das gezeigte R-Pentomino braucht über tausend Generationen, um zur Ruhe zu kommen.",
+        Message::RuleHighLife => "Wie Life, aber Zellen werden auch mit 6 Nachbarn geboren. Berühmt für seinen Replikator, hier gezeigt, der sich immer wieder selbst kopiert.",
+        Message::RuleSeeds => "Zellen werden mit genau 2 Nachbarn geboren und überleben nie. Fast jeder Anfang, selbst die zwei gezeigten Zellen, explodiert ins Chaos.",
+        Message::RuleDayAndNight => "Lebende und tote Zellen verhalten sich gleich, tote Muster auf lebendem Grund also wie lebende auf totem. Suppen zerfallen in große Flecken von beidem.",
+        Message::RuleLifeWithoutDeath => "Zellen werden wie in Life geboren und sterben nie. Anfänge wie die gezeigte Eichel wachsen zu immer größeren Flecken mit Leitern am Rand.",
+        Message::RuleReplicator => "Zellen werden bei einer ungeraden Zahl von Nachbarn geboren und überleben dabei. Jedes Muster, wie die drei gezeigten Zellen, kopiert sich immer weiter.",
+        Message::RuleDiamoeba => "Suppen wachsen zu großen rautenförmigen Amöben mit chaotischen Rändern, die langsam wachsen und schrumpfen.",
+        Message::RuleWireworld => "Elektronen laufen über Leiterbahnen, die wie lebende Zellen gezeichnet werden; Umschalt+Z über einer Leitung setzt ein Elektron darauf. Köpfe werden zu Schwänzen und Schwänze wieder zu Leitern, was für Logikgatter und ganze Computer reicht.",
+        Message::ToastNoMultiState => "Nur Zellen eines Automaten mit mehreren Zuständen wie Wireworld haben Zustände zum Durchschalten",
+        Message::ToastUndone => "Rückgängig gemacht",
+        Message::ToastRedone => "Wiederhergestellt",
+        Message::ToastNothingToUndo => "Nichts rückgängig zu machen",
+        Message::ToastNothingToRedo => "Nichts wiederherzustellen",
+        Message::ToastJumpedAhead => "Vorgesprungen",
+        Message::ToastCannotJump => {
+            "Vorspringen braucht --engine hashlife, feste Ränder, keine eingefrorenen Bereiche und keine B0-Regel"
+        }
+        Message::ToastEnginesDisagree => "Die Engines weichen voneinander ab in Generation",
+        Message::ToastNoPeriod => "Noch keine Periode erkannt; lass das Muster erst zur Ruhe kommen",
+        Message::RulePrompt => "Regel in B/S-Notation, z. B. B36/S23 für HighLife",
+        Message::CatagoluePrompt => "apgcode zum Laden, z. B. xq4_153, oder soup xq4_153 für eine Suppe von Catagolue, in der es vorkam",
+        Message::ResizePrompt => {
+            "Größe des Universums und der Teil, der an seinem Platz bleibt: center, top-left, top-right, bottom-left oder bottom-right"
+        }
+    }
+}
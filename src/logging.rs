@@ -0,0 +1,57 @@
+use std::io;
+use tracing::Level;
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{fmt, Layer};
+
+use crate::config;
+
+// Days of logs kept in the data directory.
+const MAX_LOG_FILES: usize = 7;
+
+/// Send events at `level` and above to stderr and to a log file in the data
+/// directory that starts afresh every day.
+///
+/// The returned guard writes out what is still buffered for the file when dropped.
+pub fn init(level: Level) -> Option<WorkerGuard> {
+    let filter = LevelFilter::from_level(level);
+    let stderr = fmt::layer().with_writer(io::stderr).with_filter(filter);
+    let (file, guard) = match log_file() {
+        Ok(Some(appender)) => {
+            let (writer, guard) = tracing_appender::non_blocking(appender);
+            let layer = fmt::layer()
+                .with_ansi(false)
+                .with_writer(writer)
+                .with_filter(filter);
+            (Some(layer), Some(guard))
+        }
+        Ok(None) => (None, None),
+        Err(e) => {
+            eprintln!("krida: cannot open the log file: {}", e);
+            (None, None)
+        }
+    };
+    tracing_subscriber::registry()
+        .with(stderr)
+        .with(file)
+        .init();
+    guard
+}
+
+/// The daily log file, if the data directory is known.
+fn log_file() -> Result<Option<RollingFileAppender>, String> {
+    let Some(dirs) = config::project_dirs() else {
+        return Ok(None);
+    };
+    RollingFileAppender::builder()
+        .rotation(Rotation::DAILY)
+        .filename_prefix("krida")
+        .filename_suffix("log")
+        .max_log_files(MAX_LOG_FILES)
+        .build(dirs.data_dir().join("logs"))
+        .map(Some)
+        .map_err(|e| e.to_string())
+}
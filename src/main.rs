@@ -1,12 +1,169 @@
+mod achievements;
+mod alarm;
+mod annotations;
+mod background;
+mod batch;
+mod bitgrid;
+mod border;
+mod brush;
+mod camera;
+mod catagolue;
+mod census;
+mod cli;
+mod clipboard;
+mod config;
+mod crosscheck;
+mod daily;
+mod delta;
+mod download;
+mod editor;
+mod emergency;
+mod emitter;
+mod engine;
+mod export;
+mod fuzzy;
+mod gallery;
 mod game;
+mod graph;
+mod hashlife;
+mod headless;
+mod heat;
+mod history;
+mod hud;
+mod isometric;
+mod library;
+mod lifespan;
+mod locale;
+mod logging;
+mod measure;
+mod metrics;
+mod midi;
+mod mouse;
+mod multistate;
+mod osc;
+mod overlay;
+mod panel;
+mod pattern;
+mod perf;
+mod period;
+mod placements;
+mod prompt;
+mod puzzle;
+mod recording;
+mod render;
+mod rewind;
+mod rule;
+mod rulebook;
+mod save;
+mod schedule;
+mod script;
+mod seeding;
+mod session;
+mod soup;
+mod spaceships;
+mod spacetime;
+mod stamps;
+mod stream;
+mod theme;
+mod tick;
+mod toast;
+mod torus;
+mod trails;
+mod tutorial;
+mod universe;
+mod verify;
+mod watcher;
+mod wireworld;
+mod workspace;
 
 use ggez::conf;
 use ggez::event;
+use ggez::winit::window::WindowLevel;
 use ggez::{ContextBuilder, GameResult};
 use std::env;
+use std::io::{self, Read};
 use std::path;
+use std::process;
+use tracing::{error, warn};
+
+use config::Config;
+use locale::{tr, Language, Message};
+use pattern::Pattern;
+use session::Session;
 
 fn main() -> GameResult {
+    let options = match cli::parse_args() {
+        cli::Command::Play(options) => options,
+        cli::Command::Batch(options) => {
+            if let Err(e) = batch::run(&options) {
+                eprintln!("krida: {}", e);
+                process::exit(1);
+            }
+            return Ok(());
+        }
+        cli::Command::Script(options) => {
+            if let Err(e) = script::run(&options) {
+                eprintln!("krida: {}", e);
+                process::exit(1);
+            }
+            return Ok(());
+        }
+        cli::Command::Verify(options) => {
+            if let Err(e) = verify::run(&options) {
+                eprintln!("krida: {}", e);
+                process::exit(1);
+            }
+            return Ok(());
+        }
+        cli::Command::TorusScan(options) => {
+            if let Err(e) = torus::run(&options) {
+                eprintln!("krida: {}", e);
+                process::exit(1);
+            }
+            return Ok(());
+        }
+        cli::Command::SoupSearch(options) => {
+            if let Err(e) = soup::run(&options) {
+                eprintln!("krida: {}", e);
+                process::exit(1);
+            }
+            return Ok(());
+        }
+    };
+    if let Some(generations) = options.headless {
+        let pattern = options.stdin.then(read_stdin_pattern).transpose();
+        if let Err(e) = pattern.and_then(|pattern| {
+            headless::run(&options, generations, pattern).map_err(|e| e.to_string())
+        }) {
+            eprintln!("krida: {}", e);
+            process::exit(1);
+        }
+        return Ok(());
+    }
+    // Kept until the end of main so that the log file gets everything
+    let _log_guard = logging::init(options.log_level);
+    emergency::install_panic_hook();
+    let mut config = Config::load();
+    if let Some(size) = options.cell_size {
+        config.cell_width = Some(size);
+        config.cell_height = Some(size);
+    }
+    let language = match config.language.as_deref() {
+        Some(code) => Language::from_code(code).or_else(|| {
+            warn!("unknown language `{}`", code);
+            None
+        }),
+        None => Language::from_environment(),
+    };
+    locale::init(language.unwrap_or(Language::English));
+
+    // Read the pattern before opening the window so that a bad one fails fast
+    let pattern = options.stdin.then(|| {
+        read_stdin_pattern().unwrap_or_else(|e| {
+            error!("cannot read a pattern from stdin: {}", e);
+            process::exit(1);
+        })
+    });
     let resource_dir = if let Ok(manifest_dir) = env::var("CARGO_MANIFEST_DIR") {
         let mut path = path::PathBuf::from(manifest_dir);
         path.push("resources");
@@ -14,15 +171,45 @@ fn main() -> GameResult {
     } else {
         path::PathBuf::from("./resources")
     };
+    // The screensaver always looks the same and is not worth remembering
+    let session = if options.screensaver {
+        Session::default()
+    } else {
+        Session::load()
+    }
+    .overridden(&options);
+    let cell = config.cell_size();
     let (grid_width, grid_height) = (
-        (game::GRID_WIDTH as f32) * game::CELL_SIZE,
-        (game::GRID_HEIGHT as f32) * game::CELL_SIZE,
+        (session.grid_width as f32) * cell.width,
+        (session.grid_height as f32) * cell.height,
     );
     let cb = ContextBuilder::new("krida", "nealpro")
-        .window_setup(conf::WindowSetup::default().title("Krida - Game of Life"))
-        .window_mode(conf::WindowMode::default().dimensions(grid_width, grid_height))
+        .window_setup(conf::WindowSetup::default().title(tr(Message::WindowTitle)))
+        .window_mode(
+            conf::WindowMode::default()
+                .dimensions(grid_width, grid_height)
+                .resizable(true)
+                .borderless(options.borderless)
+                .fullscreen_type(if options.present {
+                    conf::FullscreenType::Desktop
+                } else {
+                    conf::FullscreenType::Windowed
+                }),
+        )
         .add_resource_path(resource_dir);
     let (mut ctx, event_loop) = cb.build()?;
-    let state = game::MainState::new(&mut ctx)?;
+    if options.always_on_top {
+        ctx.gfx.window().set_window_level(WindowLevel::AlwaysOnTop);
+    }
+    let state = game::MainState::new(&mut ctx, &options, &config, &session, pattern)?;
     event::run(ctx, event_loop, state)
 }
+
+/// Parse everything piped into the process as a single pattern.
+fn read_stdin_pattern() -> Result<Pattern, String> {
+    let mut text = String::new();
+    io::stdin()
+        .read_to_string(&mut text)
+        .map_err(|e| e.to_string())?;
+    Pattern::parse(&text)
+}
@@ -0,0 +1,84 @@
+use ggez::graphics::{Canvas, Color, DrawMode, DrawParam, Mesh, MeshBuilder, Rect, Text};
+use ggez::{Context, GameResult};
+
+use crate::locale::{tr, Message};
+use crate::render::CellSize;
+
+const COLOR: Color = Color::new(0.3, 0.8, 1.0, 1.0);
+
+/// Two cells picked with the mouse and the offset between them.
+pub struct Measure {
+    start: Option<(usize, usize)>,
+    end: Option<(usize, usize)>,
+}
+
+impl Measure {
+    pub fn new() -> Measure {
+        Measure {
+            start: None,
+            end: None,
+        }
+    }
+
+    /// Pick the first cell, then the second; a third click starts a new measurement.
+    pub fn click(&mut self, x: usize, y: usize) {
+        match (self.start, self.end) {
+            (Some(_), None) => self.end = Some((x, y)),
+            _ => {
+                self.start = Some((x, y));
+                self.end = None;
+            }
+        }
+    }
+
+    /// Highlight the picked cells, connect them and show the offset between them.
+    pub fn draw(&self, ctx: &mut Context, canvas: &mut Canvas, cell: CellSize) -> GameResult {
+        let center = |(x, y): (usize, usize)| cell.point(x as f32 + 0.5, y as f32 + 0.5);
+        let mut shapes = MeshBuilder::new();
+        for &(x, y) in self.start.iter().chain(&self.end) {
+            let rect = cell.rect(x as f32, y as f32, 1.0, 1.0);
+            shapes.rectangle(DrawMode::stroke(2.0), rect, COLOR)?;
+        }
+
+        let report = match (self.start, self.end) {
+            (Some(start), Some(end)) => {
+                if start != end {
+                    shapes.line(&[center(start), center(end)], 1.5, COLOR)?;
+                }
+                let dx = end.0 as i64 - start.0 as i64;
+                let dy = end.1 as i64 - start.1 as i64;
+                format!(
+                    "\u{394}x = {}  \u{394}y = {}  {}: {:.2}",
+                    dx,
+                    dy,
+                    tr(Message::MeasureDiagonal),
+                    (dx as f64).hypot(dy as f64)
+                )
+            }
+            _ => tr(Message::MeasureHint).to_string(),
+        };
+        if self.start.is_some() {
+            canvas.draw(&Mesh::from_data(ctx, shapes.build()), DrawParam::default());
+        }
+
+        let text = Text::new(report);
+        let size = text.measure(ctx)?;
+        let (window_width, window_height) = ctx.gfx.drawable_size();
+        let panel = Rect::new(
+            (window_width - size.x) / 2.0 - 8.0,
+            window_height - size.y - 24.0,
+            size.x + 16.0,
+            size.y + 16.0,
+        );
+        let backdrop =
+            Mesh::new_rectangle(ctx, DrawMode::fill(), panel, Color::new(0.0, 0.0, 0.0, 0.7))?;
+        canvas.draw(&backdrop, DrawParam::default());
+        canvas.draw(
+            &text,
+            DrawParam::default()
+                .dest([panel.x + 8.0, panel.y + 8.0])
+                .color(COLOR),
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,185 @@
+use std::fmt::Write as _;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use tracing::{debug, info};
+
+use crate::universe::Universe;
+
+// How often the published values are refreshed; the rates are averaged over it.
+const WINDOW: Duration = Duration::from_secs(1);
+// Scrapers that stop sending mid-request are dropped after this.
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The values last published.
+#[derive(Clone, Copy, Default)]
+struct Sample {
+    generation: u64,
+    population: usize,
+    paused: bool,
+    generations_per_second: f64,
+    frame_seconds: f64,
+    generations_total: u64,
+    frames_total: u64,
+}
+
+impl Sample {
+    /// The sample in the Prometheus text exposition format.
+    fn render(&self) -> String {
+        let metrics: [(&str, &str, &str, String); 7] = [
+            (
+                "krida_generation",
+                "gauge",
+                "Generation of the universe.",
+                self.generation.to_string(),
+            ),
+            (
+                "krida_population",
+                "gauge",
+                "Live cells.",
+                self.population.to_string(),
+            ),
+            (
+                "krida_paused",
+                "gauge",
+                "1 while the simulation is paused.",
+                u8::from(self.paused).to_string(),
+            ),
+            (
+                "krida_generations_per_second",
+                "gauge",
+                "Generations worked out per second, over the last second.",
+                self.generations_per_second.to_string(),
+            ),
+            (
+                "krida_frame_seconds",
+                "gauge",
+                "Average time between frames over the last second.",
+                self.frame_seconds.to_string(),
+            ),
+            (
+                "krida_generations_total",
+                "counter",
+                "Generations worked out since the start.",
+                self.generations_total.to_string(),
+            ),
+            (
+                "krida_frames_total",
+                "counter",
+                "Frames since the start.",
+                self.frames_total.to_string(),
+            ),
+        ];
+        let mut text = String::new();
+        for (name, kind, help, value) in metrics {
+            let _ = writeln!(
+                text,
+                "# HELP {} {}\n# TYPE {} {}\n{} {}",
+                name, help, name, kind, name, value
+            );
+        }
+        text
+    }
+}
+
+/// Serves the generation rate, the population and the frame time at
+/// `/metrics` in the Prometheus format, for monitoring unattended installations.
+pub struct MetricsServer {
+    published: Arc<Mutex<Sample>>,
+    /// The sample being gathered for the current window.
+    sample: Sample,
+    window_start: Instant,
+    window_generations: u64,
+    window_frames: u64,
+    window_frame_time: Duration,
+}
+
+impl MetricsServer {
+    /// Listen on an address such as `127.0.0.1:9184`, answering on a background thread.
+    pub fn start(address: &str) -> io::Result<MetricsServer> {
+        let listener = TcpListener::bind(address)?;
+        info!(
+            "serving metrics at http://{}/metrics",
+            listener.local_addr()?
+        );
+        let published = Arc::new(Mutex::new(Sample::default()));
+        let shared = Arc::clone(&published);
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let sample = *shared.lock().unwrap_or_else(|e| e.into_inner());
+                if let Err(e) = answer(stream, &sample) {
+                    debug!("cannot answer a metrics request: {}", e);
+                }
+            }
+        });
+        Ok(MetricsServer {
+            published,
+            sample: Sample::default(),
+            window_start: Instant::now(),
+            window_generations: 0,
+            window_frames: 0,
+            window_frame_time: Duration::ZERO,
+        })
+    }
+
+    /// Count a frame that took `frame_time` and worked out `generations`,
+    /// publishing new values once per window.
+    pub fn observe(
+        &mut self,
+        universe: &Universe,
+        paused: bool,
+        frame_time: Duration,
+        generations: u64,
+    ) {
+        self.window_generations += generations;
+        self.window_frames += 1;
+        self.window_frame_time += frame_time;
+        let elapsed = self.window_start.elapsed();
+        if elapsed < WINDOW {
+            return;
+        }
+        let sample = &mut self.sample;
+        sample.generation = universe.generation();
+        sample.population = universe.population();
+        sample.paused = paused;
+        sample.generations_per_second = self.window_generations as f64 / elapsed.as_secs_f64();
+        sample.frame_seconds = self.window_frame_time.as_secs_f64() / self.window_frames as f64;
+        sample.generations_total += self.window_generations;
+        sample.frames_total += self.window_frames;
+        *self.published.lock().unwrap_or_else(|e| e.into_inner()) = *sample;
+        self.window_start = Instant::now();
+        self.window_generations = 0;
+        self.window_frames = 0;
+        self.window_frame_time = Duration::ZERO;
+    }
+}
+
+/// Answer a single HTTP request with the metrics, or with 404 for any other path.
+fn answer(stream: TcpStream, sample: &Sample) -> io::Result<()> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let mut reader = BufReader::new(stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    // Skip the headers up to the blank line that ends them
+    let mut line = String::new();
+    while reader.read_line(&mut line)? > 2 {
+        line.clear();
+    }
+    let path = request_line.split_whitespace().nth(1).unwrap_or("");
+    let (status, body) = if path == "/metrics" || path.starts_with("/metrics?") {
+        ("200 OK", sample.render())
+    } else {
+        ("404 Not Found", "not found, try /metrics\n".to_string())
+    };
+    let mut stream = reader.into_inner();
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
@@ -0,0 +1,157 @@
+use midir::{MidiOutput, MidiOutputConnection};
+use serde::Deserialize;
+use tracing::{info, warn};
+
+// Status bytes, to be combined with the zero-based channel.
+const NOTE_ON: u8 = 0x90;
+const NOTE_OFF: u8 = 0x80;
+// Middle C, the note of the first lane when no notes are configured.
+const BASE_NOTE: u8 = 60;
+
+/// The `[midi]` table of the configuration.
+#[derive(Deserialize)]
+#[serde(default)]
+pub struct MidiConfig {
+    /// Part of the name of the output port; the first port is used if unset.
+    pub port: Option<String>,
+    /// MIDI channel from 1 to 16.
+    pub channel: u8,
+    /// Rows and columns that play a note when a cell is born in them.
+    pub rows: Vec<usize>,
+    pub columns: Vec<usize>,
+    /// Note of each lane, rows first; lanes without one count up chromatically
+    /// from middle C.
+    pub notes: Vec<u8>,
+    pub velocity: u8,
+}
+
+impl Default for MidiConfig {
+    fn default() -> MidiConfig {
+        MidiConfig {
+            port: None,
+            channel: 1,
+            rows: Vec::new(),
+            columns: Vec::new(),
+            notes: Vec::new(),
+            velocity: 100,
+        }
+    }
+}
+
+/// A row or a column of the grid that triggers a note.
+enum Lane {
+    Row(usize),
+    Column(usize),
+}
+
+/// Plays births in the selected rows and columns as notes, one step per
+/// generation, each note lasting until the next generation.
+pub struct MidiSender {
+    connection: MidiOutputConnection,
+    channel: u8,
+    velocity: u8,
+    lanes: Vec<(Lane, u8)>,
+    /// Notes still sounding from the previous generation.
+    playing: Vec<u8>,
+    /// Whether sending has failed, which is only logged the first time.
+    failed: bool,
+}
+
+impl MidiSender {
+    /// Connect to the configured output port.
+    pub fn new(config: &MidiConfig) -> Result<MidiSender, String> {
+        if !(1..=16).contains(&config.channel) {
+            return Err(format!(
+                "the channel must be from 1 to 16, not {}",
+                config.channel
+            ));
+        }
+        let output = MidiOutput::new("krida").map_err(|e| e.to_string())?;
+        let ports = output.ports();
+        let port = ports
+            .iter()
+            .find(|port| match &config.port {
+                Some(name) => output
+                    .port_name(port)
+                    .is_ok_and(|port_name| port_name.contains(name.as_str())),
+                None => true,
+            })
+            .ok_or_else(|| match &config.port {
+                Some(name) => format!("there is no output port named like `{}`", name),
+                None => "there is no output port".to_string(),
+            })?;
+        let name = output.port_name(port).unwrap_or_default();
+        let connection = output.connect(port, "krida").map_err(|e| e.to_string())?;
+        info!("sending MIDI to {}", name);
+        let lanes = config
+            .rows
+            .iter()
+            .map(|&y| Lane::Row(y))
+            .chain(config.columns.iter().map(|&x| Lane::Column(x)))
+            .enumerate()
+            .map(|(i, lane)| {
+                let note = config
+                    .notes
+                    .get(i)
+                    .copied()
+                    .unwrap_or_else(|| BASE_NOTE.saturating_add(i as u8));
+                (lane, note.min(127))
+            })
+            .collect();
+        Ok(MidiSender {
+            connection,
+            channel: config.channel - 1,
+            velocity: config.velocity.min(127),
+            lanes,
+            playing: Vec::new(),
+            failed: false,
+        })
+    }
+
+    /// End the notes of the last step and play the lanes with births between
+    /// `previous` and `current`.
+    pub fn send_generation(&mut self, previous: &[Vec<bool>], current: &[Vec<bool>]) {
+        self.silence();
+        let born = |x: usize, y: usize| {
+            current.get(y).and_then(|row| row.get(x)) == Some(&true) && !previous[y][x]
+        };
+        let width = current.first().map_or(0, Vec::len);
+        let notes: Vec<u8> = self
+            .lanes
+            .iter()
+            .filter(|(lane, _)| match *lane {
+                Lane::Row(y) => (0..width).any(|x| born(x, y)),
+                Lane::Column(x) => (0..current.len()).any(|y| born(x, y)),
+            })
+            .map(|&(_, note)| note)
+            .collect();
+        for note in notes {
+            if !self.playing.contains(&note) {
+                self.send(&[NOTE_ON | self.channel, note, self.velocity]);
+                self.playing.push(note);
+            }
+        }
+    }
+
+    /// End every note still sounding.
+    pub fn silence(&mut self) {
+        for note in std::mem::take(&mut self.playing) {
+            self.send(&[NOTE_OFF | self.channel, note, 0]);
+        }
+    }
+
+    fn send(&mut self, message: &[u8]) {
+        if let Err(e) = self.connection.send(message) {
+            if !self.failed {
+                warn!("cannot send MIDI: {}", e);
+                self.failed = true;
+            }
+        }
+    }
+}
+
+impl Drop for MidiSender {
+    fn drop(&mut self) {
+        self.silence();
+    }
+}
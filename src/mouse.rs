@@ -0,0 +1,196 @@
+use ggez::graphics::{Canvas, Color, DrawMode, DrawParam, Mesh, MeshBuilder};
+use ggez::input::keyboard::KeyMods;
+use ggez::input::mouse::MouseButton;
+use ggez::{Context, GameResult};
+use serde::Deserialize;
+use std::collections::HashMap;
+use tracing::warn;
+
+use crate::render::CellSize;
+use crate::universe::Region;
+
+const LINE_PREVIEW_COLOR: Color = Color::new(1.0, 0.8, 0.2, 0.5);
+const SELECTION_COLOR: Color = Color::new(0.3, 0.9, 1.0, 0.9);
+
+/// What a mouse button does on the grid.
+#[derive(Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum MouseAction {
+    /// Bring a dead cell to life or kill a live one, and paint the cells dragged
+    /// over the same way.
+    Toggle,
+    /// Kill the cells under the cursor while the button is held down.
+    Erase,
+    /// Fill the dead region under the cursor, or clear the live one.
+    Fill,
+    /// Label the cell under the cursor.
+    Label,
+    /// Draw a line of live cells from where the button is pressed to where it is released.
+    Line,
+    /// Move the view along with the mouse while the button is held down.
+    Pan,
+    /// Freeze the rectangle dragged out, or thaw the frozen region clicked.
+    Freeze,
+    /// Select the rectangle dragged out while paused, to copy or cut it.
+    Select,
+    /// Do nothing, to switch off a default binding.
+    None,
+}
+
+/// A mouse button together with the modifier keys held down while pressing it.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+struct Chord {
+    button: MouseButton,
+    // Bits of the `KeyMods`, which cannot be hashed themselves
+    mods: u8,
+}
+
+impl Chord {
+    /// Parse a chord such as `left`, `ctrl+left` or `shift+alt+middle`.
+    fn parse(text: &str) -> Option<Chord> {
+        let mut mods = KeyMods::NONE;
+        let mut parts: Vec<&str> = text.split('+').map(str::trim).collect();
+        let button = match parts.pop()?.to_ascii_lowercase().as_str() {
+            "left" => MouseButton::Left,
+            "right" => MouseButton::Right,
+            "middle" => MouseButton::Middle,
+            _ => return None,
+        };
+        for part in parts {
+            mods |= match part.to_ascii_lowercase().as_str() {
+                "shift" => KeyMods::SHIFT,
+                "ctrl" => KeyMods::CTRL,
+                "alt" => KeyMods::ALT,
+                "logo" => KeyMods::LOGO,
+                _ => return None,
+            };
+        }
+        Some(Chord {
+            button,
+            mods: mods.bits(),
+        })
+    }
+}
+
+/// Which action each mouse button and set of modifiers triggers.
+pub struct MouseBindings {
+    actions: HashMap<Chord, MouseAction>,
+}
+
+impl MouseBindings {
+    /// The default bindings, overridden by the `[mouse]` table of the configuration.
+    pub fn new(config: &HashMap<String, MouseAction>) -> MouseBindings {
+        let mut actions: HashMap<Chord, MouseAction> = [
+            ("left", MouseAction::Toggle),
+            ("ctrl+left", MouseAction::Fill),
+            ("shift+left", MouseAction::Line),
+            ("right", MouseAction::Erase),
+            ("shift+right", MouseAction::Label),
+            ("middle", MouseAction::Pan),
+            ("alt+left", MouseAction::Freeze),
+            ("ctrl+shift+left", MouseAction::Select),
+        ]
+        .into_iter()
+        .filter_map(|(chord, action)| Some((Chord::parse(chord)?, action)))
+        .collect();
+        for (text, &action) in config {
+            match Chord::parse(text) {
+                Some(chord) => {
+                    actions.insert(chord, action);
+                }
+                None => warn!("ignoring the mouse binding `{}`, it is not a button", text),
+            }
+        }
+        MouseBindings { actions }
+    }
+
+    /// The action bound to `button` with exactly the modifiers `mods` held down.
+    pub fn action(&self, button: MouseButton, mods: KeyMods) -> MouseAction {
+        self.actions
+            .get(&Chord {
+                button,
+                mods: mods.bits(),
+            })
+            .copied()
+            .unwrap_or(MouseAction::None)
+    }
+}
+
+/// The cells on a straight line between two cells, both included.
+pub fn line(from: (usize, usize), to: (usize, usize)) -> Vec<(usize, usize)> {
+    let (mut x, mut y) = (from.0 as i64, from.1 as i64);
+    let (end_x, end_y) = (to.0 as i64, to.1 as i64);
+    let (dx, dy) = ((end_x - x).abs(), -(end_y - y).abs());
+    let (step_x, step_y) = ((end_x - x).signum(), (end_y - y).signum());
+    let mut error = dx + dy;
+    let mut cells = vec![(x as usize, y as usize)];
+    while (x, y) != (end_x, end_y) {
+        if 2 * error >= dy {
+            error += dy;
+            x += step_x;
+        }
+        if 2 * error <= dx {
+            error += dx;
+            y += step_y;
+        }
+        cells.push((x as usize, y as usize));
+    }
+    cells
+}
+
+/// Outline the rectangle being dragged out.
+pub fn draw_region_preview(
+    ctx: &mut Context,
+    canvas: &mut Canvas,
+    cell: CellSize,
+    region: Region,
+) -> GameResult {
+    let rect = cell.rect(
+        region.left as f32,
+        region.top as f32,
+        region.width as f32,
+        region.height as f32,
+    );
+    let mesh = Mesh::new_rectangle(ctx, DrawMode::stroke(2.0), rect, LINE_PREVIEW_COLOR)?;
+    canvas.draw(&mesh, DrawParam::default());
+    Ok(())
+}
+
+/// Outline the selected rectangle.
+pub fn draw_selection(
+    ctx: &mut Context,
+    canvas: &mut Canvas,
+    cell: CellSize,
+    region: Region,
+) -> GameResult {
+    let rect = cell.rect(
+        region.left as f32,
+        region.top as f32,
+        region.width as f32,
+        region.height as f32,
+    );
+    let mesh = Mesh::new_rectangle(ctx, DrawMode::stroke(1.0), rect, SELECTION_COLOR)?;
+    canvas.draw(&mesh, DrawParam::default());
+    Ok(())
+}
+
+/// Show where a line being drawn will bring cells to life.
+pub fn draw_line_preview(
+    ctx: &mut Context,
+    canvas: &mut Canvas,
+    cell: CellSize,
+    from: (usize, usize),
+    to: (usize, usize),
+) -> GameResult {
+    let mut builder = MeshBuilder::new();
+    for (x, y) in line(from, to) {
+        builder.rectangle(
+            DrawMode::fill(),
+            cell.rect(x as f32, y as f32, 1.0, 1.0),
+            LINE_PREVIEW_COLOR,
+        )?;
+    }
+    let mesh = Mesh::from_data(ctx, builder.build());
+    canvas.draw(&mesh, DrawParam::default());
+    Ok(())
+}
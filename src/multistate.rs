@@ -0,0 +1,167 @@
+use ggez::graphics::Color;
+
+use crate::locale::Message;
+use crate::universe::Universe;
+use crate::wireworld::Wireworld;
+
+/// The state of a cell of a multi-state automaton; 0 is always empty.
+pub type State = u8;
+pub const EMPTY: State = 0;
+
+/// A cellular automaton whose cells take more states than dead and alive.
+pub trait Automaton: Sync {
+    fn name(&self) -> &'static str;
+
+    fn description(&self) -> Message;
+
+    /// The state of cells drawn with the mouse.
+    fn drawn(&self) -> State;
+
+    /// The state a cell goes to when its state is cycled by hand.
+    fn cycled(&self, state: State) -> State;
+
+    /// The next state of a cell from its own and those of its eight neighbors,
+    /// cells past bounded edges being empty.
+    fn next(&self, state: State, neighbors: &[State; 8]) -> State;
+
+    /// The color a state is drawn in; empty cells are not drawn.
+    fn color(&self, state: State) -> Color;
+
+    /// A small pattern that shows the automaton off, rows first.
+    fn demo(&self) -> Vec<Vec<State>>;
+}
+
+/// Every built-in multi-state automaton, in the order of the rule menu after
+/// the life-like rules.
+pub const AUTOMATA: [&dyn Automaton; 1] = [&Wireworld];
+
+/// The grid of a multi-state automaton, kept beside the universe: the cells of
+/// the universe that are alive are the ones that are not empty, so that
+/// drawing, saving and everything else that only knows dead and alive still
+/// works on the shape of the pattern.
+pub struct MultiState {
+    automaton: Option<&'static dyn Automaton>,
+    states: Vec<Vec<State>>,
+    next: Vec<Vec<State>>,
+}
+
+impl MultiState {
+    pub fn new() -> MultiState {
+        MultiState {
+            automaton: None,
+            states: Vec::new(),
+            next: Vec::new(),
+        }
+    }
+
+    /// The automaton running instead of the universe's rule, if any.
+    pub fn automaton(&self) -> Option<&'static dyn Automaton> {
+        self.automaton
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.automaton.is_some()
+    }
+
+    /// Run an automaton from the next generation on, or go back to the
+    /// universe's rule with `None`, starting over from the live cells.
+    pub fn set_automaton(&mut self, automaton: Option<&'static dyn Automaton>) {
+        self.automaton = automaton;
+        self.states.clear();
+        self.next.clear();
+    }
+
+    /// The state of every cell, rows first.
+    pub fn states(&self) -> &[Vec<State>] {
+        &self.states
+    }
+
+    /// Take over the cells that were drawn, cleared or loaded since the last
+    /// generation: new live cells take the drawn state, new dead ones are
+    /// emptied, and the others keep theirs.
+    pub fn sync(&mut self, cells: &[Vec<bool>]) {
+        let Some(automaton) = self.automaton else {
+            return;
+        };
+        let state_of = |alive: bool| if alive { automaton.drawn() } else { EMPTY };
+        let same_size = self.states.len() == cells.len()
+            && self.states.first().map(Vec::len) == cells.first().map(Vec::len);
+        if !same_size {
+            self.states = cells
+                .iter()
+                .map(|row| row.iter().map(|&alive| state_of(alive)).collect())
+                .collect();
+            self.next = self.states.clone();
+            return;
+        }
+        for (states, row) in self.states.iter_mut().zip(cells) {
+            for (state, &alive) in states.iter_mut().zip(row) {
+                if (*state != EMPTY) != alive {
+                    *state = state_of(alive);
+                }
+            }
+        }
+    }
+
+    /// Set the states of a pattern of them with its top left corner at a cell.
+    pub fn place(&mut self, pattern: &[Vec<State>], left: usize, top: usize) {
+        for (dy, row) in pattern.iter().enumerate() {
+            for (dx, &state) in row.iter().enumerate() {
+                if let Some(cell) = self
+                    .states
+                    .get_mut(top + dy)
+                    .and_then(|states| states.get_mut(left + dx))
+                {
+                    *cell = state;
+                }
+            }
+        }
+    }
+
+    /// Cycle the state of a cell by hand, e.g. to put an electron on a wire,
+    /// returning whether the cell is alive now.
+    pub fn cycle(&mut self, x: usize, y: usize) -> bool {
+        let (Some(automaton), Some(state)) = (
+            self.automaton,
+            self.states.get_mut(y).and_then(|row| row.get_mut(x)),
+        ) else {
+            return false;
+        };
+        *state = automaton.cycled(*state);
+        *state != EMPTY
+    }
+
+    /// Which cells are alive, i.e. not empty.
+    pub fn cells(&self) -> Vec<Vec<bool>> {
+        self.states
+            .iter()
+            .map(|row| row.iter().map(|&state| state != EMPTY).collect())
+            .collect()
+    }
+
+    /// Work out the next generation under the automaton and the universe's
+    /// edges, and return which of its cells are alive.
+    pub fn step(&mut self, universe: &Universe) -> Vec<Vec<bool>> {
+        self.sync(universe.cells());
+        let Some(automaton) = self.automaton else {
+            return universe.cells().to_vec();
+        };
+        let states = &self.states;
+        for (y, row) in self.next.iter_mut().enumerate() {
+            for (x, next) in row.iter_mut().enumerate() {
+                let mut neighbors = [EMPTY; 8];
+                let offsets = (-1..=1)
+                    .flat_map(|dy| (-1..=1).map(move |dx| (dx, dy)))
+                    .filter(|&offset| offset != (0, 0));
+                for (neighbor, (dx, dy)) in neighbors.iter_mut().zip(offsets) {
+                    if let Some((nx, ny)) = universe.offset(x, y, dx, dy) {
+                        *neighbor = states[ny][nx];
+                    }
+                }
+                *next = automaton.next(states[y][x], &neighbors);
+            }
+        }
+        std::mem::swap(&mut self.states, &mut self.next);
+        self.cells()
+    }
+}
@@ -0,0 +1,95 @@
+use std::io;
+use std::net::{SocketAddr, ToSocketAddrs, UdpSocket};
+use tracing::warn;
+
+/// Sends what happens in each generation as OSC messages over UDP, to drive
+/// synthesizers and live-coding environments:
+///
+/// - `/krida/generation i`: the generation number
+/// - `/krida/population i`: the live cells
+/// - `/krida/births i` and `/krida/deaths i`: the cells that came to life or died
+/// - `/krida/rows i...`: for every row from the top, the cells that changed in it
+pub struct OscSender {
+    socket: UdpSocket,
+    target: SocketAddr,
+    /// Whether sending has failed, which is only logged the first time.
+    failed: bool,
+}
+
+impl OscSender {
+    /// Prepare to send to a target such as `127.0.0.1:57120`.
+    pub fn new(target: &str) -> io::Result<OscSender> {
+        let target = target.to_socket_addrs()?.next().ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, "the target has no address")
+        })?;
+        let local = if target.is_ipv4() {
+            "0.0.0.0:0"
+        } else {
+            "[::]:0"
+        };
+        Ok(OscSender {
+            socket: UdpSocket::bind(local)?,
+            target,
+            failed: false,
+        })
+    }
+
+    /// Send the messages for the step from `previous` to `current`.
+    pub fn send_generation(
+        &mut self,
+        previous: &[Vec<bool>],
+        current: &[Vec<bool>],
+        generation: u64,
+    ) {
+        let (mut population, mut births, mut deaths) = (0, 0, 0);
+        let mut rows = Vec::with_capacity(current.len());
+        for (before, now) in previous.iter().zip(current) {
+            let mut changed = 0;
+            for (&was, &is) in before.iter().zip(now) {
+                population += i32::from(is);
+                births += i32::from(is && !was);
+                deaths += i32::from(was && !is);
+                changed += i32::from(was != is);
+            }
+            rows.push(changed);
+        }
+        // OSC integers have 32 bits, long runs wrap around
+        let messages = [
+            message("/krida/generation", &[generation as i32]),
+            message("/krida/population", &[population]),
+            message("/krida/births", &[births]),
+            message("/krida/deaths", &[deaths]),
+            message("/krida/rows", &rows),
+        ];
+        for packet in &messages {
+            if let Err(e) = self.socket.send_to(packet, self.target) {
+                if !self.failed {
+                    warn!("cannot send OSC messages to {}: {}", self.target, e);
+                    self.failed = true;
+                }
+                return;
+            }
+        }
+    }
+}
+
+/// Encode an OSC message with integer arguments.
+fn message(address: &str, args: &[i32]) -> Vec<u8> {
+    let mut packet = Vec::new();
+    push_string(&mut packet, address);
+    let tags: String = std::iter::once(',')
+        .chain(args.iter().map(|_| 'i'))
+        .collect();
+    push_string(&mut packet, &tags);
+    for arg in args {
+        packet.extend_from_slice(&arg.to_be_bytes());
+    }
+    packet
+}
+
+/// Append a string terminated by at least one zero byte and padded to a multiple of four bytes.
+fn push_string(packet: &mut Vec<u8>, text: &str) {
+    packet.extend_from_slice(text.as_bytes());
+    let padding = 4 - text.len() % 4;
+    packet.extend(std::iter::repeat_n(0, padding));
+}
@@ -0,0 +1,102 @@
+use ggez::graphics::{Canvas, Color, DrawMode, DrawParam, Mesh, MeshBuilder, Text};
+use ggez::{Context, GameResult};
+use std::collections::HashSet;
+
+use crate::locale::{tr, Message};
+use crate::pattern::Pattern;
+use crate::render::CellSize;
+
+// Blueprint cells that are alive on the grid, and those that are missing.
+const MATCH_COLOR: Color = Color::new(0.2, 0.9, 0.3, 0.45);
+const MISSING_COLOR: Color = Color::new(1.0, 0.2, 0.2, 0.45);
+// Outline of live cells that are not part of the blueprint.
+const EXTRA_COLOR: Color = Color::new(1.0, 0.6, 0.1, 0.9);
+const PADDING: f32 = 8.0;
+
+/// A reference pattern shown translucently over the grid, never simulated, to
+/// compare the live cells with a blueprint.
+pub struct Overlay {
+    name: Option<String>,
+    /// Cells of the blueprint, in grid coordinates.
+    cells: HashSet<(usize, usize)>,
+    pub visible: bool,
+}
+
+impl Overlay {
+    /// Lay a pattern over the grid with its top left corner at `(left, top)`.
+    pub fn new(pattern: &Pattern, left: usize, top: usize) -> Overlay {
+        Overlay {
+            name: pattern.name.clone(),
+            cells: pattern
+                .cells
+                .iter()
+                .map(|&(x, y)| (left + x, top + y))
+                .collect(),
+            visible: true,
+        }
+    }
+
+    /// Color the blueprint cells by whether they are alive on the grid and
+    /// outline the live cells the blueprint does not have.
+    pub fn draw(
+        &self,
+        ctx: &mut Context,
+        canvas: &mut Canvas,
+        cell: CellSize,
+        grid: &[Vec<bool>],
+    ) -> GameResult {
+        let mut builder = MeshBuilder::new();
+        let mut empty = true;
+        for &(x, y) in &self.cells {
+            let Some(&alive) = grid.get(y).and_then(|row| row.get(x)) else {
+                continue;
+            };
+            let color = if alive { MATCH_COLOR } else { MISSING_COLOR };
+            builder.rectangle(
+                DrawMode::fill(),
+                cell.rect(x as f32, y as f32, 1.0, 1.0),
+                color,
+            )?;
+            empty = false;
+        }
+        for (y, row) in grid.iter().enumerate() {
+            for (x, _) in row.iter().enumerate().filter(|(_, &alive)| alive) {
+                if !self.cells.contains(&(x, y)) {
+                    builder.rectangle(
+                        DrawMode::stroke(2.0),
+                        cell.rect(x as f32, y as f32, 1.0, 1.0),
+                        EXTRA_COLOR,
+                    )?;
+                    empty = false;
+                }
+            }
+        }
+        // A mesh without any vertices cannot be built
+        if !empty {
+            canvas.draw(&Mesh::from_data(ctx, builder.build()), DrawParam::default());
+        }
+        Ok(())
+    }
+
+    /// Count the missing and the extra cells in the top left corner of the window.
+    pub fn draw_summary(&self, canvas: &mut Canvas, grid: &[Vec<bool>]) {
+        let alive =
+            |&(x, y): &(usize, usize)| grid.get(y).and_then(|row| row.get(x)) == Some(&true);
+        let matching = self.cells.iter().filter(|cell| alive(cell)).count();
+        let missing = self.cells.len() - matching;
+        let population: usize = grid
+            .iter()
+            .map(|row| row.iter().filter(|&&alive| alive).count())
+            .sum();
+        let text = Text::new(format!(
+            "{}: {}\n{}: {}  {}: {}",
+            tr(Message::Overlay),
+            self.name.as_deref().unwrap_or(tr(Message::Unnamed)),
+            tr(Message::OverlayMissing),
+            missing,
+            tr(Message::OverlayExtra),
+            population - matching
+        ));
+        canvas.draw(&text, DrawParam::default().dest([PADDING, PADDING]));
+    }
+}
@@ -0,0 +1,219 @@
+use ggegui::{egui, Gui};
+use ggez::graphics::{Canvas, DrawParam};
+use ggez::Context;
+use std::time::Duration;
+
+use crate::game::{MAX_GRID_SIZE, MIN_GRID_SIZE};
+use crate::locale::{tr, Message};
+use crate::multistate::AUTOMATA;
+use crate::rulebook;
+use crate::theme::{Theme, THEMES};
+use crate::tick;
+
+// Longest delay the speed slider goes to, in milliseconds.
+const MAX_DELAY_MS: f64 = 2000.0;
+
+/// Something asked for in the panel, for the game to carry out.
+pub enum Command {
+    TogglePause,
+    Step,
+    SetDelay(Duration),
+    /// Switch to a built-in rule, by its index in the rulebook.
+    Rule(usize),
+    /// Switch to a multi-state automaton, by its index in `AUTOMATA`.
+    Automaton(usize),
+    Resize(usize, usize),
+    Theme(Theme),
+    Clear,
+    Randomize,
+    Quicksave,
+    Quickload,
+    SaveWorkspace,
+    OpenWorkspace,
+}
+
+/// The settings the panel shows.
+pub struct Status {
+    pub paused: bool,
+    pub delay: Duration,
+    /// Name of the rule or automaton running.
+    pub rule: String,
+    pub width: usize,
+    pub height: usize,
+    pub theme: Theme,
+}
+
+/// A window of buttons and sliders for what the keyboard shortcuts do, for
+/// those who have not learned them.
+pub struct Panel {
+    gui: Gui,
+    pub visible: bool,
+    /// Size typed in but not applied yet.
+    size: Option<(usize, usize)>,
+    /// Whether the pointer and the keyboard were the panel's in the last frame.
+    wants_pointer: bool,
+    wants_keyboard: bool,
+}
+
+impl Panel {
+    pub fn new(ctx: &Context) -> Panel {
+        Panel {
+            gui: Gui::new(ctx),
+            visible: false,
+            size: None,
+            wants_pointer: false,
+            wants_keyboard: false,
+        }
+    }
+
+    /// Whether clicks and the mouse wheel are on the panel rather than the grid.
+    pub fn wants_pointer(&self) -> bool {
+        self.visible && self.wants_pointer
+    }
+
+    /// Whether keys go to a field of the panel rather than to the shortcuts.
+    pub fn wants_keyboard(&self) -> bool {
+        self.visible && self.wants_keyboard
+    }
+
+    pub fn resize(&mut self, width: f32, height: f32) {
+        self.gui.input.resize_event(width, height);
+    }
+
+    pub fn type_char(&mut self, character: char) {
+        if self.visible {
+            self.gui.input.text_input_event(character);
+        }
+    }
+
+    pub fn scroll(&mut self, x: f32, y: f32) {
+        if self.visible {
+            self.gui.input.mouse_wheel_event(x, y);
+        }
+    }
+
+    /// Lay the panel out for this frame, returning what was asked for in it.
+    pub fn update(&mut self, ctx: &mut Context, status: &Status) -> Vec<Command> {
+        let mut commands = Vec::new();
+        let gui_ctx = self.gui.ctx();
+        let size = &mut self.size;
+        egui::Window::new(tr(Message::PanelTitle))
+            .resizable(false)
+            .default_pos([12.0, 48.0])
+            .show(&gui_ctx, |ui| {
+                ui.horizontal(|ui| {
+                    let play = if status.paused {
+                        Message::PanelPlay
+                    } else {
+                        Message::PanelPause
+                    };
+                    if ui.button(tr(play)).clicked() {
+                        commands.push(Command::TogglePause);
+                    }
+                    if ui
+                        .add_enabled(status.paused, egui::Button::new(tr(Message::PanelStep)))
+                        .clicked()
+                    {
+                        commands.push(Command::Step);
+                    }
+                });
+
+                let mut delay_ms = status.delay.as_secs_f64() * 1000.0;
+                let min_ms = tick::MIN_DELAY.as_secs_f64() * 1000.0;
+                let slider = egui::Slider::new(&mut delay_ms, min_ms..=MAX_DELAY_MS)
+                    .logarithmic(true)
+                    .suffix(" ms")
+                    .text(tr(Message::PanelDelay));
+                if ui.add(slider).changed() {
+                    commands.push(Command::SetDelay(tick::delay_from_ms(delay_ms)));
+                }
+
+                egui::ComboBox::from_label(tr(Message::PanelRule))
+                    .selected_text(&status.rule)
+                    .show_ui(ui, |ui| {
+                        for (i, entry) in rulebook::RULES.iter().enumerate() {
+                            if ui
+                                .selectable_label(status.rule == entry.name, entry.name)
+                                .clicked()
+                            {
+                                commands.push(Command::Rule(i));
+                            }
+                        }
+                        for (i, automaton) in AUTOMATA.iter().enumerate() {
+                            if ui
+                                .selectable_label(status.rule == automaton.name(), automaton.name())
+                                .clicked()
+                            {
+                                commands.push(Command::Automaton(i));
+                            }
+                        }
+                    });
+
+                ui.horizontal(|ui| {
+                    // Follow the size when it changes by other means, unless it is being typed in
+                    if !ui.ctx().is_pointer_over_area() && !ui.ctx().wants_keyboard_input() {
+                        *size = None;
+                    }
+                    let (width, height) = size.get_or_insert((status.width, status.height));
+                    let range = MIN_GRID_SIZE..=MAX_GRID_SIZE;
+                    ui.add(egui::DragValue::new(width).clamp_range(range.clone()));
+                    ui.label("×");
+                    ui.add(egui::DragValue::new(height).clamp_range(range));
+                    let changed = (*width, *height) != (status.width, status.height);
+                    if ui
+                        .add_enabled(changed, egui::Button::new(tr(Message::PanelResize)))
+                        .clicked()
+                    {
+                        commands.push(Command::Resize(*width, *height));
+                    }
+                });
+
+                egui::ComboBox::from_label(tr(Message::PanelTheme))
+                    .selected_text(status.theme.name())
+                    .show_ui(ui, |ui| {
+                        for theme in THEMES {
+                            if ui
+                                .selectable_label(status.theme == theme, theme.name())
+                                .clicked()
+                            {
+                                commands.push(Command::Theme(theme));
+                            }
+                        }
+                    });
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.button(tr(Message::PanelClear)).clicked() {
+                        commands.push(Command::Clear);
+                    }
+                    if ui.button(tr(Message::PanelRandomize)).clicked() {
+                        commands.push(Command::Randomize);
+                    }
+                });
+                ui.horizontal(|ui| {
+                    if ui.button(tr(Message::PanelSave)).clicked() {
+                        commands.push(Command::Quicksave);
+                    }
+                    if ui.button(tr(Message::PanelLoad)).clicked() {
+                        commands.push(Command::Quickload);
+                    }
+                    if ui.button(tr(Message::PanelSaveWorkspace)).clicked() {
+                        commands.push(Command::SaveWorkspace);
+                    }
+                    if ui.button(tr(Message::PanelOpenWorkspace)).clicked() {
+                        commands.push(Command::OpenWorkspace);
+                    }
+                });
+            });
+        self.wants_pointer = gui_ctx.is_pointer_over_area() || gui_ctx.wants_pointer_input();
+        self.wants_keyboard = gui_ctx.wants_keyboard_input();
+        // Finishes the frame for drawing
+        drop(gui_ctx);
+        self.gui.update(ctx);
+        commands
+    }
+
+    pub fn draw(&self, canvas: &mut Canvas) {
+        canvas.draw(&self.gui, DrawParam::default());
+    }
+}
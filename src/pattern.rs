@@ -0,0 +1,512 @@
+use crate::game::MAX_GRID_SIZE;
+use crate::rule::Rule;
+
+// Longest line written to RLE files, as recommended by the format description.
+const RLE_LINE_LENGTH: usize = 70;
+
+/// A pattern read from a file, independent of where it will be placed.
+#[derive(Clone, Default)]
+pub struct Pattern {
+    /// Name from the `#N` line of an RLE file or the `!Name:` line of a plaintext file.
+    pub name: Option<String>,
+    /// Rule from the RLE header, in whatever notation the file uses.
+    pub rule: Option<String>,
+    pub width: usize,
+    pub height: usize,
+    /// Live cells `(x, y)` relative to the top left corner of the bounding box.
+    pub cells: Vec<(usize, usize)>,
+}
+
+impl Pattern {
+    /// Parse an RLE, Life 1.05, Life 1.06 or plaintext (`.cells`) pattern.
+    pub fn parse(text: &str) -> Result<Pattern, String> {
+        let first = text.lines().map(str::trim).find(|line| !line.is_empty());
+        match first {
+            Some(line) if line.starts_with("#Life 1.05") => return Pattern::parse_life105(text),
+            Some(line) if line.starts_with("#Life 1.06") => return Pattern::parse_life106(text),
+            _ => (),
+        }
+        let is_rle = text.lines().any(|line| {
+            let line = line.trim_start();
+            line.starts_with('x') && line[1..].trim_start().starts_with('=')
+        });
+        if is_rle {
+            Pattern::parse_rle(text)
+        } else {
+            Pattern::parse_plaintext(text)
+        }
+    }
+
+    /// Parse the run length encoded format used by Golly and LifeWiki, refusing
+    /// patterns larger than the largest universe and cells outside the size
+    /// given in the header.
+    pub fn parse_rle(text: &str) -> Result<Pattern, String> {
+        let mut pattern = Pattern::default();
+        let mut header_seen = false;
+        let (mut x, mut y) = (0, 0);
+        let mut run: Option<usize> = None;
+
+        'lines: for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if let Some(comment) = line.strip_prefix('#') {
+                match comment.chars().next() {
+                    Some('N') => pattern.name = Some(comment[1..].trim().to_string()),
+                    Some('r') => pattern.rule = Some(comment[1..].trim().to_string()),
+                    // Comments, authors and offsets carry nothing we need
+                    _ => (),
+                }
+                continue;
+            }
+            if !header_seen {
+                if line.is_empty() {
+                    continue;
+                }
+                pattern.parse_rle_header(line)?;
+                check_size(pattern.width as u64, pattern.height as u64)?;
+                header_seen = true;
+                continue;
+            }
+
+            let outside = || format!("line {}: cells outside the size in the header", number + 1);
+            for c in line.chars() {
+                match c {
+                    '0'..='9' => {
+                        let digit = c.to_digit(10).unwrap() as usize;
+                        run = run
+                            .unwrap_or(0)
+                            .checked_mul(10)
+                            .and_then(|run| run.checked_add(digit))
+                            .filter(|&run| run <= MAX_GRID_SIZE)
+                            .map(Some)
+                            .ok_or_else(|| format!("line {}: run too long", number + 1))?;
+                    }
+                    'b' | '.' => {
+                        x += run.take().unwrap_or(1);
+                        if x > pattern.width {
+                            return Err(outside());
+                        }
+                    }
+                    '$' => {
+                        y += run.take().unwrap_or(1);
+                        x = 0;
+                        if y > pattern.height {
+                            return Err(outside());
+                        }
+                    }
+                    '!' => break 'lines,
+                    // Every other state of a multi-state file counts as alive
+                    'o' | 'A'..='Z' => {
+                        let length = run.take().unwrap_or(1);
+                        if x + length > pattern.width || y >= pattern.height {
+                            return Err(outside());
+                        }
+                        pattern.cells.extend((x..x + length).map(|x| (x, y)));
+                        x += length;
+                    }
+                    c if c.is_whitespace() => (),
+                    _ => return Err(format!("line {}: unexpected character `{}`", number + 1, c)),
+                }
+            }
+        }
+
+        if !header_seen {
+            return Err("missing `x = ..., y = ...` header".to_string());
+        }
+        Ok(pattern)
+    }
+
+    /// Parse the plaintext format: `.` for dead cells, `O` for live ones, `!` for comments.
+    pub fn parse_plaintext(text: &str) -> Result<Pattern, String> {
+        let mut pattern = Pattern::default();
+        let mut y = 0;
+        for (number, line) in text.lines().enumerate() {
+            if let Some(comment) = line.strip_prefix('!') {
+                if let Some(name) = comment.strip_prefix("Name:") {
+                    pattern.name = Some(name.trim().to_string());
+                }
+                continue;
+            }
+            for (x, c) in line.trim_end().chars().enumerate() {
+                match c {
+                    'O' | 'o' | '*' => pattern.cells.push((x, y)),
+                    '.' => (),
+                    _ => return Err(format!("line {}: unexpected character `{}`", number + 1, c)),
+                }
+            }
+            y += 1;
+        }
+        pattern.fit_bounds();
+        check_size(pattern.width as u64, pattern.height as u64)?;
+        Ok(pattern)
+    }
+
+    /// Parse Life 1.05: blocks of `.` and `*` rows, each placed by a `#P x y`
+    /// line, with the rule in S/B notation on an `#R` line or Conway's on `#N`.
+    pub fn parse_life105(text: &str) -> Result<Pattern, String> {
+        let mut pattern = Pattern::default();
+        let mut cells = Vec::new();
+        let (mut left, mut y) = (0i64, 0i64);
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if let Some(rest) = line.strip_prefix("#P") {
+                let mut numbers = rest.split_whitespace().map(str::parse::<i64>);
+                match (numbers.next(), numbers.next()) {
+                    (Some(Ok(x)), Some(Ok(top))) => (left, y) = (x, top),
+                    _ => return Err(format!("line {}: malformed block position", number + 1)),
+                }
+            } else if line == "#N" {
+                pattern.rule = Some("B3/S23".to_string());
+            } else if let Some(rule) = line.strip_prefix("#R") {
+                pattern.rule = Some(rule.trim().to_string());
+            } else if line.starts_with('#') {
+                // The header and descriptions carry nothing we need
+            } else {
+                for (x, c) in line.chars().enumerate() {
+                    match c {
+                        '*' | 'O' | 'o' => cells.push((
+                            left.checked_add(x as i64).ok_or_else(|| too_far(number))?,
+                            y,
+                        )),
+                        '.' => (),
+                        _ => {
+                            return Err(format!(
+                                "line {}: unexpected character `{}`",
+                                number + 1,
+                                c
+                            ))
+                        }
+                    }
+                }
+                y = y.checked_add(1).ok_or_else(|| too_far(number))?;
+            }
+        }
+        pattern.place_coordinates(&cells)?;
+        Ok(pattern)
+    }
+
+    /// Parse Life 1.06: an `x y` line for every live cell, relative to any origin.
+    pub fn parse_life106(text: &str) -> Result<Pattern, String> {
+        let mut cells = Vec::new();
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut numbers = line.split_whitespace().map(str::parse::<i64>);
+            match (numbers.next(), numbers.next(), numbers.next()) {
+                (Some(Ok(x)), Some(Ok(y)), None) => cells.push((x, y)),
+                _ => {
+                    return Err(format!(
+                        "line {}: expected `x y`, not `{}`",
+                        number + 1,
+                        line
+                    ))
+                }
+            }
+        }
+        let mut pattern = Pattern::default();
+        pattern.place_coordinates(&cells)?;
+        Ok(pattern)
+    }
+
+    /// The pattern turned a quarter turn clockwise.
+    pub fn rotated(&self) -> Pattern {
+        Pattern {
+            name: self.name.clone(),
+            rule: self.rule.clone(),
+            width: self.height,
+            height: self.width,
+            cells: self
+                .cells
+                .iter()
+                .map(|&(x, y)| (self.height - 1 - y, x))
+                .collect(),
+        }
+    }
+
+    /// The pattern mirrored from left to right.
+    pub fn flipped(&self) -> Pattern {
+        Pattern {
+            name: self.name.clone(),
+            rule: self.rule.clone(),
+            width: self.width,
+            height: self.height,
+            cells: self
+                .cells
+                .iter()
+                .map(|&(x, y)| (self.width - 1 - x, y))
+                .collect(),
+        }
+    }
+
+    /// Encode the pattern in the RLE format, with a header naming the rule.
+    pub fn to_rle(&self, rule: &str) -> String {
+        // Runs of dead cells at the end of a row are implied by the `$`, and
+        // the rows are worked through from the live cells alone, so that the
+        // size of the bounding box costs nothing
+        let mut tokens = Vec::new();
+        let mut next_row = 0;
+        for (y, xs) in self.rows() {
+            // One `$` ends the previous row, every skipped empty row needs another
+            let row_ends = y - next_row + usize::from(!tokens.is_empty());
+            if row_ends > 0 {
+                tokens.push(run(row_ends, '$'));
+            }
+            next_row = y + 1;
+            let mut x = 0;
+            for alive in xs.chunk_by(|a, b| a + 1 == *b) {
+                if alive[0] > x {
+                    tokens.push(run(alive[0] - x, 'b'));
+                }
+                tokens.push(run(alive.len(), 'o'));
+                x = alive[0] + alive.len();
+            }
+        }
+        tokens.push("!".to_string());
+
+        let mut rle = String::new();
+        if let Some(name) = &self.name {
+            rle.push_str(&format!("#N {}\n", name));
+        }
+        rle.push_str(&format!(
+            "x = {}, y = {}, rule = {}\n",
+            self.width, self.height, rule
+        ));
+        let mut line_length = 0;
+        for token in tokens {
+            if line_length + token.len() > RLE_LINE_LENGTH {
+                rle.push('\n');
+                line_length = 0;
+            }
+            rle.push_str(&token);
+            line_length += token.len();
+        }
+        rle.push('\n');
+        rle
+    }
+
+    /// Encode the pattern in Life 1.06, with the origin in the middle of it.
+    pub fn to_life106(&self) -> String {
+        let (dx, dy) = ((self.width / 2) as i64, (self.height / 2) as i64);
+        let mut text = String::from("#Life 1.06\n");
+        for &(x, y) in &self.cells {
+            text.push_str(&format!("{} {}\n", x as i64 - dx, y as i64 - dy));
+        }
+        text
+    }
+
+    /// Encode the pattern in Life 1.05 as a single block around the origin,
+    /// with `#N` for Conway's rule and an `#R` line for any other.
+    pub fn to_life105(&self, rule: Rule) -> String {
+        let mut text = String::from("#Life 1.05\n");
+        if let Some(name) = &self.name {
+            text.push_str(&format!("#D {}\n", name));
+        }
+        if rule == Rule::LIFE {
+            text.push_str("#N\n");
+        } else {
+            text.push_str(&format!("#R {}\n", rule.survival_birth_notation()));
+        }
+        text.push_str(&format!(
+            "#P {} {}\n",
+            -((self.width / 2) as i64),
+            -((self.height / 2) as i64)
+        ));
+        let mut rows = self.rows().into_iter().peekable();
+        for y in 0..self.height {
+            // Dead cells at the end of a row go without saying, but a row needs a cell
+            let Some((_, xs)) = rows.next_if(|&(row, _)| row == y) else {
+                text.push_str(".\n");
+                continue;
+            };
+            let mut x = 0;
+            for alive in xs {
+                text.extend(std::iter::repeat_n('.', alive - x));
+                text.push('*');
+                x = alive + 1;
+            }
+            text.push('\n');
+        }
+        text
+    }
+
+    /// Take cells at any coordinates, moving them to the top left corner, as
+    /// long as they fit in the largest universe.
+    fn place_coordinates(&mut self, cells: &[(i64, i64)]) -> Result<(), String> {
+        let left = cells.iter().map(|&(x, _)| x).min().unwrap_or(0);
+        let top = cells.iter().map(|&(_, y)| y).min().unwrap_or(0);
+        let right = cells.iter().map(|&(x, _)| x).max().unwrap_or(0);
+        let bottom = cells.iter().map(|&(_, y)| y).max().unwrap_or(0);
+        // The span of two cells far apart may not even fit in an i64
+        let span = |low: i64, high: i64| high.abs_diff(low).saturating_add(1);
+        check_size(span(left, right), span(top, bottom))?;
+        self.cells = cells
+            .iter()
+            .map(|&(x, y)| ((x - left) as usize, (y - top) as usize))
+            .collect();
+        self.cells.sort_unstable_by_key(|&(x, y)| (y, x));
+        self.cells.dedup();
+        self.fit_bounds();
+        Ok(())
+    }
+
+    /// The live cells row by row from the top, each row's from the left.
+    fn rows(&self) -> Vec<(usize, Vec<usize>)> {
+        let mut cells = self.cells.clone();
+        cells.sort_unstable_by_key(|&(x, y)| (y, x));
+        cells.dedup();
+        cells
+            .chunk_by(|a, b| a.1 == b.1)
+            .map(|row| (row[0].1, row.iter().map(|&(x, _)| x).collect()))
+            .collect()
+    }
+
+    /// Read `x = 3, y = 3, rule = B3/S23`; the rule is optional.
+    fn parse_rle_header(&mut self, line: &str) -> Result<(), String> {
+        for field in line.split(',') {
+            let (key, value) = field
+                .split_once('=')
+                .ok_or_else(|| format!("malformed header field `{}`", field.trim()))?;
+            let value = value.trim();
+            let number = || {
+                value
+                    .parse::<usize>()
+                    .map_err(|_| format!("invalid size `{}` in header", value))
+            };
+            match key.trim() {
+                "x" => self.width = number()?,
+                "y" => self.height = number()?,
+                "rule" => self.rule = Some(value.to_string()),
+                _ => (),
+            }
+        }
+        Ok(())
+    }
+
+    /// Grow the declared size if the cells do not fit into it.
+    fn fit_bounds(&mut self) {
+        for &(x, y) in &self.cells {
+            self.width = self.width.max(x + 1);
+            self.height = self.height.max(y + 1);
+        }
+    }
+}
+
+fn too_far(number: usize) -> String {
+    format!("line {}: cells too far from the origin", number + 1)
+}
+
+/// Refuse patterns that would not fit in the largest universe, before making
+/// room for their cells.
+fn check_size(width: u64, height: u64) -> Result<(), String> {
+    let max = MAX_GRID_SIZE as u64;
+    if width > max || height > max {
+        return Err(format!(
+            "a pattern of {}x{} cells is larger than the largest universe, {}x{}",
+            width, height, max, max
+        ));
+    }
+    Ok(())
+}
+
+/// A run of `length` copies of `tag`, leaving out a count of one.
+fn run(length: usize, tag: char) -> String {
+    if length == 1 {
+        tag.to_string()
+    } else {
+        format!("{}{}", length, tag)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaCha8Rng;
+
+    use super::Pattern;
+    use crate::rule::Rule;
+
+    /// A random pattern whose bounding box is its size, as the coordinate
+    /// formats give back.
+    fn random_pattern(rng: &mut ChaCha8Rng) -> Pattern {
+        let (width, height) = (rng.gen_range(1..120), rng.gen_range(1..60));
+        // Cells on every edge, so that the bounding box is the whole size
+        let edges = [
+            (0, rng.gen_range(0..height)),
+            (rng.gen_range(0..width), 0),
+            (width - 1, height - 1),
+        ];
+        let mut cells: Vec<(usize, usize)> = (0..rng.gen_range(0..200))
+            .map(|_| (rng.gen_range(0..width), rng.gen_range(0..height)))
+            .chain(edges)
+            .collect();
+        cells.sort_unstable();
+        cells.dedup();
+        Pattern {
+            width,
+            height,
+            cells,
+            ..Pattern::default()
+        }
+    }
+
+    fn assert_same(decoded: Pattern, expected: &Pattern) {
+        let mut cells = decoded.cells;
+        cells.sort_unstable();
+        assert_eq!(cells, expected.cells);
+        assert_eq!(
+            (decoded.width, decoded.height),
+            (expected.width, expected.height)
+        );
+    }
+
+    #[test]
+    fn rle_round_trips() {
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+        for _ in 0..200 {
+            let pattern = random_pattern(&mut rng);
+            let decoded = Pattern::parse(&pattern.to_rle("B3/S23")).unwrap();
+            assert_eq!(decoded.rule.as_deref(), Some("B3/S23"));
+            assert_same(decoded, &pattern);
+        }
+    }
+
+    #[test]
+    fn life_105_and_106_round_trip() {
+        let mut rng = ChaCha8Rng::seed_from_u64(2);
+        for _ in 0..200 {
+            let pattern = random_pattern(&mut rng);
+            assert_same(Pattern::parse(&pattern.to_life106()).unwrap(), &pattern);
+            assert_same(
+                Pattern::parse(&pattern.to_life105(Rule::LIFE)).unwrap(),
+                &pattern,
+            );
+        }
+    }
+
+    #[test]
+    fn rle_refuses_runs_past_the_header_or_the_largest_universe() {
+        for text in [
+            "x = 3, y = 3\n99999999999999999999999o!",
+            "x = 3, y = 3\n18446744073709551617b!",
+            "x = 2, y = 1\n3o!",
+            "x = 2, y = 2\n2$o!",
+            "x = 5000, y = 1\no!",
+            "x = 1000, y = 1000\n1001o!",
+        ] {
+            assert!(Pattern::parse(text).is_err(), "{}", text);
+        }
+    }
+
+    #[test]
+    fn coordinate_formats_refuse_patterns_larger_than_the_largest_universe() {
+        for text in [
+            "#Life 1.06\n0 0\n1000 0\n",
+            "#Life 1.06\n-9223372036854775808 0\n9223372036854775807 0\n",
+            "#Life 1.05\n#P 9223372036854775807 0\n**\n",
+            "#Life 1.05\n#P 0 0\n*\n#P 0 5000\n*\n",
+        ] {
+            assert!(Pattern::parse(text).is_err(), "{}", text);
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use ggez::graphics::{Canvas, Color, DrawMode, DrawParam, Mesh, Rect, Text};
+use ggez::{Context, GameResult};
+use std::fmt::Write;
+use std::mem;
+use std::time::{Duration, Instant};
+
+use crate::locale::{tr, Message};
+
+// Weight of the newest sample in the moving averages.
+const SMOOTHING: f64 = 0.1;
+// Spans that have not been recorded for this long are hidden, e.g. after switching views.
+const STALE_AFTER: Duration = Duration::from_secs(1);
+// Distance of the overlay from the window corner and padding around its text.
+const MARGIN: f32 = 8.0;
+const PADDING: f32 = 6.0;
+
+/// Moving average of the time spent in one part of the game loop.
+struct Span {
+    /// Slash separated path, e.g. `update/simulation` is part of `update`.
+    name: &'static str,
+    average: f64,
+    last_recorded: Instant,
+}
+
+/// Overlay with the frame rate and a per-system breakdown of where each frame goes.
+pub struct PerfOverlay {
+    pub visible: bool,
+    // Kept sorted by name so that every span directly follows its parent
+    spans: Vec<Span>,
+    // Approximate bytes used by each large buffer
+    memory: Vec<(&'static str, usize)>,
+}
+
+impl PerfOverlay {
+    /// Create a hidden overlay.
+    pub fn new() -> PerfOverlay {
+        PerfOverlay {
+            visible: false,
+            spans: Vec::new(),
+            memory: Vec::new(),
+        }
+    }
+
+    /// Record how long a system took this frame.
+    pub fn record(&mut self, name: &'static str, elapsed: Duration) {
+        let index = match self.spans.binary_search_by(|span| span.name.cmp(name)) {
+            Ok(index) => index,
+            Err(index) => {
+                self.spans.insert(
+                    index,
+                    Span {
+                        name,
+                        average: elapsed.as_secs_f64(),
+                        last_recorded: Instant::now(),
+                    },
+                );
+                return;
+            }
+        };
+        let span = &mut self.spans[index];
+        span.average += (elapsed.as_secs_f64() - span.average) * SMOOTHING;
+        span.last_recorded = Instant::now();
+    }
+
+    /// Replace the memory report with the current size of each buffer.
+    pub fn set_memory(&mut self, memory: Vec<(&'static str, usize)>) {
+        self.memory = memory;
+    }
+
+    /// Draw the overlay in the top right corner.
+    pub fn draw(&self, ctx: &mut Context, canvas: &mut Canvas) -> GameResult {
+        if !self.visible {
+            return Ok(());
+        }
+        let mut report = format!(
+            "{}: {:.0}\n{}: {:.2} ms",
+            tr(Message::Fps),
+            ctx.time.fps(),
+            tr(Message::FrameTime),
+            ctx.time.average_delta().as_secs_f64() * 1000.0,
+        );
+        for span in self
+            .spans
+            .iter()
+            .filter(|span| span.last_recorded.elapsed() < STALE_AFTER)
+        {
+            // Indent nested systems below their parent
+            let depth = span.name.matches('/').count();
+            let label = span.name.rsplit('/').next().unwrap_or(span.name);
+            let _ = write!(
+                report,
+                "\n{:indent$}{}: {:.2} ms",
+                "",
+                label,
+                span.average * 1000.0,
+                indent = 2 * depth
+            );
+        }
+
+        if !self.memory.is_empty() {
+            let total = self.memory.iter().map(|&(_, bytes)| bytes).sum();
+            let _ = write!(report, "\n{}: {}", tr(Message::Memory), format_bytes(total));
+            for &(label, bytes) in &self.memory {
+                let _ = write!(report, "\n  {}: {}", label, format_bytes(bytes));
+            }
+        }
+
+        let text = Text::new(report);
+        let size = text.measure(ctx)?;
+        let (window_width, _) = ctx.gfx.drawable_size();
+        let left = window_width - MARGIN - size.x - 2.0 * PADDING;
+        let panel = Rect::new(left, MARGIN, size.x + 2.0 * PADDING, size.y + 2.0 * PADDING);
+
+        let backdrop =
+            Mesh::new_rectangle(ctx, DrawMode::fill(), panel, Color::new(0.0, 0.0, 0.0, 0.7))?;
+        canvas.draw(&backdrop, DrawParam::default());
+        canvas.draw(
+            &text,
+            DrawParam::default()
+                .dest([left + PADDING, MARGIN + PADDING])
+                .color(Color::GREEN),
+        );
+        Ok(())
+    }
+}
+
+/// Approximate heap size of a grid stored row by row.
+pub fn grid_bytes(grid: &[Vec<bool>]) -> usize {
+    mem::size_of_val(grid) + grid.iter().map(Vec::capacity).sum::<usize>()
+}
+
+fn format_bytes(bytes: usize) -> String {
+    const KIB: f64 = 1024.0;
+    let bytes = bytes as f64;
+    if bytes < KIB {
+        format!("{} B", bytes)
+    } else if bytes < KIB * KIB {
+        format!("{:.1} KiB", bytes / KIB)
+    } else {
+        format!("{:.1} MiB", bytes / (KIB * KIB))
+    }
+}
@@ -0,0 +1,49 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::VecDeque;
+use std::hash::{Hash, Hasher};
+
+use crate::bitgrid::BitGrid;
+
+/// Longest period that is recognized.
+pub const MAX_PERIOD: usize = 64;
+
+/// Recognizes when the universe starts repeating itself.
+pub struct PeriodDetector {
+    // Hashes of the most recent generations, newest first
+    recent: VecDeque<u64>,
+}
+
+impl PeriodDetector {
+    /// Create a detector that has not seen any generation yet.
+    pub fn new() -> PeriodDetector {
+        PeriodDetector {
+            recent: VecDeque::with_capacity(MAX_PERIOD),
+        }
+    }
+
+    /// Record a generation and return its period if it repeats one of the recent ones.
+    ///
+    /// A still life reports a period of 1.
+    pub fn observe(&mut self, grid: &BitGrid) -> Option<usize> {
+        let mut hasher = DefaultHasher::new();
+        grid.hash(&mut hasher);
+        let hash = hasher.finish();
+
+        let period = self.recent.iter().position(|&h| h == hash).map(|i| i + 1);
+        if self.recent.len() == MAX_PERIOD {
+            self.recent.pop_back();
+        }
+        self.recent.push_front(hash);
+        period
+    }
+
+    /// Approximate number of bytes held by the recorded hashes.
+    pub fn memory_usage(&self) -> usize {
+        self.recent.capacity() * std::mem::size_of::<u64>()
+    }
+
+    /// Forget all recorded generations, e.g. after the grid was replaced.
+    pub fn reset(&mut self) {
+        self.recent.clear();
+    }
+}
@@ -0,0 +1,226 @@
+use ggez::graphics::{Canvas, Color, DrawMode, DrawParam, Mesh, Rect, Text, TextFragment};
+use ggez::{Context, GameResult};
+use serde::{Deserialize, Serialize};
+
+use crate::locale::{tr, Message};
+use crate::pattern::Pattern;
+use crate::render::CellSize;
+use crate::universe::Universe;
+
+const PADDING: f32 = 8.0;
+const SELECTED_COLOR: Color = Color::new(1.0, 0.8, 0.2, 1.0);
+const ENTRY_COLOR: Color = Color::new(0.8, 0.8, 0.8, 1.0);
+
+/// A pattern placed on the grid, remembered so it can be edited as a whole.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Placement {
+    name: Option<String>,
+    /// Live cells as loaded, relative to the top left corner.
+    cells: Vec<(usize, usize)>,
+    left: usize,
+    top: usize,
+    /// Quarter turns clockwise, applied after mirroring.
+    quarter_turns: u8,
+    /// Whether the pattern is mirrored left to right.
+    mirrored: bool,
+}
+
+impl Placement {
+    /// The cells in the current orientation, relative to the top left corner.
+    fn oriented(&self) -> Vec<(usize, usize)> {
+        let cells: Vec<(i64, i64)> = self
+            .cells
+            .iter()
+            .map(|&(x, y)| {
+                let (mut x, mut y) = (x as i64, y as i64);
+                if self.mirrored {
+                    x = -x;
+                }
+                for _ in 0..self.quarter_turns {
+                    (x, y) = (-y, x);
+                }
+                (x, y)
+            })
+            .collect();
+        let left = cells.iter().map(|&(x, _)| x).min().unwrap_or(0);
+        let top = cells.iter().map(|&(_, y)| y).min().unwrap_or(0);
+        cells
+            .into_iter()
+            .map(|(x, y)| ((x - left) as usize, (y - top) as usize))
+            .collect()
+    }
+
+    /// Width and height of the bounding box in the current orientation.
+    fn size(&self) -> (usize, usize) {
+        let cells = self.oriented();
+        let width = cells.iter().map(|&(x, _)| x + 1).max().unwrap_or(0);
+        let height = cells.iter().map(|&(_, y)| y + 1).max().unwrap_or(0);
+        (width, height)
+    }
+
+    fn set_cells(&self, universe: &mut Universe, alive: bool) {
+        for (x, y) in self.oriented() {
+            universe.set(self.left + x, self.top + y, alive);
+        }
+    }
+}
+
+/// Every pattern placed this session, with one of them selected for editing.
+pub struct Placements {
+    entries: Vec<Placement>,
+    selected: usize,
+    pub visible: bool,
+}
+
+impl Placements {
+    pub fn new() -> Placements {
+        Placements {
+            entries: Vec::new(),
+            selected: 0,
+            visible: false,
+        }
+    }
+
+    /// Remember a pattern placed with its top left corner at `(left, top)` and select it.
+    pub fn record(&mut self, pattern: &Pattern, left: usize, top: usize) {
+        self.entries.push(Placement {
+            name: pattern.name.clone(),
+            cells: pattern.cells.clone(),
+            left,
+            top,
+            quarter_turns: 0,
+            mirrored: false,
+        });
+        self.selected = self.entries.len() - 1;
+    }
+
+    pub fn entries(&self) -> &[Placement] {
+        &self.entries
+    }
+
+    /// Replace every placement, e.g. with the ones of a reopened workspace.
+    pub fn replace(&mut self, entries: Vec<Placement>) {
+        self.selected = entries.len().saturating_sub(1);
+        self.entries = entries;
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.entries.len() {
+            self.selected += 1;
+        }
+    }
+
+    /// Bring the cells of the selected pattern to life again where it was placed.
+    pub fn place_again(&self, universe: &mut Universe) {
+        if let Some(entry) = self.entries.get(self.selected) {
+            entry.set_cells(universe, true);
+        }
+    }
+
+    /// Kill the cells of the selected pattern and forget it.
+    pub fn delete(&mut self, universe: &mut Universe) {
+        if self.selected < self.entries.len() {
+            let entry = self.entries.remove(self.selected);
+            entry.set_cells(universe, false);
+            self.selected = self.selected.min(self.entries.len().saturating_sub(1));
+        }
+    }
+
+    /// Turn the selected pattern a quarter turn clockwise in place.
+    pub fn rotate(&mut self, universe: &mut Universe) {
+        self.edit(universe, |entry| {
+            entry.quarter_turns = (entry.quarter_turns + 1) % 4
+        });
+    }
+
+    /// Mirror the selected pattern left to right in place.
+    pub fn mirror(&mut self, universe: &mut Universe) {
+        self.edit(universe, |entry| entry.mirrored = !entry.mirrored);
+    }
+
+    /// Move the selected pattern so that its top left corner is at `(x, y)`.
+    pub fn move_to(&mut self, universe: &mut Universe, x: usize, y: usize) {
+        self.edit(universe, |entry| (entry.left, entry.top) = (x, y));
+    }
+
+    /// Take the selected pattern off the grid, change it and put it back.
+    fn edit(&mut self, universe: &mut Universe, change: impl FnOnce(&mut Placement)) {
+        if let Some(entry) = self.entries.get_mut(self.selected) {
+            entry.set_cells(universe, false);
+            change(entry);
+            entry.set_cells(universe, true);
+        }
+    }
+
+    /// Outline the selected pattern on the grid.
+    pub fn draw_outline(
+        &self,
+        ctx: &mut Context,
+        canvas: &mut Canvas,
+        cell: CellSize,
+    ) -> GameResult {
+        if let Some(entry) = self.entries.get(self.selected) {
+            let (width, height) = entry.size();
+            let outline = cell.rect(
+                entry.left as f32,
+                entry.top as f32,
+                width as f32,
+                height as f32,
+            );
+            let mesh = Mesh::new_rectangle(ctx, DrawMode::stroke(2.0), outline, SELECTED_COLOR)?;
+            canvas.draw(&mesh, DrawParam::default());
+        }
+        Ok(())
+    }
+
+    /// List every placement in the top right corner of the window.
+    pub fn draw_list(&self, ctx: &mut Context, canvas: &mut Canvas) -> GameResult {
+        let mut text = Text::new(tr(Message::PlacementsTitle));
+        if self.entries.is_empty() {
+            text.add(format!("\n{}", tr(Message::PlacementsEmpty)));
+        }
+        for (i, entry) in self.entries.iter().enumerate() {
+            let (width, height) = entry.size();
+            let mut line = format!(
+                "\n{} {}x{} ({}, {}) {}\u{b0}",
+                entry.name.as_deref().unwrap_or(tr(Message::Unnamed)),
+                width,
+                height,
+                entry.left,
+                entry.top,
+                u32::from(entry.quarter_turns) * 90
+            );
+            if entry.mirrored {
+                line.push_str(&format!(" {}", tr(Message::Mirrored)));
+            }
+            let color = if i == self.selected {
+                SELECTED_COLOR
+            } else {
+                ENTRY_COLOR
+            };
+            text.add(TextFragment::new(line).color(color));
+        }
+        text.add(format!("\n\n{}", tr(Message::PlacementsKeys)));
+
+        let size = text.measure(ctx)?;
+        let (window_width, _) = ctx.gfx.drawable_size();
+        let panel = Rect::new(
+            window_width - size.x - 3.0 * PADDING,
+            PADDING,
+            size.x + 2.0 * PADDING,
+            size.y + 2.0 * PADDING,
+        );
+        let backdrop =
+            Mesh::new_rectangle(ctx, DrawMode::fill(), panel, Color::new(0.0, 0.0, 0.0, 0.8))?;
+        canvas.draw(&backdrop, DrawParam::default());
+        canvas.draw(
+            &text,
+            DrawParam::default().dest([panel.x + PADDING, panel.y + PADDING]),
+        );
+        Ok(())
+    }
+}
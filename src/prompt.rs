@@ -0,0 +1,78 @@
+use ggez::graphics::{Canvas, Color, DrawMode, DrawParam, Mesh, Rect, Text};
+use ggez::input::keyboard::KeyCode;
+use ggez::{Context, GameResult};
+
+// Longest text that can be typed.
+const MAX_LENGTH: usize = 60;
+const PADDING: f32 = 12.0;
+
+/// What a key did to the prompt.
+pub enum Outcome {
+    Editing,
+    Confirmed(String),
+    Cancelled,
+}
+
+/// A single line of text typed by the player in a box at the center of the window.
+pub struct Prompt {
+    title: String,
+    text: String,
+}
+
+impl Prompt {
+    /// Open a prompt with a title and the text to start editing from.
+    pub fn new(title: String, text: String) -> Prompt {
+        Prompt { title, text }
+    }
+
+    /// Append a typed character; control characters are handled by `key`.
+    pub fn type_char(&mut self, c: char) {
+        if !c.is_control() && self.text.chars().count() < MAX_LENGTH {
+            self.text.push(c);
+        }
+    }
+
+    /// Handle the keys that edit, confirm or cancel the text.
+    pub fn key(&mut self, key: Option<KeyCode>) -> Outcome {
+        match key {
+            Some(KeyCode::Back) => {
+                self.text.pop();
+                Outcome::Editing
+            }
+            Some(KeyCode::Return | KeyCode::NumpadEnter) => {
+                Outcome::Confirmed(self.text.trim().to_string())
+            }
+            Some(KeyCode::Escape) => Outcome::Cancelled,
+            _ => Outcome::Editing,
+        }
+    }
+
+    /// Draw the title and the text with a cursor.
+    pub fn draw(&self, ctx: &mut Context, canvas: &mut Canvas) -> GameResult {
+        let text = Text::new(format!("{}\n> {}_", self.title, self.text));
+        let size = text.measure(ctx)?;
+        let (window_width, window_height) = ctx.gfx.drawable_size();
+        // Keep the box wide enough for the longest text so it does not jump while typing
+        let width = size.x.max(window_width / 3.0) + 2.0 * PADDING;
+        let panel = Rect::new(
+            (window_width - width) / 2.0,
+            (window_height - size.y) / 2.0 - PADDING,
+            width,
+            size.y + 2.0 * PADDING,
+        );
+        let backdrop = Mesh::new_rectangle(
+            ctx,
+            DrawMode::fill(),
+            panel,
+            Color::new(0.1, 0.1, 0.1, 0.95),
+        )?;
+        let border = Mesh::new_rectangle(ctx, DrawMode::stroke(1.0), panel, Color::WHITE)?;
+        canvas.draw(&backdrop, DrawParam::default());
+        canvas.draw(&border, DrawParam::default());
+        canvas.draw(
+            &text,
+            DrawParam::default().dest([panel.x + PADDING, panel.y + PADDING]),
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,286 @@
+use ggez::graphics::{Canvas, Color, DrawMode, DrawParam, Mesh, Rect, Text};
+use ggez::{Context, GameError, GameResult};
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::io::Read;
+use std::path::Path;
+
+use crate::locale::{tr, Message};
+use crate::pattern::Pattern;
+use crate::spaceships::{normalize, orient};
+use crate::universe::Universe;
+
+// Directory of the level files inside the resources.
+const LEVEL_DIR: &str = "/levels";
+const MARGIN: f32 = 8.0;
+const PADDING: f32 = 8.0;
+
+/// A level file as written in TOML.
+#[derive(Deserialize)]
+struct LevelFile {
+    title: String,
+    description: String,
+    /// Most cells the player may add.
+    max_cells: usize,
+    /// Generations the target has to appear within.
+    generations: u64,
+    /// RLE or plaintext of the pattern to produce.
+    target: String,
+    /// Cells that are already there when the level starts.
+    #[serde(default)]
+    start: Option<String>,
+}
+
+/// A goal to reach with a limited number of placed cells.
+pub struct Level {
+    title: String,
+    description: String,
+    max_cells: usize,
+    generations: u64,
+    start: Pattern,
+    /// The target in each of its distinct orientations, as row-major masks.
+    targets: Vec<(usize, usize, Vec<bool>)>,
+}
+
+/// Where the current attempt stands.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Status {
+    Editing,
+    Running,
+    Solved,
+    Failed,
+}
+
+/// The levels and the progress through them.
+pub struct Puzzle {
+    levels: Vec<Level>,
+    current: usize,
+    status: Status,
+    /// Cells of the start pattern, which the player cannot change.
+    fixed: HashSet<(usize, usize)>,
+    placed: usize,
+}
+
+impl Puzzle {
+    /// Load every `.toml` file in `resources/levels`, in file name order.
+    pub fn load(ctx: &Context) -> GameResult<Puzzle> {
+        let mut paths: Vec<_> = ctx
+            .fs
+            .read_dir(LEVEL_DIR)?
+            .filter(|path| path.extension().is_some_and(|e| e == "toml"))
+            .collect();
+        paths.sort();
+        let levels = paths
+            .iter()
+            .map(|path| Level::load(ctx, path))
+            .collect::<GameResult<Vec<Level>>>()?;
+        if levels.is_empty() {
+            return Err(GameError::ResourceLoadError(format!(
+                "no levels in {}",
+                LEVEL_DIR
+            )));
+        }
+        Ok(Puzzle {
+            levels,
+            current: 0,
+            status: Status::Editing,
+            fixed: HashSet::new(),
+            placed: 0,
+        })
+    }
+
+    pub fn status(&self) -> Status {
+        self.status
+    }
+
+    /// Set up the current level from scratch.
+    pub fn restart(&mut self, universe: &mut Universe) {
+        let level = &self.levels[self.current];
+        universe.clear();
+        let (left, top) = universe.centered(&level.start);
+        universe.place(&level.start, left, top);
+        self.fixed = level
+            .start
+            .cells
+            .iter()
+            .map(|&(x, y)| (left + x, top + y))
+            .collect();
+        self.placed = 0;
+        self.status = Status::Editing;
+    }
+
+    /// Move on to the next level once the current one is solved.
+    pub fn advance(&mut self, universe: &mut Universe) {
+        if self.status == Status::Solved && self.current + 1 < self.levels.len() {
+            self.current += 1;
+            self.restart(universe);
+        }
+    }
+
+    /// Toggle a cell if the rules of the level allow it.
+    pub fn toggle(&mut self, universe: &mut Universe, x: usize, y: usize) {
+        if self.status != Status::Editing
+            || x >= universe.width()
+            || y >= universe.height()
+            || self.fixed.contains(&(x, y))
+        {
+            return;
+        }
+        if universe.cells()[y][x] {
+            universe.set(x, y, false);
+            self.placed -= 1;
+        } else if self.placed < self.levels[self.current].max_cells {
+            universe.set(x, y, true);
+            self.placed += 1;
+        }
+    }
+
+    /// Start running the placed cells.
+    pub fn run(&mut self) {
+        if self.status == Status::Editing {
+            self.status = Status::Running;
+        }
+    }
+
+    /// Check the goal after a generation has been computed.
+    pub fn check(&mut self, universe: &Universe) {
+        if self.status != Status::Running {
+            return;
+        }
+        let level = &self.levels[self.current];
+        if level
+            .targets
+            .iter()
+            .any(|target| appears(universe.cells(), target))
+        {
+            self.status = Status::Solved;
+        } else if universe.generation() >= level.generations {
+            self.status = Status::Failed;
+        }
+    }
+
+    /// Draw the goal and the state of the attempt in the top left corner.
+    pub fn draw(&self, ctx: &mut Context, canvas: &mut Canvas, generation: u64) -> GameResult {
+        let level = &self.levels[self.current];
+        let hint = match self.status {
+            Status::Editing => tr(Message::PuzzleEditing),
+            Status::Running => "",
+            Status::Solved if self.current + 1 == self.levels.len() => tr(Message::PuzzleComplete),
+            Status::Solved => tr(Message::PuzzleSolved),
+            Status::Failed => tr(Message::PuzzleFailed),
+        };
+        let text = Text::new(format!(
+            "{} {}/{}: {}\n{}\n{}: {}/{}  {}: {}/{}\n{}",
+            tr(Message::PuzzleLevel),
+            self.current + 1,
+            self.levels.len(),
+            level.title,
+            level.description,
+            tr(Message::PuzzleCells),
+            self.placed,
+            level.max_cells,
+            tr(Message::Generation),
+            generation.min(level.generations),
+            level.generations,
+            hint
+        ));
+        let size = text.measure(ctx)?;
+        let panel = Rect::new(
+            MARGIN,
+            MARGIN,
+            size.x + 2.0 * PADDING,
+            size.y + 2.0 * PADDING,
+        );
+        let backdrop =
+            Mesh::new_rectangle(ctx, DrawMode::fill(), panel, Color::new(0.0, 0.0, 0.0, 0.7))?;
+        canvas.draw(&backdrop, DrawParam::default());
+        let color = match self.status {
+            Status::Solved => Color::GREEN,
+            Status::Failed => Color::RED,
+            _ => Color::WHITE,
+        };
+        canvas.draw(
+            &text,
+            DrawParam::default()
+                .dest([MARGIN + PADDING, MARGIN + PADDING])
+                .color(color),
+        );
+        Ok(())
+    }
+}
+
+impl Level {
+    fn load(ctx: &Context, path: &Path) -> GameResult<Level> {
+        let invalid =
+            |e: String| GameError::ResourceLoadError(format!("{}: {}", path.display(), e));
+        let mut text = String::new();
+        ctx.fs
+            .open(path)?
+            .read_to_string(&mut text)
+            .map_err(|e| invalid(e.to_string()))?;
+        let file: LevelFile = toml::from_str(&text).map_err(|e| invalid(e.to_string()))?;
+        let target = Pattern::parse(&file.target).map_err(invalid)?;
+        let start = match &file.start {
+            Some(start) => Pattern::parse(start).map_err(invalid)?,
+            None => Pattern::default(),
+        };
+
+        // Accept the target in any rotation or reflection
+        let mut seen = HashSet::new();
+        let mut targets = Vec::new();
+        for orientation in 0..8 {
+            let cells = normalize(
+                target
+                    .cells
+                    .iter()
+                    .map(|&(x, y)| orient((x as i32, y as i32), orientation)),
+            );
+            if seen.insert(cells.clone()) {
+                targets.push(mask(&cells));
+            }
+        }
+
+        Ok(Level {
+            title: file.title,
+            description: file.description,
+            max_cells: file.max_cells,
+            generations: file.generations,
+            start,
+            targets,
+        })
+    }
+}
+
+/// Bounding box size and row-major alive flags of normalized cells.
+fn mask(cells: &[(i32, i32)]) -> (usize, usize, Vec<bool>) {
+    let width = cells.iter().map(|&(x, _)| x).max().unwrap_or(0) as usize + 1;
+    let height = cells.iter().map(|&(_, y)| y).max().unwrap_or(0) as usize + 1;
+    let mut mask = vec![false; width * height];
+    for &(x, y) in cells {
+        mask[y as usize * width + x as usize] = true;
+    }
+    (width, height, mask)
+}
+
+/// Whether the target is somewhere in the grid with nothing else touching it.
+fn appears(grid: &[Vec<bool>], (width, height, mask): &(usize, usize, Vec<bool>)) -> bool {
+    let rows = grid.len() as isize;
+    let columns = grid.first().map_or(0, Vec::len) as isize;
+    let alive = |x: isize, y: isize| {
+        x >= 0 && y >= 0 && x < columns && y < rows && grid[y as usize][x as usize]
+    };
+    let expected = |x: isize, y: isize| {
+        x >= 0
+            && y >= 0
+            && (x as usize) < *width
+            && (y as usize) < *height
+            && mask[y as usize * width + x as usize]
+    };
+    (0..=rows - *height as isize).any(|top| {
+        (0..=columns - *width as isize).any(|left| {
+            // The target and the one cell wide ring around it must match exactly
+            (-1..=*height as isize)
+                .all(|y| (-1..=*width as isize).all(|x| alive(left + x, top + y) == expected(x, y)))
+        })
+    })
+}
@@ -0,0 +1,118 @@
+use ggez::graphics::{Canvas, Color, DrawMode, DrawParam, Mesh, Text};
+use ggez::{Context, GameResult};
+use std::env;
+use std::io;
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+use std::time::Duration;
+
+use crate::bitgrid::BitGrid;
+use crate::export;
+use crate::locale::{tr_with, Message};
+
+// Generations kept before the recording stops by itself, to bound the memory
+// it takes: a byte per cell and generation.
+const MAX_FRAMES: usize = 3000;
+const INDICATOR_COLOR: Color = Color::new(0.9, 0.1, 0.1, 1.0);
+const MARGIN: f32 = 16.0;
+
+/// Generations captured from the grid while recording, encoded as an animated
+/// GIF once the recording stops, at a size that does not depend on the window.
+pub struct Recorder {
+    frames: Option<Vec<Vec<Vec<bool>>>>,
+    /// Where the last recording is being encoded to, and how that went.
+    saving: Option<Receiver<io::Result<PathBuf>>>,
+}
+
+impl Recorder {
+    pub fn new() -> Recorder {
+        Recorder {
+            frames: None,
+            saving: None,
+        }
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.frames.is_some()
+    }
+
+    /// Start recording with the grid as it is now as the first frame.
+    pub fn start(&mut self, cells: &[Vec<bool>]) {
+        self.frames = Some(vec![cells.to_vec()]);
+    }
+
+    /// Add a generation to the recording; returns whether there is room for more.
+    pub fn capture(&mut self, cells: &BitGrid) -> bool {
+        let Some(frames) = &mut self.frames else {
+            return true;
+        };
+        frames.push(cells.to_rows());
+        frames.len() < MAX_FRAMES
+    }
+
+    /// Stop recording and encode the frames on a background thread, each shown
+    /// for `delay`, into the export directory next to the executable.
+    pub fn stop(&mut self, delay: Duration) -> Option<PathBuf> {
+        let frames = self.frames.take()?;
+        let path = recording_path();
+        let target = path.clone();
+        // GIF delays are in hundredths of a second, and browsers ignore those below 2
+        let delay = (delay.as_millis() / 10).clamp(2, u16::MAX as u128) as u16;
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            let result = export::write_gif(&target, &frames, delay).map(|()| target);
+            let _ = sender.send(result);
+        });
+        self.saving = Some(receiver);
+        Some(path)
+    }
+
+    /// The outcome of encoding the last recording, once it is done.
+    pub fn poll(&mut self) -> Option<io::Result<PathBuf>> {
+        let result = match self.saving.as_ref()?.try_recv() {
+            Ok(result) => result,
+            Err(TryRecvError::Empty) => return None,
+            Err(TryRecvError::Disconnected) => Err(io::Error::other("the encoder stopped")),
+        };
+        self.saving = None;
+        Some(result)
+    }
+
+    /// A red dot and the number of frames at the top of the window while recording.
+    pub fn draw_indicator(&self, ctx: &mut Context, canvas: &mut Canvas) -> GameResult {
+        let Some(frames) = &self.frames else {
+            return Ok(());
+        };
+        let (window_width, _) = ctx.gfx.drawable_size();
+        let text = Text::new(tr_with(Message::Recording, &[&frames.len()]));
+        let size = text.measure(ctx)?;
+        let left = (window_width - size.x) / 2.0;
+        let dot = Mesh::new_circle(
+            ctx,
+            DrawMode::fill(),
+            [left - 12.0, MARGIN + size.y / 2.0],
+            6.0,
+            0.5,
+            INDICATOR_COLOR,
+        )?;
+        canvas.draw(&dot, DrawParam::default());
+        canvas.draw(
+            &text,
+            DrawParam::default()
+                .dest([left, MARGIN])
+                .color(INDICATOR_COLOR),
+        );
+        Ok(())
+    }
+}
+
+/// A fresh file in the export directory beside the executable, or in the
+/// working directory if the executable cannot be found.
+fn recording_path() -> PathBuf {
+    let path = export::export_path("gif");
+    match env::current_exe() {
+        Ok(exe) => exe.parent().map_or(path.clone(), |dir| dir.join(&path)),
+        Err(_) => path,
+    }
+}
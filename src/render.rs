@@ -0,0 +1,86 @@
+use ggez::graphics::{Canvas, Color, DrawParam, Mesh, MeshData, Rect, Vertex};
+use ggez::Context;
+
+/// Size of a cell on screen, which need not be square.
+#[derive(Clone, Copy)]
+pub struct CellSize {
+    pub width: f32,
+    pub height: f32,
+}
+
+impl CellSize {
+    /// Screen position of a point given in cell coordinates.
+    pub fn point(&self, x: f32, y: f32) -> [f32; 2] {
+        [x * self.width, y * self.height]
+    }
+
+    /// Screen rectangle covering `width` by `height` cells from `(x, y)`.
+    pub fn rect(&self, x: f32, y: f32, width: f32, height: f32) -> Rect {
+        Rect::new(
+            x * self.width,
+            y * self.height,
+            width * self.width,
+            height * self.height,
+        )
+    }
+
+    /// The cell under a screen position.
+    pub fn cell_at(&self, x: f32, y: f32) -> (usize, usize) {
+        ((x / self.width) as usize, (y / self.height) as usize)
+    }
+}
+
+/// Vertex and index buffers for a mesh built up one quad at a time.
+pub struct QuadBatch {
+    vertices: Vec<Vertex>,
+    indices: Vec<u32>,
+}
+
+impl QuadBatch {
+    /// Create an empty batch.
+    pub fn new() -> QuadBatch {
+        QuadBatch {
+            vertices: Vec::new(),
+            indices: Vec::new(),
+        }
+    }
+
+    /// Append a quad, given by its corners in winding order, as two triangles.
+    pub fn push(&mut self, corners: [[f32; 2]; 4], color: Color) {
+        let base = self.vertices.len() as u32;
+        for position in corners {
+            self.vertices.push(Vertex {
+                position,
+                uv: [0.0, 0.0],
+                color: color.into(),
+            });
+        }
+        self.indices
+            .extend_from_slice(&[base, base + 1, base + 2, base, base + 2, base + 3]);
+    }
+
+    /// Upload the batch and draw it; empty batches are skipped.
+    pub fn draw(&self, ctx: &mut Context, canvas: &mut Canvas) {
+        if self.indices.is_empty() {
+            return;
+        }
+        let mesh = Mesh::from_data(
+            ctx,
+            MeshData {
+                vertices: &self.vertices,
+                indices: &self.indices,
+            },
+        );
+        canvas.draw(&mesh, DrawParam::default());
+    }
+}
+
+/// Darken a color to fake lighting on the different faces of a solid.
+pub fn shade(color: Color, factor: f32) -> Color {
+    Color::new(
+        color.r * factor,
+        color.g * factor,
+        color.b * factor,
+        color.a,
+    )
+}
@@ -0,0 +1,88 @@
+use std::collections::VecDeque;
+
+use crate::bitgrid::BitGrid;
+use crate::delta::{self, Decoder, Encoder};
+
+/// Generations kept to rewind through when the configuration does not say otherwise.
+pub const DEFAULT_DEPTH: usize = 512;
+// Frames between keyframes, bounding how many deltas a rewind replays.
+const KEYFRAME_INTERVAL: u64 = 32;
+
+/// The last few hundred generations, stored as frames of the delta protocol of
+/// the stream: a keyframe every few generations and in between only the cells
+/// that flipped, so that a settling soup takes a few bytes a generation rather
+/// than a byte a cell.
+pub struct Rewind {
+    encoder: Encoder,
+    /// Oldest first, the newest being the generation on the grid.
+    frames: VecDeque<Vec<u8>>,
+    /// The generation before the oldest frame, for deltas left without their
+    /// keyframe once it was dropped.
+    base: Decoder,
+    depth: usize,
+}
+
+impl Rewind {
+    /// Keep `depth` generations; 0 keeps none.
+    pub fn new(depth: usize) -> Rewind {
+        Rewind {
+            encoder: Encoder::new(KEYFRAME_INTERVAL),
+            frames: VecDeque::new(),
+            base: Decoder::default(),
+            depth,
+        }
+    }
+
+    /// Keep a generation, dropping the oldest once there are too many.
+    pub fn record(&mut self, cells: &BitGrid, generation: u64) {
+        if self.depth == 0 {
+            return;
+        }
+        self.frames
+            .push_back(self.encoder.encode(cells, generation));
+        while self.frames.len() > self.depth {
+            if let Some(oldest) = self.frames.pop_front() {
+                // Frames of this protocol always apply in order
+                let _ = self.base.apply(&oldest);
+            }
+        }
+    }
+
+    /// Go back up to `steps` generations, no further than the oldest one kept,
+    /// forgetting the ones after it; returns its cells and number.
+    pub fn rewind(&mut self, steps: usize) -> Option<(Vec<Vec<bool>>, u64)> {
+        if self.frames.len() < 2 {
+            return None;
+        }
+        let keep = self.frames.len() - steps.min(self.frames.len() - 1);
+        self.frames.truncate(keep);
+        // The next generation recorded starts over with a keyframe
+        self.encoder.forget();
+        let last = keep - 1;
+        // Replay from the last keyframe, or from before the oldest frame if that is gone
+        let (mut decoder, first) = match self
+            .frames
+            .range(..=last)
+            .rposition(|f| delta::is_keyframe(f))
+        {
+            Some(keyframe) => (Decoder::default(), keyframe),
+            None => (self.base.clone(), 0),
+        };
+        for frame in self.frames.range(first..=last) {
+            decoder.apply(frame).ok()?;
+        }
+        Some((decoder.cells().to_vec(), decoder.generation()))
+    }
+
+    /// Forget every generation, e.g. when a new pattern is loaded.
+    pub fn clear(&mut self) {
+        self.frames.clear();
+        self.encoder.forget();
+        self.base = Decoder::default();
+    }
+
+    /// Approximate bytes held by the frames.
+    pub fn memory_usage(&self) -> usize {
+        self.frames.iter().map(Vec::capacity).sum()
+    }
+}
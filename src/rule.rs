@@ -0,0 +1,83 @@
+use std::fmt;
+
+/// A Life-like rule: the numbers of live neighbors at which a dead cell comes to
+/// life and at which a live cell stays alive.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Rule {
+    birth: [bool; 9],
+    survival: [bool; 9],
+}
+
+impl Rule {
+    /// Conway's Game of Life, B3/S23.
+    pub const LIFE: Rule = Rule {
+        birth: [false, false, false, true, false, false, false, false, false],
+        survival: [false, false, true, true, false, false, false, false, false],
+    };
+
+    /// Parse a rule in B/S notation such as `B36/S23`, or in the older S/B
+    /// notation such as `23/36` that some pattern files still use.
+    pub fn parse(text: &str) -> Result<Rule, String> {
+        let invalid = || format!("unsupported rule `{}`", text);
+        let mut rule = Rule {
+            birth: [false; 9],
+            survival: [false; 9],
+        };
+        let text = text.trim();
+        if text.contains(['B', 'b']) {
+            for part in text.split('/') {
+                let mut chars = part.chars();
+                let counts = match chars.next() {
+                    Some('B' | 'b') => &mut rule.birth,
+                    Some('S' | 's') => &mut rule.survival,
+                    _ => return Err(invalid()),
+                };
+                set_counts(counts, chars.as_str()).ok_or_else(invalid)?;
+            }
+        } else {
+            let (survival, birth) = text.split_once('/').ok_or_else(invalid)?;
+            set_counts(&mut rule.survival, survival).ok_or_else(invalid)?;
+            set_counts(&mut rule.birth, birth).ok_or_else(invalid)?;
+        }
+        Ok(rule)
+    }
+
+    /// Whether a dead cell with `neighbors` live neighbors comes to life.
+    pub fn born(&self, neighbors: usize) -> bool {
+        self.birth[neighbors]
+    }
+
+    /// Whether a live cell with `neighbors` live neighbors stays alive.
+    pub fn survives(&self, neighbors: usize) -> bool {
+        self.survival[neighbors]
+    }
+
+    /// Write the rule in the older S/B notation, e.g. `23/3`, that Life 1.05 uses.
+    pub fn survival_birth_notation(&self) -> String {
+        format!("{}/{}", digits(&self.survival), digits(&self.birth))
+    }
+}
+
+impl fmt::Display for Rule {
+    /// Write the rule in B/S notation.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "B{}/S{}", digits(&self.birth), digits(&self.survival))
+    }
+}
+
+/// The neighbor counts marked in `counts`, as digits.
+fn digits(counts: &[bool; 9]) -> String {
+    (0..9)
+        .filter(|&n| counts[n])
+        .map(|n| char::from(b'0' + n as u8))
+        .collect()
+}
+
+/// Mark every neighbor count listed in `digits`.
+fn set_counts(counts: &mut [bool; 9], digits: &str) -> Option<()> {
+    for c in digits.chars() {
+        let n = c.to_digit(10).filter(|&n| n <= 8)?;
+        counts[n as usize] = true;
+    }
+    Some(())
+}
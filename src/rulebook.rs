@@ -0,0 +1,141 @@
+use ggez::graphics::{Canvas, Color, DrawMode, DrawParam, Mesh, Rect, Text};
+use ggez::{Context, GameResult};
+
+use crate::locale::{tr, Message};
+use crate::multistate::AUTOMATA;
+use crate::seeding::Distribution;
+
+const PADDING: f32 = 12.0;
+const MARGIN: f32 = 8.0;
+// Widest the description panel gets before wrapping, in pixels.
+const NOTES_WIDTH: f32 = 520.0;
+
+/// What the grid is seeded with when switching to a rule.
+pub enum Demo {
+    /// A plaintext pattern, centered.
+    Pattern(&'static str),
+    /// Random noise, for rules where any soup shows what they do.
+    Soup(Distribution),
+}
+
+/// A built-in rule with a short description and a seed that shows it off.
+pub struct Entry {
+    pub name: &'static str,
+    /// In B/S notation.
+    pub rule: &'static str,
+    pub description: Message,
+    pub demo: Demo,
+}
+
+/// Every built-in rule, in the order of the menu.
+pub const RULES: [Entry; 7] = [
+    Entry {
+        name: "Life",
+        rule: "B3/S23",
+        description: Message::RuleLife,
+        demo: Demo::Pattern(".OO\nOO.\n.O."),
+    },
+    Entry {
+        name: "HighLife",
+        rule: "B36/S23",
+        description: Message::RuleHighLife,
+        demo: Demo::Pattern("..OOO\n.O..O\nO...O\nO..O.\nOOO.."),
+    },
+    Entry {
+        name: "Seeds",
+        rule: "B2/S",
+        description: Message::RuleSeeds,
+        demo: Demo::Pattern("OO"),
+    },
+    Entry {
+        name: "Day & Night",
+        rule: "B3678/S34678",
+        description: Message::RuleDayAndNight,
+        demo: Demo::Soup(Distribution::Disk),
+    },
+    Entry {
+        name: "Life without Death",
+        rule: "B3/S012345678",
+        description: Message::RuleLifeWithoutDeath,
+        demo: Demo::Pattern(".O.....\n...O...\nOO..OOO"),
+    },
+    Entry {
+        name: "Replicator",
+        rule: "B1357/S1357",
+        description: Message::RuleReplicator,
+        demo: Demo::Pattern("OO\nO."),
+    },
+    Entry {
+        name: "Diamoeba",
+        rule: "B35678/S5678",
+        description: Message::RuleDiamoeba,
+        demo: Demo::Soup(Distribution::Disk),
+    },
+];
+
+/// Draw the menu of built-in rules and multi-state automata, numbered by the
+/// keys that pick them.
+pub fn draw_menu(ctx: &mut Context, canvas: &mut Canvas) -> GameResult {
+    let mut lines = vec![tr(Message::RulebookTitle).to_string()];
+    for (i, entry) in RULES.iter().enumerate() {
+        lines.push(format!("{}: {} ({})", i + 1, entry.name, entry.rule));
+    }
+    for (i, automaton) in AUTOMATA.iter().enumerate() {
+        lines.push(format!("{}: {}", RULES.len() + i + 1, automaton.name()));
+    }
+    lines.push(tr(Message::SeedingCancel).to_string());
+    let text = Text::new(lines.join("\n"));
+    let size = text.measure(ctx)?;
+    let (window_width, window_height) = ctx.gfx.drawable_size();
+    let panel = Rect::new(
+        (window_width - size.x) / 2.0 - PADDING,
+        (window_height - size.y) / 2.0 - PADDING,
+        size.x + 2.0 * PADDING,
+        size.y + 2.0 * PADDING,
+    );
+    let backdrop = Mesh::new_rectangle(
+        ctx,
+        DrawMode::fill(),
+        panel,
+        Color::new(0.1, 0.1, 0.1, 0.95),
+    )?;
+    let border = Mesh::new_rectangle(ctx, DrawMode::stroke(1.0), panel, Color::WHITE)?;
+    canvas.draw(&backdrop, DrawParam::default());
+    canvas.draw(&border, DrawParam::default());
+    canvas.draw(
+        &text,
+        DrawParam::default().dest([panel.x + PADDING, panel.y + PADDING]),
+    );
+    Ok(())
+}
+
+/// Describe a rule at the bottom of the window.
+pub fn draw_notes(ctx: &mut Context, canvas: &mut Canvas, entry: &Entry) -> GameResult {
+    let (window_width, window_height) = ctx.gfx.drawable_size();
+    let mut text = Text::new(format!(
+        "{} ({})\n{}\n{}",
+        entry.name,
+        entry.rule,
+        tr(entry.description),
+        tr(Message::RulebookStart)
+    ));
+    text.set_bounds([
+        NOTES_WIDTH.min(window_width - 2.0 * (MARGIN + PADDING)),
+        f32::INFINITY,
+    ]);
+    let size = text.measure(ctx)?;
+    let panel = Rect::new(
+        (window_width - size.x) / 2.0 - PADDING,
+        window_height - MARGIN - size.y - 2.0 * PADDING,
+        size.x + 2.0 * PADDING,
+        size.y + 2.0 * PADDING,
+    );
+    let backdrop =
+        Mesh::new_rectangle(ctx, DrawMode::fill(), panel, Color::new(0.0, 0.0, 0.0, 0.8))?;
+    canvas.draw(&backdrop, DrawParam::default());
+    canvas.draw(
+        &text,
+        DrawParam::default().dest([panel.x + PADDING, panel.y + PADDING]),
+    );
+    Ok(())
+}
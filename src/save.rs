@@ -0,0 +1,119 @@
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+
+use crate::annotations::Label;
+
+/// Directory that holds saved states.
+pub const SAVE_DIR: &str = "saves";
+// File written by quicksave and read by quickload.
+const QUICKSAVE_FILE: &str = "quicksave.state.gz";
+// File the grid is rescued to when the game crashes.
+const EMERGENCY_FILE: &str = "emergency.state.gz";
+// First line of every state file, followed by the grid dimensions.
+const HEADER: &str = "krida-state 1";
+// Every gzip stream starts with these two bytes.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+// Prefix of the lines after the grid that attach a label to a cell.
+const LABEL_PREFIX: &str = "label ";
+
+/// Everything a state file holds.
+pub struct State {
+    pub grid: Vec<Vec<bool>>,
+    pub labels: Vec<Label>,
+}
+
+/// Path of the quicksave file.
+pub fn quicksave_path() -> PathBuf {
+    Path::new(SAVE_DIR).join(QUICKSAVE_FILE)
+}
+
+/// Path of the file written when the game crashes.
+pub fn emergency_path() -> PathBuf {
+    Path::new(SAVE_DIR).join(EMERGENCY_FILE)
+}
+
+/// Write the grid as gzip-compressed text, one row of `.` and `O` per line.
+///
+/// Labels follow the grid as `label X Y text` lines, which older versions skip.
+pub fn write_state(path: &Path, grid: &[Vec<bool>], labels: &[Label]) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let file = BufWriter::new(File::create(path)?);
+    let mut out = GzEncoder::new(file, Compression::default());
+
+    let width = grid.first().map_or(0, Vec::len);
+    writeln!(out, "{} {} {}", HEADER, width, grid.len())?;
+    for row in grid {
+        let line: String = row
+            .iter()
+            .map(|&alive| if alive { 'O' } else { '.' })
+            .collect();
+        writeln!(out, "{}", line)?;
+    }
+    for label in labels {
+        writeln!(
+            out,
+            "{}{} {} {}",
+            LABEL_PREFIX, label.x, label.y, label.text
+        )?;
+    }
+    out.finish()?.flush()
+}
+
+/// Read a state file, decompressing it first if it is gzipped.
+///
+/// Uncompressed files written by hand or by older versions load just as well.
+pub fn read_state(path: &Path) -> io::Result<State> {
+    let bytes = fs::read(path)?;
+    let mut text = String::new();
+    if bytes.starts_with(&GZIP_MAGIC) {
+        GzDecoder::new(&bytes[..]).read_to_string(&mut text)?;
+    } else {
+        text = String::from_utf8(bytes).map_err(|e| invalid(&e.to_string()))?;
+    }
+
+    let mut lines = text.lines();
+    let header = lines.next().unwrap_or_default();
+    let dimensions = header
+        .strip_prefix(HEADER)
+        .ok_or_else(|| invalid("not a krida state file"))?;
+    let mut numbers = dimensions.split_whitespace().map(str::parse::<usize>);
+    let (width, height) = match (numbers.next(), numbers.next()) {
+        (Some(Ok(width)), Some(Ok(height))) => (width, height),
+        _ => return Err(invalid("missing grid dimensions")),
+    };
+
+    let grid: Vec<Vec<bool>> = lines
+        .by_ref()
+        .take(height)
+        .map(|line| line.chars().take(width).map(|c| c == 'O').collect())
+        .collect();
+    if grid.len() != height || grid.iter().any(|row| row.len() != width) {
+        return Err(invalid("grid is truncated"));
+    }
+
+    let labels = lines
+        .filter_map(|line| line.strip_prefix(LABEL_PREFIX))
+        .map(|label| {
+            let mut fields = label.splitn(3, ' ');
+            match (fields.next(), fields.next(), fields.next()) {
+                (Some(x), Some(y), Some(text)) => Ok(Label {
+                    x: x.parse().map_err(|_| invalid("invalid label position"))?,
+                    y: y.parse().map_err(|_| invalid("invalid label position"))?,
+                    text: text.to_string(),
+                }),
+                _ => Err(invalid("incomplete label")),
+            }
+        })
+        .collect::<io::Result<Vec<Label>>>()?;
+    Ok(State { grid, labels })
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
@@ -0,0 +1,40 @@
+use serde::Deserialize;
+use std::time::Duration;
+
+use crate::tick;
+
+/// A stretch of generations run at one speed.
+#[derive(Clone, Copy, Deserialize)]
+pub struct Stage {
+    /// Generation the stage starts at.
+    pub from: u64,
+    /// Time between generations during the stage, fractions allowed.
+    pub delay_ms: f64,
+}
+
+/// Speeds that change with the generation, e.g. slow for the first 100
+/// generations and ten times as fast afterwards.
+#[derive(Clone, Default, Deserialize)]
+#[serde(from = "Vec<Stage>")]
+pub struct SpeedSchedule {
+    /// Sorted by the generation they start at.
+    stages: Vec<Stage>,
+}
+
+impl From<Vec<Stage>> for SpeedSchedule {
+    fn from(mut stages: Vec<Stage>) -> SpeedSchedule {
+        stages.sort_by_key(|stage| stage.from);
+        SpeedSchedule { stages }
+    }
+}
+
+impl SpeedSchedule {
+    /// Delay of the stage `generation` falls in, or `None` before the first stage.
+    pub fn delay_at(&self, generation: u64) -> Option<Duration> {
+        self.stages
+            .iter()
+            .rev()
+            .find(|stage| stage.from <= generation)
+            .map(|stage| tick::delay_from_ms(stage.delay_ms))
+    }
+}
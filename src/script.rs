@@ -0,0 +1,159 @@
+use mlua::{Lua, Table};
+use std::cell::RefCell;
+use std::fs;
+use std::rc::Rc;
+
+use crate::cli::ScriptOptions;
+use crate::pattern::Pattern;
+use crate::rule::Rule;
+use crate::universe::Universe;
+
+// Side of the square universe scripts run in when no size is given.
+const DEFAULT_SIZE: usize = 512;
+
+/// Run a Lua script against a headless universe through a subset of Golly's API.
+///
+/// As in Golly, scripts get the API with `local g = golly()` and address cells
+/// with the origin at the center of the universe.
+pub fn run(options: &ScriptOptions) -> Result<(), String> {
+    let source = fs::read_to_string(&options.path)
+        .map_err(|e| format!("cannot read {}: {}", options.path.display(), e))?;
+    let (width, height) = options.size.unwrap_or((DEFAULT_SIZE, DEFAULT_SIZE));
+    let universe = Rc::new(RefCell::new(Universe::new(width, height)));
+
+    let lua = Lua::new();
+    let result = golly_api(&lua, &universe).and_then(|g| {
+        let golly = lua.create_function(move |_, ()| Ok(g.clone()))?;
+        lua.globals().set("golly", golly)?;
+        lua.load(&source)
+            .set_name(options.path.display().to_string())
+            .exec()
+    });
+    result.map_err(|e| e.to_string())
+}
+
+/// Build the table returned by `golly()`.
+fn golly_api(lua: &Lua, universe: &Rc<RefCell<Universe>>) -> mlua::Result<Table> {
+    let g = lua.create_table()?;
+
+    let u = universe.clone();
+    g.set(
+        "new",
+        lua.create_function(move |_, _title: Option<String>| {
+            let mut universe = u.borrow_mut();
+            universe.clear();
+            universe.reset_generation();
+            Ok(())
+        })?,
+    )?;
+
+    let u = universe.clone();
+    g.set(
+        "open",
+        lua.create_function(move |_, path: String| {
+            let text = fs::read_to_string(&path).map_err(mlua::Error::external)?;
+            let pattern = Pattern::parse(&text).map_err(mlua::Error::runtime)?;
+            let mut universe = u.borrow_mut();
+            universe.clear();
+            universe.reset_generation();
+            let (left, top) = universe.centered(&pattern);
+            universe.place(&pattern, left, top);
+            Ok(())
+        })?,
+    )?;
+
+    let u = universe.clone();
+    g.set(
+        "save",
+        lua.create_function(move |_, (path, format): (String, Option<String>)| {
+            if format.as_deref().is_some_and(|f| f != "rle") {
+                return Err(mlua::Error::runtime("save only supports the rle format"));
+            }
+            let universe = u.borrow();
+            let rle = universe.to_pattern().to_rle(&universe.rule().to_string());
+            fs::write(&path, rle).map_err(mlua::Error::external)
+        })?,
+    )?;
+
+    let u = universe.clone();
+    g.set(
+        "setcell",
+        lua.create_function(move |_, (x, y, state): (i64, i64, i64)| {
+            let mut universe = u.borrow_mut();
+            // Cells outside the bounded universe are silently dropped
+            if let Some((x, y)) = to_cell(&universe, x, y) {
+                universe.set(x, y, state != 0);
+            }
+            Ok(())
+        })?,
+    )?;
+
+    let u = universe.clone();
+    g.set(
+        "getcell",
+        lua.create_function(move |_, (x, y): (i64, i64)| {
+            let universe = u.borrow();
+            let alive = to_cell(&universe, x, y).is_some_and(|(x, y)| universe.cells()[y][x]);
+            Ok(alive as i64)
+        })?,
+    )?;
+
+    let u = universe.clone();
+    g.set(
+        "run",
+        lua.create_function(move |_, generations: u64| {
+            let mut universe = u.borrow_mut();
+            for _ in 0..generations {
+                universe.step();
+            }
+            Ok(())
+        })?,
+    )?;
+
+    // Golly returns the population and generation as strings because they can be huge
+    let u = universe.clone();
+    g.set(
+        "getpop",
+        lua.create_function(move |_, ()| Ok(u.borrow().population().to_string()))?,
+    )?;
+
+    let u = universe.clone();
+    g.set(
+        "getgen",
+        lua.create_function(move |_, ()| Ok(u.borrow().generation().to_string()))?,
+    )?;
+
+    let u = universe.clone();
+    g.set(
+        "getrule",
+        lua.create_function(move |_, ()| Ok(u.borrow().rule().to_string()))?,
+    )?;
+
+    let u = universe.clone();
+    g.set(
+        "setrule",
+        lua.create_function(move |_, rule: String| {
+            let rule = Rule::parse(&rule).map_err(mlua::Error::runtime)?;
+            u.borrow_mut().set_rule(rule);
+            Ok(())
+        })?,
+    )?;
+
+    // Without a status bar, messages go to stdout
+    g.set(
+        "show",
+        lua.create_function(|_, message: String| {
+            println!("{}", message);
+            Ok(())
+        })?,
+    )?;
+
+    Ok(g)
+}
+
+/// Map Golly coordinates, centered on the origin, to a cell of the universe.
+fn to_cell(universe: &Universe, x: i64, y: i64) -> Option<(usize, usize)> {
+    let x = usize::try_from(x + (universe.width() / 2) as i64).ok()?;
+    let y = usize::try_from(y + (universe.height() / 2) as i64).ok()?;
+    (x < universe.width() && y < universe.height()).then_some((x, y))
+}
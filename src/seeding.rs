@@ -0,0 +1,206 @@
+use ggez::graphics::{Canvas, Color, DrawMode, DrawParam, Mesh, Rect, Text};
+use ggez::{Context, GameResult};
+use std::fmt;
+
+use crate::locale::{tr, Message};
+
+// Density of the noise inside the shaped distributions.
+const NOISE_DENSITY: f64 = 0.5;
+// Radius of the disk and outer radius of the ring, as a share of the shorter side.
+const DISK_RADIUS: f64 = 0.35;
+// Inner radius of the ring, as a share of its outer radius.
+const RING_INNER_RADIUS: f64 = 0.7;
+// Width of each stripe of noise and of each empty stripe, in cells.
+const STRIPE_WIDTH: usize = 6;
+const PADDING: f32 = 12.0;
+
+/// Shapes of random noise the grid can be seeded with.
+#[derive(Clone, Copy)]
+pub enum Distribution {
+    /// The same density everywhere.
+    Uniform,
+    /// Noise in a disk around the center.
+    Disk,
+    /// Noise in a ring around the center.
+    Ring,
+    /// Vertical stripes of noise with empty stripes in between.
+    Stripes,
+    /// Density rising from nothing on the left to full on the right.
+    Gradient,
+}
+
+/// Every distribution, in the order of the menu.
+pub const DISTRIBUTIONS: [Distribution; 5] = [
+    Distribution::Uniform,
+    Distribution::Disk,
+    Distribution::Ring,
+    Distribution::Stripes,
+    Distribution::Gradient,
+];
+
+impl Distribution {
+    fn message(self) -> Message {
+        match self {
+            Distribution::Uniform => Message::SeedUniform,
+            Distribution::Disk => Message::SeedDisk,
+            Distribution::Ring => Message::SeedRing,
+            Distribution::Stripes => Message::SeedStripes,
+            Distribution::Gradient => Message::SeedGradient,
+        }
+    }
+
+    /// Chance of the cell at `(x, y)` of a `width` by `height` grid being alive.
+    pub fn density(self, x: usize, y: usize, width: usize, height: usize) -> f64 {
+        let radius = DISK_RADIUS * width.min(height) as f64;
+        let distance = || {
+            let dx = x as f64 + 0.5 - width as f64 / 2.0;
+            let dy = y as f64 + 0.5 - height as f64 / 2.0;
+            dx.hypot(dy)
+        };
+        let inside = match self {
+            Distribution::Uniform => true,
+            Distribution::Disk => distance() <= radius,
+            Distribution::Ring => (RING_INNER_RADIUS * radius..=radius).contains(&distance()),
+            Distribution::Stripes => (x / STRIPE_WIDTH).is_multiple_of(2),
+            Distribution::Gradient => return (x as f64 + 0.5) / width as f64,
+        };
+        if inside {
+            NOISE_DENSITY
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Symmetry classes a soup can be constrained to, named as on Catagolue.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum SoupSymmetry {
+    /// No symmetry at all.
+    C1,
+    /// The same after half a turn around the center.
+    C2,
+    /// The same after a quarter turn around the center.
+    C4,
+    /// Mirrored across the vertical axis.
+    D2,
+    /// Mirrored across both axes.
+    D4,
+    /// Mirrored across both axes and both diagonals.
+    D8,
+}
+
+impl fmt::Display for SoupSymmetry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            SoupSymmetry::C1 => "C1",
+            SoupSymmetry::C2 => "C2",
+            SoupSymmetry::C4 => "C4",
+            SoupSymmetry::D2 => "D2",
+            SoupSymmetry::D4 => "D4",
+            SoupSymmetry::D8 => "D8",
+        })
+    }
+}
+
+impl SoupSymmetry {
+    /// The next class in the menu, wrapping around.
+    pub fn next(self) -> SoupSymmetry {
+        match self {
+            SoupSymmetry::C1 => SoupSymmetry::C2,
+            SoupSymmetry::C2 => SoupSymmetry::C4,
+            SoupSymmetry::C4 => SoupSymmetry::D2,
+            SoupSymmetry::D2 => SoupSymmetry::D4,
+            SoupSymmetry::D4 => SoupSymmetry::D8,
+            SoupSymmetry::D8 => SoupSymmetry::C1,
+        }
+    }
+
+    /// Whether the class turns the grid by a quarter or mirrors it across a
+    /// diagonal, which only a square can take.
+    fn needs_square(self) -> bool {
+        matches!(self, SoupSymmetry::C4 | SoupSymmetry::D8)
+    }
+
+    /// Every cell `(u, v)` of an `width` by `height` grid is mapped to, the
+    /// cell itself included.
+    fn images(self, u: usize, v: usize, width: usize, height: usize) -> Vec<(usize, usize)> {
+        let (mu, mv) = (width - 1 - u, height - 1 - v);
+        match self {
+            SoupSymmetry::C1 => vec![(u, v)],
+            SoupSymmetry::C2 => vec![(u, v), (mu, mv)],
+            // On a square, where the width and height are the same
+            SoupSymmetry::C4 => vec![(u, v), (mv, u), (mu, mv), (v, mu)],
+            SoupSymmetry::D2 => vec![(u, v), (mu, v)],
+            SoupSymmetry::D4 => vec![(u, v), (mu, v), (u, mv), (mu, mv)],
+            SoupSymmetry::D8 => vec![
+                (u, v),
+                (mv, u),
+                (mu, mv),
+                (v, mu),
+                (mu, v),
+                (u, mv),
+                (v, u),
+                (mv, mu),
+            ],
+        }
+    }
+}
+
+/// The cells made symmetric: each takes the state of the first cell it is
+/// mapped to, so that the random states of one share of the grid are repeated
+/// over the rest. The classes that need a square keep to the largest one in
+/// the middle and leave the cells around it dead.
+pub fn symmetrize(cells: &[Vec<bool>], symmetry: SoupSymmetry) -> Vec<Vec<bool>> {
+    let (height, width) = (cells.len(), cells.first().map_or(0, Vec::len));
+    let (mut left, mut top, mut columns, mut rows) = (0, 0, width, height);
+    if symmetry.needs_square() {
+        let side = width.min(height);
+        (left, top, columns, rows) = ((width - side) / 2, (height - side) / 2, side, side);
+    }
+    let mut result = vec![vec![false; width]; height];
+    for v in 0..rows {
+        for u in 0..columns {
+            let (su, sv) = symmetry
+                .images(u, v, columns, rows)
+                .into_iter()
+                .min_by_key(|&(u, v)| (v, u))
+                .unwrap_or((u, v));
+            result[top + v][left + u] = cells[top + sv][left + su];
+        }
+    }
+    result
+}
+
+/// Draw the menu of distributions, numbered by the keys that pick them, and
+/// the symmetry the soup will have.
+pub fn draw_menu(ctx: &mut Context, canvas: &mut Canvas, symmetry: SoupSymmetry) -> GameResult {
+    let mut lines = vec![tr(Message::SeedingTitle).to_string()];
+    for (i, distribution) in DISTRIBUTIONS.iter().enumerate() {
+        lines.push(format!("{}: {}", i + 1, tr(distribution.message())));
+    }
+    lines.push(format!("S: {} {}", tr(Message::SeedingSymmetry), symmetry));
+    lines.push(tr(Message::SeedingCancel).to_string());
+    let text = Text::new(lines.join("\n"));
+    let size = text.measure(ctx)?;
+    let (window_width, window_height) = ctx.gfx.drawable_size();
+    let panel = Rect::new(
+        (window_width - size.x) / 2.0 - PADDING,
+        (window_height - size.y) / 2.0 - PADDING,
+        size.x + 2.0 * PADDING,
+        size.y + 2.0 * PADDING,
+    );
+    let backdrop = Mesh::new_rectangle(
+        ctx,
+        DrawMode::fill(),
+        panel,
+        Color::new(0.1, 0.1, 0.1, 0.95),
+    )?;
+    let border = Mesh::new_rectangle(ctx, DrawMode::stroke(1.0), panel, Color::WHITE)?;
+    canvas.draw(&backdrop, DrawParam::default());
+    canvas.draw(&border, DrawParam::default());
+    canvas.draw(
+        &text,
+        DrawParam::default().dest([panel.x + PADDING, panel.y + PADDING]),
+    );
+    Ok(())
+}
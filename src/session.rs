@@ -0,0 +1,102 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use tracing::{error, warn};
+
+use crate::cli::Options;
+use crate::config;
+use crate::game::{self, ViewMode};
+use crate::theme::Theme;
+use crate::tick;
+
+/// Settings changed while playing, saved on exit and restored on the next launch.
+///
+/// They live in the data directory rather than in `config.toml`, which is left
+/// to the user to edit.
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct Session {
+    pub grid_width: usize,
+    pub grid_height: usize,
+    /// Time between generations, fractions allowed.
+    pub update_delay_ms: f64,
+    /// Percent by which the speed keys change the update delay.
+    pub speed_step_percent: u32,
+    pub view: ViewMode,
+    /// Colors picked in the panel.
+    pub theme: Theme,
+}
+
+impl Default for Session {
+    fn default() -> Session {
+        Session {
+            grid_width: game::GRID_WIDTH,
+            grid_height: game::GRID_HEIGHT,
+            update_delay_ms: game::DEFAULT_UPDATE_DELAY_MILISECONDS as f64,
+            speed_step_percent: game::DEFAULT_SPEED_STEP_PERCENT,
+            view: ViewMode::Flat,
+            theme: Theme::Classic,
+        }
+    }
+}
+
+impl Session {
+    /// Read the settings of the last session, falling back to the defaults.
+    pub fn load() -> Session {
+        let session = match session_path().map(fs::read_to_string) {
+            Some(Ok(text)) => toml::from_str(&text).unwrap_or_else(|e| {
+                warn!("ignoring the last session: {}", e);
+                Session::default()
+            }),
+            Some(Err(e)) if e.kind() != io::ErrorKind::NotFound => {
+                error!("cannot read the last session: {}", e);
+                Session::default()
+            }
+            _ => Session::default(),
+        };
+        session.clamped()
+    }
+
+    /// Apply the size and speed given on the command line, which win over the last session.
+    pub fn overridden(mut self, options: &Options) -> Session {
+        if let Some(width) = options.width {
+            self.grid_width = width;
+        }
+        if let Some(height) = options.height {
+            self.grid_height = height;
+        }
+        if let Some(speed) = options.speed {
+            self.update_delay_ms = 1000.0 / speed;
+        }
+        self
+    }
+
+    /// Write the settings for the next launch.
+    pub fn save(&self) -> io::Result<()> {
+        let Some(path) = session_path() else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let text = toml::to_string(self).map_err(io::Error::other)?;
+        fs::write(path, text)
+    }
+
+    /// Keep hand-edited values within what the game supports.
+    fn clamped(mut self) -> Session {
+        let (min, max) = (game::MIN_GRID_SIZE, game::MAX_GRID_SIZE);
+        self.grid_width = self.grid_width.clamp(min, max);
+        self.grid_height = self.grid_height.clamp(min, max);
+        self.update_delay_ms = tick::delay_from_ms(self.update_delay_ms).as_secs_f64() * 1000.0;
+        self.speed_step_percent = self
+            .speed_step_percent
+            .clamp(game::MIN_SPEED_STEP_PERCENT, game::MAX_SPEED_STEP_PERCENT);
+        self
+    }
+}
+
+fn session_path() -> Option<PathBuf> {
+    config::project_dirs().map(|dirs| dirs.data_dir().join("session.toml"))
+}
@@ -0,0 +1,350 @@
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::BTreeSet;
+use std::fs;
+use std::io::{self, Write};
+use std::mem;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::cli::SoupSearchOptions;
+use crate::pattern::Pattern;
+use crate::period::PeriodDetector;
+use crate::rule::Rule;
+use crate::save::SAVE_DIR;
+use crate::universe::Universe;
+
+// Chance of each cell of a soup being alive.
+const DENSITY: f64 = 0.5;
+// How often the dashboard is brought up to date.
+const DASHBOARD_INTERVAL: Duration = Duration::from_secs(1);
+// Version of the checkpoint format written by this build, the only one it resumes.
+const CHECKPOINT_VERSION: u32 = 1;
+
+/// What became of a soup.
+#[derive(Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Fate {
+    Died,
+    Settled {
+        period: usize,
+    },
+    /// Still changing when the generations ran out.
+    Unsettled,
+}
+
+/// A soup that has been run, as sent back by a worker.
+#[derive(Serialize, Deserialize)]
+struct Find {
+    /// Index of the soup, which together with the seed of the search recreates it.
+    index: u64,
+    fate: Fate,
+    /// Generation the soup died or settled in, or the limit if it did not.
+    lifespan: u64,
+    peak_population: usize,
+}
+
+/// How far a search has come, as written to checkpoints.
+#[derive(Default, Serialize, Deserialize)]
+struct Progress {
+    done: u64,
+    died: u64,
+    settled: u64,
+    /// Every soup below this index has been run.
+    next: u64,
+    /// Soups at or above `next` that have been run, out of order.
+    finished: BTreeSet<u64>,
+    /// The longest-lived soups, longest first.
+    best: Vec<Find>,
+}
+
+impl Progress {
+    fn record(&mut self, find: Find, top: usize) {
+        self.done += 1;
+        match find.fate {
+            Fate::Died => self.died += 1,
+            Fate::Settled { .. } => self.settled += 1,
+            Fate::Unsettled => (),
+        }
+        self.finished.insert(find.index);
+        while self.finished.remove(&self.next) {
+            self.next += 1;
+        }
+        if self.best.len() < top
+            || self
+                .best
+                .last()
+                .is_some_and(|last| find.lifespan > last.lifespan)
+        {
+            self.best.push(find);
+            self.best.sort_by_key(|find| Reverse(find.lifespan));
+            self.best.truncate(top);
+        }
+    }
+}
+
+/// Everything needed to resume a search: what it searches and how far it has come.
+#[derive(Serialize, Deserialize)]
+struct Checkpoint {
+    version: u32,
+    rule: String,
+    seed: u64,
+    soups: u64,
+    size: (usize, usize),
+    margin: usize,
+    generations: u64,
+    top: usize,
+    progress: Progress,
+}
+
+impl Checkpoint {
+    fn read(path: &Path) -> Result<Checkpoint, String> {
+        let text = fs::read_to_string(path)
+            .map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+        let checkpoint: Checkpoint = toml::from_str(&text)
+            .map_err(|e| format!("cannot resume {}: {}", path.display(), e))?;
+        if checkpoint.version != CHECKPOINT_VERSION {
+            return Err(format!(
+                "cannot resume {}: unsupported checkpoint version {}",
+                path.display(),
+                checkpoint.version
+            ));
+        }
+        Ok(checkpoint)
+    }
+
+    /// Write the checkpoint next to its final place first, so that a crash
+    /// while writing leaves the previous checkpoint intact.
+    fn write(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let text = toml::to_string(self).map_err(io::Error::other)?;
+        let partial = path.with_extension("toml.partial");
+        fs::write(&partial, text)?;
+        fs::rename(&partial, path)
+    }
+}
+
+/// Where the checkpoints of a search are written unless it was resumed from one.
+fn checkpoint_path(seed: u64) -> PathBuf {
+    Path::new(SAVE_DIR)
+        .join("checkpoints")
+        .join(format!("soup_search_{}.toml", seed))
+}
+
+/// Run random soups on every worker thread, showing the progress on stderr,
+/// and print the longest-lived ones with their RLE.
+pub fn run(options: &SoupSearchOptions) -> Result<(), String> {
+    let mut options = options.clone();
+    let mut progress = Progress::default();
+    if let Some(path) = &options.resume {
+        let checkpoint = Checkpoint::read(path)?;
+        // The checkpoint decides which soups are run, the command line only how
+        options.rule = checkpoint.rule;
+        options.seed = checkpoint.seed;
+        options.soups = checkpoint.soups;
+        options.size = checkpoint.size;
+        options.margin = checkpoint.margin;
+        options.generations = checkpoint.generations;
+        options.top = checkpoint.top;
+        progress = checkpoint.progress;
+        eprintln!("resuming {} after {} soups", path.display(), progress.done);
+    }
+    let options = &options;
+    let checkpoint_file = options
+        .resume
+        .clone()
+        .unwrap_or_else(|| checkpoint_path(options.seed));
+    let rule = Rule::parse(&options.rule)?;
+    let threads = options
+        .threads
+        .unwrap_or_else(|| thread::available_parallelism().map_or(1, |threads| threads.get()));
+    let next = AtomicU64::new(progress.next);
+    // Soups run before the checkpoint was written are not run again
+    let skipped = progress.finished.clone();
+    let (sender, results) = mpsc::channel::<Find>();
+    let started = Instant::now();
+    let done_before = progress.done;
+    let mut last_checkpoint = progress.done;
+    let mut result = Ok(());
+
+    thread::scope(|scope| {
+        for _ in 0..threads {
+            let sender = sender.clone();
+            let (next, skipped) = (&next, &skipped);
+            scope.spawn(move || loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                if index >= options.soups {
+                    break;
+                }
+                if skipped.contains(&index) {
+                    continue;
+                }
+                let soup = soup(options.seed, index, options.size);
+                // The main thread only stops listening once every soup is in
+                if sender.send(run_soup(&soup, rule, index, options)).is_err() {
+                    break;
+                }
+            });
+        }
+        // Only the workers hold senders now, so the queue closes when they are done
+        drop(sender);
+
+        let mut last_shown = Instant::now();
+        loop {
+            match results.recv_timeout(DASHBOARD_INTERVAL) {
+                Ok(find) => progress.record(find, options.top),
+                Err(RecvTimeoutError::Timeout) => (),
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+            if last_shown.elapsed() >= DASHBOARD_INTERVAL {
+                let rate = (progress.done - done_before) as f64
+                    / started.elapsed().as_secs_f64().max(f64::EPSILON);
+                show_dashboard(&progress, options.soups, rate, threads);
+                last_shown = Instant::now();
+            }
+            if options
+                .checkpoint_every
+                .is_some_and(|every| progress.done - last_checkpoint >= every)
+            {
+                last_checkpoint = progress.done;
+                let checkpoint = Checkpoint {
+                    version: CHECKPOINT_VERSION,
+                    rule: options.rule.clone(),
+                    seed: options.seed,
+                    soups: options.soups,
+                    size: options.size,
+                    margin: options.margin,
+                    generations: options.generations,
+                    top: options.top,
+                    progress: mem::take(&mut progress),
+                };
+                if let Err(e) = checkpoint.write(&checkpoint_file) {
+                    result = Err(format!("cannot write {}: {}", checkpoint_file.display(), e));
+                    // Stop handing out soups, a search that cannot be resumed is not worth running on
+                    next.store(options.soups, Ordering::Relaxed);
+                }
+                progress = checkpoint.progress;
+            }
+        }
+    });
+    result?;
+    let rate =
+        (progress.done - done_before) as f64 / started.elapsed().as_secs_f64().max(f64::EPSILON);
+    show_dashboard(&progress, options.soups, rate, threads);
+    eprintln!();
+    eprintln!(
+        "{} soups: {} died, {} settled, {} still changing after {} generations",
+        progress.done,
+        progress.died,
+        progress.settled,
+        progress.done - progress.died - progress.settled,
+        options.generations
+    );
+
+    let rule = rule.to_string();
+    for (rank, find) in progress.best.iter().enumerate() {
+        let fate = match find.fate {
+            Fate::Died => format!("died after {} generations", find.lifespan),
+            Fate::Settled { period } => format!(
+                "settled with period {} after {} generations",
+                period, find.lifespan
+            ),
+            Fate::Unsettled => format!("still changing after {} generations", find.lifespan),
+        };
+        println!(
+            "#{} soup {} of seed {}: {}, peak population {}",
+            rank + 1,
+            find.index,
+            options.seed,
+            fate,
+            find.peak_population
+        );
+        print!(
+            "{}",
+            soup(options.seed, find.index, options.size).to_rle(&rule)
+        );
+        println!();
+    }
+    Ok(())
+}
+
+/// The soup with the given index, the same on every machine for the same seed.
+fn soup(seed: u64, index: u64, (width, height): (usize, usize)) -> Pattern {
+    // A stream of its own per index, so that no soup of one seed turns up again under the next
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    rng.set_stream(index);
+    let mut cells = Vec::new();
+    for y in 0..height {
+        for x in 0..width {
+            if rng.gen_bool(DENSITY) {
+                cells.push((x, y));
+            }
+        }
+    }
+    Pattern {
+        name: Some(format!("Soup {} of seed {}", index, seed)),
+        rule: None,
+        width,
+        height,
+        cells,
+    }
+}
+
+/// Run a soup, centered on an empty board, until it dies, settles or runs out of generations.
+fn run_soup(soup: &Pattern, rule: Rule, index: u64, options: &SoupSearchOptions) -> Find {
+    let (width, height) = options.size;
+    let mut universe = Universe::new(width + 2 * options.margin, height + 2 * options.margin);
+    universe.set_rule(rule);
+    universe.place(soup, options.margin, options.margin);
+    let mut detector = PeriodDetector::new();
+    detector.observe(universe.grid());
+    let mut peak_population = universe.population();
+    for generation in 1..=options.generations {
+        universe.step();
+        let population = universe.population();
+        peak_population = peak_population.max(population);
+        let fate = if population == 0 {
+            Some(Fate::Died)
+        } else {
+            detector
+                .observe(universe.grid())
+                .map(|period| Fate::Settled { period })
+        };
+        if let Some(fate) = fate {
+            return Find {
+                index,
+                fate,
+                lifespan: generation,
+                peak_population,
+            };
+        }
+    }
+    Find {
+        index,
+        fate: Fate::Unsettled,
+        lifespan: options.generations,
+        peak_population,
+    }
+}
+
+/// Overwrite the progress line with the soups done, the rate and the best find.
+fn show_dashboard(progress: &Progress, total: u64, rate: f64, threads: usize) {
+    let best = progress.best.first().map_or(String::new(), |find| {
+        format!(
+            ", longest-lived: soup {} at {} generations",
+            find.index, find.lifespan
+        )
+    });
+    eprint!(
+        "\r{}/{} soups, {:.1} soups/s on {} threads{}\x1b[K",
+        progress.done, total, rate, threads, best
+    );
+    let _ = io::stderr().flush();
+}
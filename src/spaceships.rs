@@ -0,0 +1,231 @@
+use ggez::graphics::{Canvas, Color, DrawMode, DrawParam, Mesh, MeshBuilder};
+use ggez::{Context, GameResult};
+use std::collections::HashSet;
+
+use crate::render::CellSize;
+use crate::rule::Rule;
+
+// One phase of each ship; the remaining phases and orientations are derived by simulation.
+const GLIDER: &[(i32, i32)] = &[(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)];
+const LWSS: &[(i32, i32)] = &[
+    (1, 0),
+    (4, 0),
+    (0, 1),
+    (0, 2),
+    (4, 2),
+    (0, 3),
+    (1, 3),
+    (2, 3),
+    (3, 3),
+];
+// Ships have period 4.
+const PERIOD: usize = 4;
+const GLIDER_COLOR: Color = Color::new(0.2, 1.0, 0.3, 1.0);
+const LWSS_COLOR: Color = Color::new(1.0, 0.6, 0.1, 1.0);
+
+/// Kind of spaceship that is recognized.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Ship {
+    Glider,
+    Lwss,
+}
+
+/// A ship found in the grid, with its bounding box in cells.
+pub struct Detection {
+    pub ship: Ship,
+    pub x: usize,
+    pub y: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+/// One phase of a ship in one orientation.
+struct Template {
+    ship: Ship,
+    width: usize,
+    height: usize,
+    /// Row-major alive flags for the bounding box.
+    mask: Vec<bool>,
+    /// First live cell in row-major order, used to anchor the match.
+    anchor: (usize, usize),
+}
+
+/// Finds isolated gliders and lightweight spaceships by template matching.
+pub struct ShipDetector {
+    templates: Vec<Template>,
+    detections: Vec<Detection>,
+    pub enabled: bool,
+}
+
+impl ShipDetector {
+    /// Build the templates for every phase and orientation of each ship.
+    pub fn new() -> ShipDetector {
+        let mut templates = Vec::new();
+        for (ship, seed) in [(Ship::Glider, GLIDER), (Ship::Lwss, LWSS)] {
+            let mut seen = HashSet::new();
+            let mut phase: HashSet<(i32, i32)> = seed.iter().copied().collect();
+            for _ in 0..PERIOD {
+                for orientation in 0..8 {
+                    let cells = normalize(phase.iter().map(|&c| orient(c, orientation)));
+                    if seen.insert(cells.clone()) {
+                        templates.push(Template::new(ship, &cells));
+                    }
+                }
+                phase = step(&phase, Rule::LIFE);
+            }
+        }
+        ShipDetector {
+            templates,
+            detections: Vec::new(),
+            enabled: false,
+        }
+    }
+
+    /// Search the grid for ships that are not touching any other live cell.
+    pub fn scan(&mut self, grid: &[Vec<bool>]) {
+        self.detections.clear();
+        let height = grid.len();
+        let width = grid.first().map_or(0, Vec::len);
+        let alive = |x: isize, y: isize| {
+            x >= 0
+                && y >= 0
+                && (x as usize) < width
+                && (y as usize) < height
+                && grid[y as usize][x as usize]
+        };
+
+        for (y, row) in grid.iter().enumerate() {
+            for (x, _) in row.iter().enumerate().filter(|(_, &alive)| alive) {
+                for template in &self.templates {
+                    let (ax, ay) = template.anchor;
+                    let (Some(left), Some(top)) = (x.checked_sub(ax), y.checked_sub(ay)) else {
+                        continue;
+                    };
+                    // The ship and the one cell wide ring around it must match exactly
+                    let matches = (-1..=template.height as isize).all(|ty| {
+                        (-1..=template.width as isize).all(|tx| {
+                            alive(left as isize + tx, top as isize + ty) == template.at(tx, ty)
+                        })
+                    });
+                    if matches {
+                        self.detections.push(Detection {
+                            ship: template.ship,
+                            x: left,
+                            y: top,
+                            width: template.width,
+                            height: template.height,
+                        });
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Number of detected ships of a kind.
+    pub fn count(&self, ship: Ship) -> usize {
+        self.detections.iter().filter(|d| d.ship == ship).count()
+    }
+
+    /// Outline every detected ship in the color of its kind.
+    pub fn draw(&self, ctx: &mut Context, canvas: &mut Canvas, cell: CellSize) -> GameResult {
+        if self.detections.is_empty() {
+            return Ok(());
+        }
+        let mut outlines = MeshBuilder::new();
+        for detection in &self.detections {
+            let color = match detection.ship {
+                Ship::Glider => GLIDER_COLOR,
+                Ship::Lwss => LWSS_COLOR,
+            };
+            let rect = cell.rect(
+                detection.x as f32 - 0.5,
+                detection.y as f32 - 0.5,
+                detection.width as f32 + 1.0,
+                detection.height as f32 + 1.0,
+            );
+            outlines.rectangle(DrawMode::stroke(2.0), rect, color)?;
+        }
+        canvas.draw(
+            &Mesh::from_data(ctx, outlines.build()),
+            DrawParam::default(),
+        );
+        Ok(())
+    }
+}
+
+impl Template {
+    fn new(ship: Ship, cells: &[(i32, i32)]) -> Template {
+        let width = cells.iter().map(|&(x, _)| x).max().unwrap_or(0) as usize + 1;
+        let height = cells.iter().map(|&(_, y)| y).max().unwrap_or(0) as usize + 1;
+        let mut mask = vec![false; width * height];
+        for &(x, y) in cells {
+            mask[y as usize * width + x as usize] = true;
+        }
+        let first = mask.iter().position(|&alive| alive).unwrap_or(0);
+        Template {
+            ship,
+            width,
+            height,
+            mask,
+            anchor: (first % width, first / width),
+        }
+    }
+
+    /// Whether the template has a live cell here; everything outside the bounding box is dead.
+    fn at(&self, x: isize, y: isize) -> bool {
+        x >= 0
+            && y >= 0
+            && (x as usize) < self.width
+            && (y as usize) < self.height
+            && self.mask[y as usize * self.width + x as usize]
+    }
+}
+
+/// Apply one of the eight rotations and reflections of the square.
+pub fn orient((x, y): (i32, i32), orientation: u8) -> (i32, i32) {
+    let (x, y) = if orientation & 4 != 0 { (y, x) } else { (x, y) };
+    let x = if orientation & 1 != 0 { -x } else { x };
+    let y = if orientation & 2 != 0 { -y } else { y };
+    (x, y)
+}
+
+/// Move the cells so that their bounding box starts at the origin, in a canonical order.
+pub fn normalize(cells: impl Iterator<Item = (i32, i32)>) -> Vec<(i32, i32)> {
+    let mut cells: Vec<(i32, i32)> = cells.collect();
+    let left = cells.iter().map(|&(x, _)| x).min().unwrap_or(0);
+    let top = cells.iter().map(|&(_, y)| y).min().unwrap_or(0);
+    for cell in &mut cells {
+        *cell = (cell.0 - left, cell.1 - top);
+    }
+    cells.sort_unstable();
+    cells
+}
+
+/// Advance a small set of cells on an unbounded plane by one generation.
+///
+/// Births on 0 neighbors are ignored, they would fill the whole plane.
+pub fn step(cells: &HashSet<(i32, i32)>, rule: Rule) -> HashSet<(i32, i32)> {
+    let neighbors = |(x, y): (i32, i32)| {
+        (-1..=1)
+            .flat_map(move |dy| (-1..=1).map(move |dx| (x + dx, y + dy)))
+            .filter(move |&c| c != (x, y))
+    };
+    // Live cells without live neighbors survive under rules with S0
+    let candidates: HashSet<(i32, i32)> = cells
+        .iter()
+        .flat_map(|&c| neighbors(c))
+        .chain(cells.iter().copied())
+        .collect();
+    candidates
+        .into_iter()
+        .filter(|&c| {
+            let count = neighbors(c).filter(|n| cells.contains(n)).count();
+            if cells.contains(&c) {
+                rule.survives(count)
+            } else {
+                rule.born(count)
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,162 @@
+use ggez::graphics::{Canvas, Color};
+use ggez::Context;
+use std::collections::VecDeque;
+
+use crate::bitgrid::BitGrid;
+use crate::render::{shade, QuadBatch};
+
+/// Number of past generations kept for the space-time view.
+pub const HISTORY_DEPTH: usize = 32;
+// Screen offset of every older generation along the time axis.
+const LAYER_OFFSET_X: f32 = 6.0;
+const LAYER_OFFSET_Y: f32 = 4.0;
+// Colors of the newest and the oldest generation.
+const NEW_COLOR: [f32; 3] = [1.0, 1.0, 1.0];
+const OLD_COLOR: [f32; 3] = [0.1, 0.2, 0.8];
+
+/// Ring buffer of past generations, newest first, kept packed.
+pub struct SpaceTime {
+    layers: VecDeque<BitGrid>,
+}
+
+impl SpaceTime {
+    /// Create an empty history.
+    pub fn new() -> SpaceTime {
+        SpaceTime {
+            layers: VecDeque::with_capacity(HISTORY_DEPTH),
+        }
+    }
+
+    /// Remember a generation that is about to be replaced by the next one.
+    pub fn push(&mut self, grid: &BitGrid) {
+        if self.layers.len() == HISTORY_DEPTH {
+            // Reuse the oldest layer instead of allocating a new one
+            let mut oldest = self.layers.pop_back().unwrap();
+            oldest.copy_from(grid);
+            self.layers.push_front(oldest);
+        } else {
+            self.layers.push_front(grid.clone());
+        }
+    }
+
+    /// The recorded generations, oldest first.
+    pub fn layers(&self) -> impl Iterator<Item = &BitGrid> {
+        self.layers.iter().rev()
+    }
+
+    /// Approximate number of bytes held by the recorded generations.
+    pub fn memory_usage(&self) -> usize {
+        self.layers.iter().map(BitGrid::memory_usage).sum()
+    }
+
+    /// Forget all recorded generations.
+    pub fn clear(&mut self) {
+        self.layers.clear();
+    }
+
+    /// Draw the current grid and its history as cubes stacked along the time axis.
+    ///
+    /// Older generations recede towards the top right corner, so a still life
+    /// becomes a solid column and a glider leaves a diagonal world-line.
+    pub fn draw(&self, ctx: &mut Context, canvas: &mut Canvas, grid: &BitGrid) {
+        let depth = HISTORY_DEPTH as f32;
+        let (window_width, window_height) = ctx.gfx.drawable_size();
+        let (width, height) = (grid.width(), grid.height());
+        // Shrink the cubes so that the whole extruded volume fits in the window
+        let size = ((window_width - depth * LAYER_OFFSET_X) / width as f32)
+            .min((window_height - depth * LAYER_OFFSET_Y) / height as f32);
+        let origin_y = depth * LAYER_OFFSET_Y;
+
+        let mut batch = QuadBatch::new();
+
+        // Paint from the oldest generation to the newest so nearer cubes cover farther ones
+        for t in (0..=self.layers.len()).rev() {
+            let layer = if t == 0 { grid } else { &self.layers[t - 1] };
+            let newer = match t {
+                0 => None,
+                1 => Some(grid),
+                _ => Some(&self.layers[t - 2]),
+            };
+            let color = layer_color(t as f32 / depth);
+            let layer_x = t as f32 * LAYER_OFFSET_X;
+            let layer_y = origin_y - t as f32 * LAYER_OFFSET_Y;
+
+            for y in (0..height).rev() {
+                for x in 0..width {
+                    if !layer.get(x, y) {
+                        continue;
+                    }
+                    let px = layer_x + x as f32 * size;
+                    let py = layer_y + y as f32 * size;
+                    let (ox, oy) = (LAYER_OFFSET_X, LAYER_OFFSET_Y);
+
+                    // Faces shared with a neighboring cube can never be seen
+                    if y == 0 || !layer.get(x, y - 1) {
+                        let top = [
+                            [px, py],
+                            [px + ox, py - oy],
+                            [px + size + ox, py - oy],
+                            [px + size, py],
+                        ];
+                        batch.push(top, shade(color, 1.0));
+                    }
+                    if x + 1 == width || !layer.get(x + 1, y) {
+                        let right = [
+                            [px + size, py],
+                            [px + size + ox, py - oy],
+                            [px + size + ox, py + size - oy],
+                            [px + size, py + size],
+                        ];
+                        batch.push(right, shade(color, 0.55));
+                    }
+                    if !newer.is_some_and(|n| n.get(x, y)) {
+                        let front = [
+                            [px, py],
+                            [px + size, py],
+                            [px + size, py + size],
+                            [px, py + size],
+                        ];
+                        batch.push(front, shade(color, 0.8));
+                    }
+                }
+            }
+        }
+
+        batch.draw(ctx, canvas);
+    }
+}
+
+/// Blend between the newest and oldest colors; `age` runs from 0.0 to 1.0.
+fn layer_color(age: f32) -> Color {
+    let lerp = |i: usize| NEW_COLOR[i] + (OLD_COLOR[i] - NEW_COLOR[i]) * age;
+    Color::new(lerp(0), lerp(1), lerp(2), 1.0 - 0.6 * age)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use super::{SpaceTime, HISTORY_DEPTH};
+    use crate::universe::Universe;
+
+    #[test]
+    fn history_keeps_the_latest_generations_oldest_first() {
+        let mut rng = ChaCha8Rng::seed_from_u64(3);
+        let mut universe = Universe::new(70, 40);
+        universe.fill_random(0.4, &mut rng);
+        let mut spacetime = SpaceTime::new();
+        let mut pushed = Vec::new();
+        // Past the depth, so the oldest layers get reused
+        for _ in 0..HISTORY_DEPTH + 10 {
+            spacetime.push(universe.grid());
+            pushed.push(universe.grid().clone());
+            universe.advance(1);
+        }
+        let layers: Vec<_> = spacetime.layers().cloned().collect();
+        assert_eq!(layers.len(), HISTORY_DEPTH);
+        assert!(layers == pushed[pushed.len() - HISTORY_DEPTH..]);
+        spacetime.clear();
+        assert_eq!(spacetime.layers().count(), 0);
+    }
+}
@@ -0,0 +1,202 @@
+use ggez::graphics::{Canvas, Color, DrawMode, DrawParam, Mesh, MeshBuilder, Rect, Text};
+use ggez::input::keyboard::KeyCode;
+use ggez::{Context, GameResult};
+use rand::Rng;
+
+use crate::locale::{tr, Message};
+use crate::pattern::Pattern;
+use crate::render::CellSize;
+
+const PADDING: f32 = 12.0;
+const PREVIEW_COLOR: Color = Color::new(0.4, 0.8, 1.0, 0.5);
+/// Copies scattered when the prompt is confirmed as it opens.
+pub const DEFAULT_SCATTER: usize = 20;
+// Random spots tried for each scattered copy before giving up on it.
+const SCATTER_ATTEMPTS: usize = 200;
+// Dead cells kept around every scattered copy, so that none start out touching.
+const SCATTER_GAP: usize = 2;
+
+/// A classic pattern that can be stamped onto the grid.
+pub struct Stamp {
+    pub name: &'static str,
+    rle: &'static str,
+}
+
+/// The stamps, picked with the number keys 1 to 9.
+pub const STAMPS: [Stamp; 9] = [
+    Stamp {
+        name: "Glider",
+        rle: include_str!("../resources/patterns/glider.rle"),
+    },
+    Stamp {
+        name: "LWSS",
+        rle: "x = 5, y = 4\nbo2bo$o4b$o3bo$4o!",
+    },
+    Stamp {
+        name: "Beacon",
+        rle: "x = 4, y = 4\n2o2b$2o2b$2b2o$2b2o!",
+    },
+    Stamp {
+        name: "Pulsar",
+        rle: include_str!("../resources/patterns/pulsar.rle"),
+    },
+    Stamp {
+        name: "Pentadecathlon",
+        rle: "x = 10, y = 3\n2bo4bo2b$2ob4ob2o$2bo4bo2b!",
+    },
+    Stamp {
+        name: "Gosper glider gun",
+        rle: include_str!("../resources/patterns/gosper_gun.rle"),
+    },
+    Stamp {
+        name: "R-pentomino",
+        rle: include_str!("../resources/patterns/r-pentomino.rle"),
+    },
+    Stamp {
+        name: "Acorn",
+        rle: include_str!("../resources/patterns/acorn.rle"),
+    },
+    Stamp {
+        name: "Diehard",
+        rle: "x = 8, y = 3\n6bob$2o6b$bo3b3o!",
+    },
+];
+
+impl Stamp {
+    pub fn pattern(&self) -> Result<Pattern, String> {
+        let mut pattern = Pattern::parse(self.rle)?;
+        pattern.name = Some(self.name.to_string());
+        Ok(pattern)
+    }
+}
+
+/// The stamp a number key picks.
+pub fn index_for(key: Option<KeyCode>) -> Option<usize> {
+    match key? {
+        KeyCode::Key1 | KeyCode::Numpad1 => Some(0),
+        KeyCode::Key2 | KeyCode::Numpad2 => Some(1),
+        KeyCode::Key3 | KeyCode::Numpad3 => Some(2),
+        KeyCode::Key4 | KeyCode::Numpad4 => Some(3),
+        KeyCode::Key5 | KeyCode::Numpad5 => Some(4),
+        KeyCode::Key6 | KeyCode::Numpad6 => Some(5),
+        KeyCode::Key7 | KeyCode::Numpad7 => Some(6),
+        KeyCode::Key8 | KeyCode::Numpad8 => Some(7),
+        KeyCode::Key9 | KeyCode::Numpad9 => Some(8),
+        _ => None,
+    }
+}
+
+/// Top left corner at which a stamp ends up centered on the cell under the cursor.
+pub fn top_left(pattern: &Pattern, (x, y): (usize, usize)) -> (usize, usize) {
+    (
+        x.saturating_sub(pattern.width / 2),
+        y.saturating_sub(pattern.height / 2),
+    )
+}
+
+/// Where to put up to `count` copies of a pattern, each turned and mirrored at
+/// random, as `(copy, left, top)`: with a gap around it, a copy's bounding box
+/// overlaps neither the other copies nor any live cell. Fewer come back when
+/// there is no room left for them.
+pub fn scatter(
+    pattern: &Pattern,
+    cells: &[Vec<bool>],
+    count: usize,
+    rng: &mut impl Rng,
+) -> Vec<(Pattern, usize, usize)> {
+    let (width, height) = (cells.first().map_or(0, Vec::len), cells.len());
+    let mut taken = cells.to_vec();
+    let mut copies = Vec::new();
+    for _ in 0..count {
+        let mut copy = pattern.clone();
+        for _ in 0..rng.gen_range(0..4) {
+            copy = copy.rotated();
+        }
+        if rng.gen() {
+            copy = copy.flipped();
+        }
+        if copy.width > width || copy.height > height {
+            continue;
+        }
+        let spot = (0..SCATTER_ATTEMPTS)
+            .map(|_| {
+                (
+                    rng.gen_range(0..=width - copy.width),
+                    rng.gen_range(0..=height - copy.height),
+                )
+            })
+            .find(|&(left, top)| {
+                let rows =
+                    top.saturating_sub(SCATTER_GAP)..(top + copy.height + SCATTER_GAP).min(height);
+                let columns =
+                    left.saturating_sub(SCATTER_GAP)..(left + copy.width + SCATTER_GAP).min(width);
+                !taken[rows]
+                    .iter()
+                    .any(|row| row[columns.clone()].contains(&true))
+            });
+        let Some((left, top)) = spot else {
+            continue;
+        };
+        for row in &mut taken[top..top + copy.height] {
+            row[left..left + copy.width].fill(true);
+        }
+        copies.push((copy, left, top));
+    }
+    copies
+}
+
+pub fn draw_menu(ctx: &mut Context, canvas: &mut Canvas) -> GameResult {
+    let mut lines = vec![tr(Message::StampsTitle).to_string()];
+    for (i, stamp) in STAMPS.iter().enumerate() {
+        lines.push(format!("{}: {}", i + 1, stamp.name));
+    }
+    lines.push(tr(Message::StampsHelp).to_string());
+    lines.push(tr(Message::SeedingCancel).to_string());
+    let text = Text::new(lines.join("\n"));
+    let size = text.measure(ctx)?;
+    let (window_width, window_height) = ctx.gfx.drawable_size();
+    let panel = Rect::new(
+        (window_width - size.x) / 2.0 - PADDING,
+        (window_height - size.y) / 2.0 - PADDING,
+        size.x + 2.0 * PADDING,
+        size.y + 2.0 * PADDING,
+    );
+    let backdrop = Mesh::new_rectangle(
+        ctx,
+        DrawMode::fill(),
+        panel,
+        Color::new(0.1, 0.1, 0.1, 0.95),
+    )?;
+    let border = Mesh::new_rectangle(ctx, DrawMode::stroke(1.0), panel, Color::WHITE)?;
+    canvas.draw(&backdrop, DrawParam::default());
+    canvas.draw(&border, DrawParam::default());
+    canvas.draw(
+        &text,
+        DrawParam::default().dest([panel.x + PADDING, panel.y + PADDING]),
+    );
+    Ok(())
+}
+
+/// Show translucently where a stamp with its top left corner at `(left, top)` would go.
+pub fn draw_preview(
+    ctx: &mut Context,
+    canvas: &mut Canvas,
+    cell: CellSize,
+    pattern: &Pattern,
+    (left, top): (usize, usize),
+) -> GameResult {
+    // A mesh without any vertices cannot be built
+    if pattern.cells.is_empty() {
+        return Ok(());
+    }
+    let mut builder = MeshBuilder::new();
+    for &(x, y) in &pattern.cells {
+        builder.rectangle(
+            DrawMode::fill(),
+            cell.rect((left + x) as f32, (top + y) as f32, 1.0, 1.0),
+            PREVIEW_COLOR,
+        )?;
+    }
+    canvas.draw(&Mesh::from_data(ctx, builder.build()), DrawParam::default());
+    Ok(())
+}
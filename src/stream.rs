@@ -0,0 +1,194 @@
+use std::io::{self, Read, Write};
+use std::net::{Shutdown, TcpListener, TcpStream};
+use std::sync::mpsc::{self, Receiver, SyncSender, TryRecvError, TrySendError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use tracing::{debug, info};
+
+use crate::bitgrid::BitGrid;
+use crate::delta::{self, Decoder, Encoder};
+
+// Frames waiting to be written to a spectator; one that falls further behind
+// is dropped, rather than holding up the simulation.
+const QUEUE_FRAMES: usize = 64;
+// Largest frame a spectator reads, a keyframe of a checkerboard of the largest
+// universe with room to spare.
+const MAX_FRAME: usize = 1 << 24;
+
+/// Streams every generation to the spectators connected over TCP, as frames
+/// of the delta protocol, each preceded by its length as a 32-bit big-endian
+/// number. Spectators joining get a keyframe first.
+pub struct StreamServer {
+    encoder: Encoder,
+    spectators: Vec<Connection>,
+    /// Spectators accepted since the last generation, waiting for a keyframe.
+    joining: Arc<Mutex<Vec<Connection>>>,
+}
+
+impl StreamServer {
+    /// Listen on an address such as `0.0.0.0:7878`, accepting on a background thread.
+    pub fn start(address: &str, keyframe_interval: u64) -> io::Result<StreamServer> {
+        let listener = TcpListener::bind(address)?;
+        info!("streaming generations at {}", listener.local_addr()?);
+        let joining = Arc::new(Mutex::new(Vec::new()));
+        let shared = Arc::clone(&joining);
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let _ = stream.set_nodelay(true);
+                let connection = match Connection::open(stream) {
+                    Ok(connection) => connection,
+                    Err(e) => {
+                        debug!("cannot accept a spectator: {}", e);
+                        continue;
+                    }
+                };
+                shared
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .push(connection);
+            }
+        });
+        Ok(StreamServer {
+            encoder: Encoder::new(keyframe_interval),
+            spectators: Vec::new(),
+            joining,
+        })
+    }
+
+    /// Send a generation to every spectator, dropping those that cannot keep up.
+    pub fn send_generation(&mut self, cells: &BitGrid, generation: u64) {
+        let joined = std::mem::take(&mut *self.joining.lock().unwrap_or_else(|e| e.into_inner()));
+        if self.spectators.is_empty() && joined.is_empty() {
+            // Nobody to take a delta, so the next one watching starts afresh
+            self.encoder.forget();
+            return;
+        }
+        let encoded = self.encoder.encode(cells, generation);
+        let frame = message(&encoded);
+        self.spectators.retain(|spectator| spectator.send(&frame));
+        if joined.is_empty() {
+            return;
+        }
+        let keyframe = if delta::is_keyframe(&encoded) {
+            frame
+        } else {
+            message(&delta::keyframe(cells, generation))
+        };
+        for spectator in joined {
+            if spectator.send(&keyframe) {
+                self.spectators.push(spectator);
+            }
+        }
+    }
+}
+
+/// A frame preceded by its length, shared by the queues of every spectator.
+fn message(frame: &[u8]) -> Arc<[u8]> {
+    let mut message = Vec::with_capacity(frame.len() + 4);
+    message.extend_from_slice(&(frame.len() as u32).to_be_bytes());
+    message.extend_from_slice(frame);
+    message.into()
+}
+
+/// A spectator, whose frames are written by a thread of its own so that a slow
+/// one never holds up the others or the simulation.
+struct Connection {
+    frames: SyncSender<Arc<[u8]>>,
+    /// The same socket, to shut down a spectator that fell behind, even in the
+    /// middle of a write.
+    stream: TcpStream,
+}
+
+impl Connection {
+    fn open(stream: TcpStream) -> io::Result<Connection> {
+        let mut writer = stream.try_clone()?;
+        let (frames, queue) = mpsc::sync_channel::<Arc<[u8]>>(QUEUE_FRAMES);
+        thread::spawn(move || {
+            for message in queue {
+                if let Err(e) = writer.write_all(&message) {
+                    info!(peer = ?writer.peer_addr().ok(), "a spectator left: {}", e);
+                    return;
+                }
+            }
+        });
+        info!(peer = ?stream.peer_addr().ok(), "a spectator joined");
+        Ok(Connection { frames, stream })
+    }
+
+    /// Queue a frame, returning whether the spectator is still there.
+    fn send(&self, message: &Arc<[u8]>) -> bool {
+        match self.frames.try_send(Arc::clone(message)) {
+            Ok(()) => true,
+            Err(TrySendError::Full(_)) => {
+                info!(peer = ?self.stream.peer_addr().ok(), "a spectator fell behind");
+                let _ = self.stream.shutdown(Shutdown::Both);
+                false
+            }
+            Err(TrySendError::Disconnected(_)) => false,
+        }
+    }
+}
+
+/// The cells and number of a generation received, or why the stream ended.
+type Received = Result<(Vec<Vec<bool>>, u64), String>;
+
+/// Follows the generations another krida streams, decoding them on a
+/// background thread.
+pub struct Spectator {
+    received: Receiver<Received>,
+}
+
+impl Spectator {
+    /// Connect to a `StreamServer` at an address such as `192.168.1.20:7878`.
+    pub fn connect(address: &str) -> io::Result<Spectator> {
+        let mut stream = TcpStream::connect(address)?;
+        info!("spectating the generations streamed at {}", address);
+        let (sender, received) = mpsc::channel();
+        thread::spawn(move || {
+            let mut decoder = Decoder::default();
+            loop {
+                let result = receive(&mut stream)
+                    .map_err(|e| e.to_string())
+                    .and_then(|frame| decoder.apply(&frame))
+                    .map(|()| (decoder.cells().to_vec(), decoder.generation()));
+                let failed = result.is_err();
+                if sender.send(result).is_err() || failed {
+                    return;
+                }
+            }
+        });
+        Ok(Spectator { received })
+    }
+
+    /// The latest generation received since the last call, skipping the ones
+    /// in between, or why the stream ended.
+    pub fn poll(&mut self) -> Option<Received> {
+        let mut latest = None;
+        loop {
+            match self.received.try_recv() {
+                Ok(Ok(generation)) => latest = Some(Ok(generation)),
+                Ok(Err(e)) => return Some(Err(e)),
+                Err(TryRecvError::Empty) => return latest,
+                Err(TryRecvError::Disconnected) => {
+                    return latest.or_else(|| Some(Err("the stream ended".to_string())))
+                }
+            }
+        }
+    }
+}
+
+/// Read the next frame with its length.
+fn receive(stream: &mut TcpStream) -> io::Result<Vec<u8>> {
+    let mut length = [0u8; 4];
+    stream.read_exact(&mut length)?;
+    let length = u32::from_be_bytes(length) as usize;
+    if length > MAX_FRAME {
+        return Err(io::Error::other(format!(
+            "a frame of {} bytes is too large",
+            length
+        )));
+    }
+    let mut frame = vec![0u8; length];
+    stream.read_exact(&mut frame)?;
+    Ok(frame)
+}
@@ -0,0 +1,48 @@
+use ggez::graphics::Color;
+use serde::{Deserialize, Serialize};
+
+/// Every color theme, in the order of the panel's list.
+pub const THEMES: [Theme; 4] = [Theme::Classic, Theme::Phosphor, Theme::Amber, Theme::Ice];
+
+/// Colors of the live cells and of the window behind them.
+#[derive(Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Theme {
+    /// White cells on black.
+    #[default]
+    Classic,
+    /// Green cells on a dark green, like an old monitor.
+    Phosphor,
+    Amber,
+    Ice,
+}
+
+impl Theme {
+    pub fn name(self) -> &'static str {
+        match self {
+            Theme::Classic => "Classic",
+            Theme::Phosphor => "Phosphor",
+            Theme::Amber => "Amber",
+            Theme::Ice => "Ice",
+        }
+    }
+
+    /// The color of live cells, unless they are colored by age or state.
+    pub fn cells(self) -> Color {
+        match self {
+            Theme::Classic => Color::WHITE,
+            Theme::Phosphor => Color::new(0.3, 1.0, 0.4, 1.0),
+            Theme::Amber => Color::new(1.0, 0.7, 0.2, 1.0),
+            Theme::Ice => Color::new(0.7, 0.9, 1.0, 1.0),
+        }
+    }
+
+    pub fn background(self) -> Color {
+        match self {
+            Theme::Classic => Color::BLACK,
+            Theme::Phosphor => Color::new(0.0, 0.06, 0.02, 1.0),
+            Theme::Amber => Color::new(0.08, 0.04, 0.0, 1.0),
+            Theme::Ice => Color::new(0.02, 0.04, 0.1, 1.0),
+        }
+    }
+}
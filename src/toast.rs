@@ -0,0 +1,67 @@
+use ggez::graphics::{Canvas, Color, DrawMode, DrawParam, Mesh, Rect, Text};
+use ggez::{Context, GameResult};
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+// How long a toast stays up, the last part of it fading out.
+const LIFETIME: Duration = Duration::from_millis(2500);
+const FADE: Duration = Duration::from_millis(500);
+// Toasts shown at once; older ones make way for new ones.
+const MAX_TOASTS: usize = 4;
+const MARGIN: f32 = 8.0;
+const PADDING: f32 = 6.0;
+
+/// Short messages acknowledging what a key did, stacked in the bottom left corner.
+pub struct Toasts {
+    shown: VecDeque<(String, Instant)>,
+}
+
+impl Toasts {
+    pub fn new() -> Toasts {
+        Toasts {
+            shown: VecDeque::new(),
+        }
+    }
+
+    /// Show a message for a couple of seconds.
+    pub fn show(&mut self, message: impl Into<String>) {
+        if self.shown.len() == MAX_TOASTS {
+            self.shown.pop_front();
+        }
+        self.shown.push_back((message.into(), Instant::now()));
+    }
+
+    /// Draw the messages that are still up, the newest at the bottom.
+    pub fn draw(&mut self, ctx: &mut Context, canvas: &mut Canvas) -> GameResult {
+        self.shown.retain(|(_, shown)| shown.elapsed() < LIFETIME);
+        let (_, window_height) = ctx.gfx.drawable_size();
+        let mut bottom = window_height - MARGIN;
+        for (message, shown) in self.shown.iter().rev() {
+            let left = LIFETIME.saturating_sub(shown.elapsed());
+            let alpha = (left.as_secs_f32() / FADE.as_secs_f32()).min(1.0);
+            let text = Text::new(message.as_str());
+            let size = text.measure(ctx)?;
+            let panel = Rect::new(
+                MARGIN,
+                bottom - size.y - 2.0 * PADDING,
+                size.x + 2.0 * PADDING,
+                size.y + 2.0 * PADDING,
+            );
+            let backdrop = Mesh::new_rectangle(
+                ctx,
+                DrawMode::fill(),
+                panel,
+                Color::new(0.0, 0.0, 0.0, 0.75 * alpha),
+            )?;
+            canvas.draw(&backdrop, DrawParam::default());
+            canvas.draw(
+                &text,
+                DrawParam::default()
+                    .dest([panel.x + PADDING, panel.y + PADDING])
+                    .color(Color::new(1.0, 1.0, 1.0, alpha)),
+            );
+            bottom = panel.y - MARGIN / 2.0;
+        }
+        Ok(())
+    }
+}
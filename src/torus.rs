@@ -0,0 +1,140 @@
+use std::collections::{HashSet, VecDeque};
+use std::fs;
+
+use crate::cli::TorusScanOptions;
+use crate::pattern::Pattern;
+use crate::period::{PeriodDetector, MAX_PERIOD};
+use crate::rule::Rule;
+use crate::spaceships;
+use crate::universe::Universe;
+
+/// What became of a pattern.
+enum Fate {
+    /// Every cell died in the given generation.
+    Died(u64),
+    /// The universe first repeated itself in the given generation.
+    Settled { period: usize, generation: u64 },
+    /// The universe did not repeat within the generations that were run.
+    Unsettled,
+}
+
+impl Fate {
+    fn period(&self) -> Option<usize> {
+        match self {
+            Fate::Settled { period, .. } => Some(*period),
+            Fate::Died(_) | Fate::Unsettled => None,
+        }
+    }
+}
+
+/// Run a pattern on every torus and shift of the scan and print a CSV line for each.
+pub fn run(options: &TorusScanOptions) -> Result<(), String> {
+    let text = fs::read_to_string(&options.path)
+        .map_err(|e| format!("cannot read {}: {}", options.path.display(), e))?;
+    let pattern = Pattern::parse(&text)?;
+    let ((min_width, min_height), (max_width, max_height)) = options.sizes;
+    let (min_shift, max_shift) = options.shifts;
+    if min_width > max_width || min_height > max_height || min_shift > max_shift {
+        return Err("ranges have to go from the smaller to the larger value".to_string());
+    }
+
+    let plane_fate = simulate_plane(&pattern, options.generations);
+
+    println!("width,height,shift,fate,generation,period,population,period_changed");
+    let (mut tori, mut survived, mut changed) = (0, 0, 0);
+    // Tori smaller than the pattern cannot hold it
+    for height in min_height.max(pattern.height)..=max_height {
+        for width in min_width.max(pattern.width)..=max_width {
+            for shift in min_shift..=max_shift {
+                let mut universe = Universe::new(width, height);
+                universe.set_toroidal(true);
+                universe.set_wrap_shift(shift);
+                let (fate, population) = simulate(&pattern, &mut universe, options.generations);
+                let (name, generation) = match fate {
+                    Fate::Died(generation) => ("died", generation.to_string()),
+                    Fate::Settled { generation, .. } => ("settled", generation.to_string()),
+                    Fate::Unsettled => ("unsettled", String::new()),
+                };
+                let period_changed = fate.period() != plane_fate.period();
+                println!(
+                    "{},{},{},{},{},{},{},{}",
+                    width,
+                    height,
+                    shift,
+                    name,
+                    generation,
+                    fate.period().map(|p| p.to_string()).unwrap_or_default(),
+                    population,
+                    period_changed
+                );
+                tori += 1;
+                survived += usize::from(!matches!(fate, Fate::Died(_)));
+                changed += usize::from(period_changed);
+            }
+        }
+    }
+    eprintln!(
+        "{} tori: the pattern survived on {} and changed its period on {} (on an open plane: {})",
+        tori,
+        survived,
+        changed,
+        plane_fate
+            .period()
+            .map_or("no period".to_string(), |p| format!("period {}", p))
+    );
+    Ok(())
+}
+
+/// Run the pattern, centered, until it dies or repeats, returning its fate and final population.
+fn simulate(pattern: &Pattern, universe: &mut Universe, generations: u64) -> (Fate, usize) {
+    let (left, top) = universe.centered(pattern);
+    universe.place(pattern, left, top);
+    let mut detector = PeriodDetector::new();
+    detector.observe(universe.grid());
+    for generation in 1..=generations {
+        universe.step();
+        let population = universe.population();
+        if population == 0 {
+            return (Fate::Died(generation), 0);
+        }
+        if let Some(period) = detector.observe(universe.grid()) {
+            return (Fate::Settled { period, generation }, population);
+        }
+    }
+    (Fate::Unsettled, universe.population())
+}
+
+/// Run the pattern on an unbounded plane, where nothing interferes with it.
+fn simulate_plane(pattern: &Pattern, generations: u64) -> Fate {
+    let mut cells: HashSet<(i32, i32)> = pattern
+        .cells
+        .iter()
+        .map(|&(x, y)| (x as i32, y as i32))
+        .collect();
+    // The most recent generations, newest first, in a canonical order
+    let mut recent: VecDeque<Vec<(i32, i32)>> = VecDeque::with_capacity(MAX_PERIOD);
+    let sorted = |cells: &HashSet<(i32, i32)>| {
+        let mut cells: Vec<(i32, i32)> = cells.iter().copied().collect();
+        cells.sort_unstable();
+        cells
+    };
+    recent.push_front(sorted(&cells));
+    for generation in 1..=generations {
+        cells = spaceships::step(&cells, Rule::LIFE);
+        if cells.is_empty() {
+            return Fate::Died(generation);
+        }
+        let current = sorted(&cells);
+        if let Some(i) = recent.iter().position(|earlier| *earlier == current) {
+            return Fate::Settled {
+                period: i + 1,
+                generation,
+            };
+        }
+        if recent.len() == MAX_PERIOD {
+            recent.pop_back();
+        }
+        recent.push_front(current);
+    }
+    Fate::Unsettled
+}
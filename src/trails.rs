@@ -0,0 +1,119 @@
+use ggez::graphics::{Canvas, Color, DrawMode, DrawParam, Mesh, MeshBuilder};
+use ggez::{Context, GameResult};
+use std::f32::consts::TAU;
+
+use crate::render::CellSize;
+
+// Generations a birth stays visible, fading out.
+const TRAIL_LENGTH: u8 = 8;
+
+/// Newborn cells colored by the direction they were born in, fading over a few
+/// generations so that fronts leave trails.
+///
+/// The direction points from the centroid of the live neighbors that gave
+/// birth to a cell towards the cell, and picks the hue: red for rightwards,
+/// chartreuse for downwards, cyan for leftwards and violet for upwards.
+pub struct BirthTrails {
+    pub enabled: bool,
+    /// Hue as a share of a full turn and generations since the birth, per cell.
+    trails: Vec<Vec<Option<(f32, u8)>>>,
+}
+
+impl BirthTrails {
+    pub fn new() -> BirthTrails {
+        BirthTrails {
+            enabled: false,
+            trails: Vec::new(),
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.trails.clear();
+    }
+
+    /// Age the trails and add the births between two generations.
+    pub fn observe(&mut self, previous: &[Vec<bool>], current: &[Vec<bool>]) {
+        let (width, height) = (current.first().map_or(0, Vec::len), current.len());
+        if self.trails.len() != height || self.trails.first().map_or(0, Vec::len) != width {
+            self.trails = vec![vec![None; width]; height];
+        }
+        for (y, row) in current.iter().enumerate() {
+            for (x, &alive) in row.iter().enumerate() {
+                let trail = &mut self.trails[y][x];
+                if alive && !previous[y][x] {
+                    *trail = parent_direction(previous, x, y).map(|hue| (hue, 0));
+                } else if let Some((_, age)) = trail {
+                    *age += 1;
+                    if *age >= TRAIL_LENGTH {
+                        *trail = None;
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn draw(&self, ctx: &mut Context, canvas: &mut Canvas, cell: CellSize) -> GameResult {
+        let mut builder = MeshBuilder::new();
+        let mut empty = true;
+        for (y, row) in self.trails.iter().enumerate() {
+            for (x, trail) in row.iter().enumerate() {
+                if let Some((hue, age)) = *trail {
+                    let mut color = hue_color(hue);
+                    color.a = 1.0 - f32::from(age) / f32::from(TRAIL_LENGTH);
+                    builder.rectangle(
+                        DrawMode::fill(),
+                        cell.rect(x as f32, y as f32, 1.0, 1.0),
+                        color,
+                    )?;
+                    empty = false;
+                }
+            }
+        }
+        // A mesh without any vertices cannot be built
+        if !empty {
+            let mesh = Mesh::from_data(ctx, builder.build());
+            canvas.draw(&mesh, DrawParam::default());
+        }
+        Ok(())
+    }
+}
+
+/// Hue of the direction from the centroid of the live neighbors of `(x, y)` to
+/// the cell, or `None` if the neighbors are balanced around it.
+fn parent_direction(grid: &[Vec<bool>], x: usize, y: usize) -> Option<f32> {
+    let (mut dx, mut dy) = (0i32, 0i32);
+    let top = y.saturating_sub(1);
+    for (ny, row) in grid.iter().enumerate().skip(top).take(y + 2 - top) {
+        let left = x.saturating_sub(1);
+        for (nx, _) in row
+            .iter()
+            .enumerate()
+            .skip(left)
+            .take(x + 2 - left)
+            .filter(|(_, &alive)| alive)
+        {
+            dx += x as i32 - nx as i32;
+            dy += y as i32 - ny as i32;
+        }
+    }
+    if (dx, dy) == (0, 0) {
+        return None;
+    }
+    // Screen coordinates grow downwards, so a quarter turn is straight down
+    Some((dy as f32).atan2(dx as f32).rem_euclid(TAU) / TAU)
+}
+
+/// Fully saturated color of a hue given as a share of a full turn.
+fn hue_color(hue: f32) -> Color {
+    let sector = hue * 6.0;
+    let rising = sector.fract();
+    let (r, g, b) = match sector as u32 % 6 {
+        0 => (1.0, rising, 0.0),
+        1 => (1.0 - rising, 1.0, 0.0),
+        2 => (0.0, 1.0, rising),
+        3 => (0.0, 1.0 - rising, 1.0),
+        4 => (rising, 0.0, 1.0),
+        _ => (1.0, 0.0, 1.0 - rising),
+    };
+    Color::new(r, g, b, 1.0)
+}
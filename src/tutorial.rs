@@ -0,0 +1,128 @@
+use ggez::graphics::{Canvas, Color, DrawMode, DrawParam, Mesh, Rect, Text};
+use ggez::{Context, GameResult};
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use tracing::error;
+
+use crate::config;
+use crate::locale::{tr, Message};
+
+// How long the closing message stays up.
+const FAREWELL_DURATION: Duration = Duration::from_secs(5);
+// Space between the prompt and the bottom of the window.
+const MARGIN: f32 = 24.0;
+const PADDING: f32 = 10.0;
+// Lessons in order: the prompt and the input that completes it, and how often.
+const LESSONS: &[(Message, Action, u32)] = &[
+    (Message::TutorialPlace, Action::ToggleCell, 3),
+    (Message::TutorialResume, Action::Resume, 1),
+    (Message::TutorialPause, Action::Pause, 1),
+    (Message::TutorialStep, Action::Step, 3),
+    (Message::TutorialGun, Action::LoadGun, 1),
+    (Message::TutorialWatch, Action::Resume, 1),
+];
+
+/// Inputs the tutorial waits for.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Action {
+    ToggleCell,
+    Resume,
+    Pause,
+    Step,
+    LoadGun,
+}
+
+/// Overlay prompts that walk a new player through the basic controls.
+pub struct Tutorial {
+    lesson: usize,
+    progress: u32,
+    finished_at: Option<Instant>,
+}
+
+impl Tutorial {
+    /// Start from the first lesson.
+    pub fn new() -> Tutorial {
+        Tutorial {
+            lesson: 0,
+            progress: 0,
+            finished_at: None,
+        }
+    }
+
+    /// Whether the tutorial has never been completed or skipped on this machine.
+    pub fn is_first_run() -> bool {
+        marker_path().is_some_and(|path| !path.exists())
+    }
+
+    /// Remember that the tutorial has been seen so it does not start again.
+    pub fn mark_seen() {
+        let Some(path) = marker_path() else {
+            return;
+        };
+        let written = path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| fs::write(&path, ""));
+        if let Err(e) = written {
+            error!("cannot write {}: {}", path.display(), e);
+        }
+    }
+
+    /// Move on to the next lesson once the player has done what the current one asks.
+    pub fn observe(&mut self, action: Action) {
+        let Some(&(_, expected, times)) = LESSONS.get(self.lesson) else {
+            return;
+        };
+        if action != expected {
+            return;
+        }
+        self.progress += 1;
+        if self.progress == times {
+            self.lesson += 1;
+            self.progress = 0;
+            if self.lesson == LESSONS.len() {
+                self.finished_at = Some(Instant::now());
+            }
+        }
+    }
+
+    /// Whether the closing message has been shown long enough.
+    pub fn is_over(&self) -> bool {
+        self.finished_at
+            .is_some_and(|at| at.elapsed() > FAREWELL_DURATION)
+    }
+
+    /// Draw the current prompt centered at the bottom of the window.
+    pub fn draw(&self, ctx: &mut Context, canvas: &mut Canvas) -> GameResult {
+        let prompt = match LESSONS.get(self.lesson) {
+            Some(&(message, _, times)) if times > 1 => {
+                format!("{} ({}/{})", tr(message), self.progress, times)
+            }
+            Some(&(message, _, _)) => tr(message).to_string(),
+            None => tr(Message::TutorialDone).to_string(),
+        };
+        let text = Text::new(format!("{}\n{}", prompt, tr(Message::TutorialSkip)));
+        let size = text.measure(ctx)?;
+        let (window_width, window_height) = ctx.gfx.drawable_size();
+        let panel = Rect::new(
+            (window_width - size.x) / 2.0 - PADDING,
+            window_height - MARGIN - size.y - 2.0 * PADDING,
+            size.x + 2.0 * PADDING,
+            size.y + 2.0 * PADDING,
+        );
+        let backdrop =
+            Mesh::new_rectangle(ctx, DrawMode::fill(), panel, Color::new(0.0, 0.0, 0.3, 0.8))?;
+        canvas.draw(&backdrop, DrawParam::default());
+        canvas.draw(
+            &text,
+            DrawParam::default().dest([panel.x + PADDING, panel.y + PADDING]),
+        );
+        Ok(())
+    }
+}
+
+// An empty file whose presence means the tutorial has been seen.
+fn marker_path() -> Option<PathBuf> {
+    config::project_dirs().map(|dirs| dirs.data_dir().join("tutorial-seen"))
+}
@@ -0,0 +1,632 @@
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::cell::OnceCell;
+
+use crate::bitgrid::{BitGrid, Edges};
+use crate::pattern::Pattern;
+use crate::perf;
+use crate::rule::Rule;
+
+/// Which part of the universe stays in place when it is resized.
+#[derive(Clone, Copy)]
+pub enum Anchor {
+    Center,
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+impl Anchor {
+    /// Parse a name such as `center` or `top-left`.
+    pub fn from_name(name: &str) -> Option<Anchor> {
+        match name {
+            "center" => Some(Anchor::Center),
+            "top-left" => Some(Anchor::TopLeft),
+            "top-right" => Some(Anchor::TopRight),
+            "bottom-left" => Some(Anchor::BottomLeft),
+            "bottom-right" => Some(Anchor::BottomRight),
+            _ => None,
+        }
+    }
+}
+
+/// A rectangle of cells.
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Region {
+    pub left: usize,
+    pub top: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Region {
+    /// The smallest region holding two opposite corners.
+    pub fn spanning((x1, y1): (usize, usize), (x2, y2): (usize, usize)) -> Region {
+        Region {
+            left: x1.min(x2),
+            top: y1.min(y2),
+            width: x1.abs_diff(x2) + 1,
+            height: y1.abs_diff(y2) + 1,
+        }
+    }
+
+    pub fn contains(&self, x: usize, y: usize) -> bool {
+        (self.left..self.left + self.width).contains(&x)
+            && (self.top..self.top + self.height).contains(&y)
+    }
+}
+
+/// The cells of a finite Game of Life universe and the rules that advance them.
+///
+/// Nothing in here depends on a window, so the same code drives the game and the
+/// headless tools.
+#[derive(Clone)]
+pub struct Universe {
+    width: usize,
+    height: usize,
+    cells: BitGrid,
+    /// The generation before the last step.
+    previous: BitGrid,
+    /// Both unpacked into rows, the first time they are asked for.
+    rows: OnceCell<Vec<Vec<bool>>>,
+    previous_rows: OnceCell<Vec<Vec<bool>>>,
+    generation: u64,
+    /// Whether the edges wrap around, making cells at opposite edges neighbors.
+    toroidal: bool,
+    /// Columns by which the cells across the top and bottom edges of a torus are
+    /// shifted, which turns it into a twisted torus.
+    wrap_shift: usize,
+    rule: Rule,
+    /// Regions whose cells keep their state, but still count as neighbors.
+    frozen: Vec<Region>,
+}
+
+impl Universe {
+    /// Create an empty universe.
+    pub fn new(width: usize, height: usize) -> Universe {
+        Universe {
+            width,
+            height,
+            cells: BitGrid::new(width, height),
+            previous: BitGrid::new(width, height),
+            rows: OnceCell::new(),
+            previous_rows: OnceCell::new(),
+            generation: 0,
+            toroidal: false,
+            wrap_shift: 0,
+            rule: Rule::LIFE,
+            frozen: Vec::new(),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Number of generations computed since the universe was last reset.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Start counting generations from zero again.
+    pub fn reset_generation(&mut self) {
+        self.generation = 0;
+    }
+
+    /// Continue counting from a generation, e.g. that of a reopened workspace.
+    pub fn set_generation(&mut self, generation: u64) {
+        self.generation = generation;
+    }
+
+    pub fn is_toroidal(&self) -> bool {
+        self.toroidal
+    }
+
+    /// Choose between a bounded universe and one whose edges wrap around.
+    pub fn set_toroidal(&mut self, toroidal: bool) {
+        self.toroidal = toroidal;
+    }
+
+    pub fn wrap_shift(&self) -> usize {
+        self.wrap_shift
+    }
+
+    /// Shift the cells across the top and bottom edges of a torus by `shift` columns.
+    pub fn set_wrap_shift(&mut self, shift: usize) {
+        self.wrap_shift = shift;
+    }
+
+    /// The rule `step` applies.
+    pub fn rule(&self) -> Rule {
+        self.rule
+    }
+
+    pub fn set_rule(&mut self, rule: Rule) {
+        self.rule = rule;
+    }
+
+    /// The cells, row by row.
+    pub fn cells(&self) -> &[Vec<bool>] {
+        self.rows.get_or_init(|| self.cells.to_rows())
+    }
+
+    /// The cells packed 64 to a word, for what looks at every generation
+    /// without needing them unpacked.
+    pub fn grid(&self) -> &BitGrid {
+        &self.cells
+    }
+
+    pub fn frozen(&self) -> &[Region] {
+        &self.frozen
+    }
+
+    /// Keep the cells of a region as they are, e.g. as a wall around a reaction.
+    pub fn freeze(&mut self, region: Region) {
+        self.frozen.push(region);
+    }
+
+    /// Let the cells of the most recently frozen region under `(x, y)` change
+    /// again; returns whether there was one.
+    pub fn thaw_at(&mut self, x: usize, y: usize) -> bool {
+        match self.frozen.iter().rposition(|region| region.contains(x, y)) {
+            Some(i) => {
+                self.frozen.remove(i);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Replace every frozen region, e.g. with the ones of a reopened workspace.
+    pub fn set_frozen(&mut self, frozen: Vec<Region>) {
+        self.frozen = frozen;
+    }
+
+    /// The generation before the last call to `step`.
+    pub fn previous(&self) -> &[Vec<bool>] {
+        self.previous_rows.get_or_init(|| self.previous.to_rows())
+    }
+
+    pub fn previous_grid(&self) -> &BitGrid {
+        &self.previous
+    }
+
+    /// Replace the cells with their state `generations` later, as worked out by
+    /// another engine.
+    pub fn jump(&mut self, cells: Vec<Vec<bool>>, generations: u64) {
+        let packed = BitGrid::from_rows(&cells, self.width, self.height);
+        self.previous = std::mem::replace(&mut self.cells, packed);
+        self.previous_rows = std::mem::take(&mut self.rows);
+        if cells.len() == self.height && cells.iter().all(|row| row.len() == self.width) {
+            self.rows = OnceCell::from(cells);
+        }
+        self.generation += generations;
+    }
+
+    /// Set the state of a cell; positions outside the universe are ignored.
+    pub fn set(&mut self, x: usize, y: usize, alive: bool) {
+        if x < self.width && y < self.height {
+            self.cells.set(x, y, alive);
+            // Keep rows already unpacked, rather than unpacking them again per cell
+            if let Some(rows) = self.rows.get_mut() {
+                rows[y][x] = alive;
+            }
+        }
+    }
+
+    /// Toggle the state of a cell at a given position, returning its new state.
+    pub fn toggle(&mut self, x: usize, y: usize) -> bool {
+        if x < self.width && y < self.height {
+            let alive = !self.cells.get(x, y);
+            self.set(x, y, alive);
+            alive
+        } else {
+            false
+        }
+    }
+
+    /// Flip every cell connected to `(x, y)` through cells of the same state, along
+    /// rows and columns and across wrapped edges, filling a dead region or clearing
+    /// a live one.
+    pub fn flood_fill(&mut self, x: usize, y: usize) {
+        if x >= self.width || y >= self.height {
+            return;
+        }
+        let target = self.cells.get(x, y);
+        let mut pending = vec![(x, y)];
+        self.set(x, y, !target);
+        while let Some((x, y)) = pending.pop() {
+            for (dx, dy) in [(-1, 0), (1, 0), (0, -1), (0, 1)] {
+                let Some((nx, ny)) = self.offset(x, y, dx, dy) else {
+                    continue;
+                };
+                if self.cells.get(nx, ny) == target {
+                    self.set(nx, ny, !target);
+                    pending.push((nx, ny));
+                }
+            }
+        }
+    }
+
+    /// Kill every cell.
+    pub fn clear(&mut self) {
+        self.cells.clear();
+        self.rows = OnceCell::new();
+    }
+
+    /// Replace the cells with a grid of any size, keeping only what fits.
+    pub fn load(&mut self, grid: &[Vec<bool>]) {
+        self.cells = BitGrid::from_rows(grid, self.width, self.height);
+        self.rows = OnceCell::new();
+    }
+
+    /// Grow or shrink the universe, keeping the cells at the anchor where they are.
+    ///
+    /// Returns how far the surviving cells moved, to carry along anything tied to them.
+    pub fn resize(&mut self, width: usize, height: usize, anchor: Anchor) -> (isize, isize) {
+        let grow_x = width as isize - self.width as isize;
+        let grow_y = height as isize - self.height as isize;
+        let (dx, dy) = match anchor {
+            Anchor::Center => (grow_x / 2, grow_y / 2),
+            Anchor::TopLeft => (0, 0),
+            Anchor::TopRight => (grow_x, 0),
+            Anchor::BottomLeft => (0, grow_y),
+            Anchor::BottomRight => (grow_x, grow_y),
+        };
+
+        let mut cells = BitGrid::new(width, height);
+        for (x, y) in self.cells.live_cells() {
+            let (nx, ny) = (x as isize + dx, y as isize + dy);
+            if (0..width as isize).contains(&nx) && (0..height as isize).contains(&ny) {
+                cells.set(nx as usize, ny as usize, true);
+            }
+        }
+        self.cells = cells;
+        self.previous = BitGrid::new(width, height);
+        self.rows = OnceCell::new();
+        self.previous_rows = OnceCell::new();
+        self.width = width;
+        self.height = height;
+        // Move the frozen regions along, cutting off whatever no longer fits
+        self.frozen = self
+            .frozen
+            .iter()
+            .filter_map(|region| {
+                let left = (region.left as isize + dx).max(0);
+                let top = (region.top as isize + dy).max(0);
+                let right = (region.left as isize + region.width as isize + dx).min(width as isize);
+                let bottom =
+                    (region.top as isize + region.height as isize + dy).min(height as isize);
+                (left < right && top < bottom).then(|| Region {
+                    left: left as usize,
+                    top: top as usize,
+                    width: (right - left) as usize,
+                    height: (bottom - top) as usize,
+                })
+            })
+            .collect();
+        (dx, dy)
+    }
+
+    /// Bring the cells of a pattern to life with its top left corner at `(left, top)`.
+    pub fn place(&mut self, pattern: &Pattern, left: usize, top: usize) {
+        for &(x, y) in &pattern.cells {
+            // Patterns larger than the universe lose whatever does not fit
+            self.set(left + x, top + y, true);
+        }
+    }
+
+    /// Copy the cells of a region into a pattern the size of the region.
+    pub fn copy_region(&self, region: Region) -> Pattern {
+        let mut pattern = Pattern {
+            width: region.width,
+            height: region.height,
+            ..Pattern::default()
+        };
+        for y in region.top..(region.top + region.height).min(self.height) {
+            for x in region.left..(region.left + region.width).min(self.width) {
+                if self.cells.get(x, y) {
+                    pattern.cells.push((x - region.left, y - region.top));
+                }
+            }
+        }
+        pattern
+    }
+
+    /// Kill every cell of a region.
+    pub fn clear_region(&mut self, region: Region) {
+        for y in region.top..(region.top + region.height).min(self.height) {
+            for x in region.left..(region.left + region.width).min(self.width) {
+                self.set(x, y, false);
+            }
+        }
+    }
+
+    /// Top left corner at which a pattern ends up centered.
+    pub fn centered(&self, pattern: &Pattern) -> (usize, usize) {
+        (
+            self.width.saturating_sub(pattern.width) / 2,
+            self.height.saturating_sub(pattern.height) / 2,
+        )
+    }
+
+    /// Give every cell the same chance of being alive.
+    pub fn fill_random(&mut self, density: f64, rng: &mut impl Rng) {
+        self.fill_random_with(|_, _| density, rng);
+    }
+
+    /// Bring every cell `(x, y)` to life with the chance `density(x, y)`.
+    pub fn fill_random_with(&mut self, density: impl Fn(usize, usize) -> f64, rng: &mut impl Rng) {
+        for y in 0..self.height {
+            for x in 0..self.width {
+                self.cells.set(x, y, rng.gen_bool(density(x, y)));
+            }
+        }
+        self.rows = OnceCell::new();
+    }
+
+    /// Count the live cells.
+    pub fn population(&self) -> usize {
+        self.cells.population()
+    }
+
+    /// Smallest rectangle `(left, top, right, bottom)` containing every live cell.
+    pub fn bounding_box(&self) -> Option<(usize, usize, usize, usize)> {
+        self.cells.bounding_box()
+    }
+
+    /// Copy the live cells inside the bounding box into a pattern.
+    pub fn to_pattern(&self) -> Pattern {
+        let mut pattern = Pattern::default();
+        if let Some((left, top, right, bottom)) = self.bounding_box() {
+            pattern.width = right - left + 1;
+            pattern.height = bottom - top + 1;
+            pattern.cells = self
+                .cells
+                .live_cells()
+                .map(|(x, y)| (x - left, y - top))
+                .collect();
+        }
+        pattern
+    }
+
+    /// Approximate number of bytes held by both generations, packed and unpacked.
+    pub fn memory_usage(&self) -> usize {
+        let unpacked = [&self.rows, &self.previous_rows]
+            .iter()
+            .filter_map(|rows| rows.get())
+            .map(|rows| perf::grid_bytes(rows))
+            .sum::<usize>();
+        self.cells.memory_usage() + self.previous.memory_usage() + unpacked
+    }
+
+    /// The cell `dx` columns and `dy` rows away, if it is inside the universe or
+    /// the edges wrap around.
+    pub fn offset(&self, x: usize, y: usize, dx: isize, dy: isize) -> Option<(usize, usize)> {
+        let (width, height) = (self.width as isize, self.height as isize);
+        let (mut nx, ny) = (x as isize + dx, y as isize + dy);
+        if !self.toroidal {
+            let inside = (0..width).contains(&nx) && (0..height).contains(&ny);
+            return inside.then_some((nx as usize, ny as usize));
+        }
+        // Crossing the top or bottom edge also moves along it by the shift
+        if ny < 0 {
+            nx -= self.wrap_shift as isize;
+        } else if ny >= height {
+            nx += self.wrap_shift as isize;
+        }
+        Some((
+            nx.rem_euclid(width) as usize,
+            ny.rem_euclid(height) as usize,
+        ))
+    }
+
+    /// Count the live neighbors of a cell.
+    fn live_neighbor_count(&self, x: usize, y: usize) -> usize {
+        let cells = self.cells();
+        let mut count = 0;
+        // Check the 3x3 grid around the cell
+        // The following code wraps around the edges of the grid.
+        // This is a common technique in Game of Life implementations.
+        // However, it is not the only way to handle the edges.
+        // Infact, the more consistent way is to ignore the edges, because the Game of Life is played on an infinite grid.
+        if self.toroidal {
+            // On a torus the neighbors across an edge are at the opposite edge
+            return (-1..=1)
+                .flat_map(|dy| (-1..=1).map(move |dx| (dx, dy)))
+                .filter(|&offset| offset != (0, 0))
+                .filter_map(|(dx, dy)| self.offset(x, y, dx, dy))
+                .filter(|&(nx, ny)| cells[ny][nx])
+                .count();
+        }
+        let xs = [x.wrapping_sub(1), x, x + 1];
+        let ys = [y.wrapping_sub(1), y, y + 1];
+
+        for &i in &ys {
+            if i >= self.height {
+                continue;
+            }
+            for &j in &xs {
+                if j >= self.width || (i == y && j == x) {
+                    continue;
+                }
+                if cells[i][j] {
+                    count += 1;
+                }
+            }
+        }
+
+        count
+    }
+
+    /// Advance the universe by one generation one cell at a time, slowly but
+    /// simply enough to check the packed stepping against.
+    pub fn step_scalar(&mut self) {
+        let cells = self.cells();
+        let mut next = vec![vec![false; self.width]; self.height];
+        for y in 0..self.height {
+            for x in 0..self.width {
+                if self.frozen.iter().any(|region| region.contains(x, y)) {
+                    next[y][x] = cells[y][x];
+                    continue;
+                }
+                let live_neighbors = self.live_neighbor_count(x, y);
+                next[y][x] = if cells[y][x] {
+                    self.rule.survives(live_neighbors)
+                } else {
+                    self.rule.born(live_neighbors)
+                };
+            }
+        }
+        self.jump(next, 1);
+    }
+
+    /// Advance the universe by one generation based on its rule.
+    pub fn step(&mut self) {
+        self.advance(1);
+    }
+
+    /// Advance the universe by `generations`, working on the cells packed into words.
+    pub fn advance(&mut self, generations: u64) {
+        if generations == 0 {
+            return;
+        }
+        let edges = Edges {
+            toroidal: self.toroidal,
+            wrap_shift: self.wrap_shift,
+        };
+        let frozen = (!self.frozen.is_empty())
+            .then(|| BitGrid::from_regions(&self.frozen, self.width, self.height));
+        for _ in 0..generations {
+            self.cells
+                .step(&mut self.previous, self.rule, edges, frozen.as_ref());
+            std::mem::swap(&mut self.cells, &mut self.previous);
+        }
+        // After a single generation, the rows unpacked before are those of the previous one
+        self.previous_rows = match generations {
+            1 => std::mem::take(&mut self.rows),
+            _ => OnceCell::new(),
+        };
+        self.rows = OnceCell::new();
+        self.generation += generations;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaCha8Rng;
+
+    use super::{Region, Universe};
+    use crate::rule::Rule;
+
+    /// A random universe of an awkward size, so that rows end anywhere in a word.
+    fn random_universe(rng: &mut ChaCha8Rng) -> Universe {
+        let mut universe = Universe::new(rng.gen_range(1..150), rng.gen_range(1..40));
+        universe.fill_random(0.4, rng);
+        universe
+    }
+
+    /// Step a copy of the universe one cell at a time alongside it, comparing
+    /// every generation.
+    fn assert_matches_scalar(mut packed: Universe, generations: u64) {
+        let mut scalar = packed.clone();
+        for generation in 1..=generations {
+            packed.step();
+            scalar.step_scalar();
+            assert_eq!(
+                packed.cells(),
+                scalar.cells(),
+                "{}x{}, generation {}",
+                packed.width(),
+                packed.height(),
+                generation
+            );
+            assert_eq!(packed.previous(), scalar.previous());
+            assert_eq!(packed.population(), scalar.population());
+        }
+    }
+
+    #[test]
+    fn packed_stepping_matches_scalar_when_bounded() {
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+        for _ in 0..40 {
+            assert_matches_scalar(random_universe(&mut rng), 20);
+        }
+    }
+
+    #[test]
+    fn packed_stepping_matches_scalar_on_a_torus() {
+        let mut rng = ChaCha8Rng::seed_from_u64(2);
+        for _ in 0..40 {
+            let mut universe = random_universe(&mut rng);
+            universe.set_toroidal(true);
+            // Twisted now and then, by more than the width too
+            if rng.gen_bool(0.5) {
+                universe.set_wrap_shift(rng.gen_range(0..300));
+            }
+            assert_matches_scalar(universe, 20);
+        }
+    }
+
+    #[test]
+    fn packed_stepping_matches_scalar_in_other_rules_and_frozen_regions() {
+        let mut rng = ChaCha8Rng::seed_from_u64(3);
+        let rules = ["B36/S23", "B2/S", "B3678/S34678", "B0123478/S01234678"];
+        for rule in rules {
+            for _ in 0..10 {
+                let mut universe = random_universe(&mut rng);
+                universe.set_rule(Rule::parse(rule).unwrap());
+                universe.set_toroidal(rng.gen_bool(0.5));
+                let (width, height) = (universe.width(), universe.height());
+                universe.freeze(Region {
+                    left: rng.gen_range(0..width),
+                    top: rng.gen_range(0..height),
+                    width: rng.gen_range(1..=width),
+                    height: rng.gen_range(1..=height),
+                });
+                assert_matches_scalar(universe, 10);
+            }
+        }
+    }
+
+    #[test]
+    fn advancing_at_once_matches_stepping() {
+        let mut rng = ChaCha8Rng::seed_from_u64(4);
+        for _ in 0..20 {
+            let mut at_once = random_universe(&mut rng);
+            at_once.set_toroidal(rng.gen_bool(0.5));
+            let mut stepped = at_once.clone();
+            let generations = rng.gen_range(2..30);
+            at_once.advance(generations);
+            for _ in 0..generations {
+                stepped.step();
+            }
+            assert_eq!(at_once.cells(), stepped.cells());
+            assert_eq!(at_once.previous(), stepped.previous());
+            assert_eq!(at_once.generation(), stepped.generation());
+        }
+    }
+
+    #[test]
+    fn edits_show_in_rows_already_unpacked() {
+        let mut universe = Universe::new(70, 3);
+        assert!(!universe.cells()[1][65]);
+        universe.set(65, 1, true);
+        assert!(universe.toggle(3, 2));
+        assert!(universe.cells()[1][65] && universe.cells()[2][3]);
+        universe.clear_region(Region {
+            left: 60,
+            top: 0,
+            width: 10,
+            height: 3,
+        });
+        assert!(!universe.cells()[1][65]);
+        assert_eq!(universe.population(), 1);
+    }
+}
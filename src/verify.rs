@@ -0,0 +1,125 @@
+use crate::cli::VerifyOptions;
+use crate::pattern::Pattern;
+use crate::universe::Universe;
+
+// Expected states, one line per pattern; see the file for the columns.
+const GOLDEN: &str = include_str!("../golden/golden.txt");
+// Reference patterns, compiled in so that the check does not depend on the working directory.
+const PATTERNS: &[(&str, &str)] = &[
+    ("acorn", include_str!("../golden/acorn.rle")),
+    ("blinker", include_str!("../golden/blinker.rle")),
+    ("diehard", include_str!("../golden/diehard.rle")),
+    ("glider", include_str!("../golden/glider.rle")),
+    ("gosper_gun", include_str!("../golden/gosper_gun.rle")),
+    ("lwss", include_str!("../golden/lwss.rle")),
+    ("pulsar", include_str!("../golden/pulsar.rle")),
+    ("r_pentomino", include_str!("../golden/r_pentomino.rle")),
+];
+
+/// One line of the golden file.
+struct Golden<'a> {
+    name: &'a str,
+    generations: u64,
+    width: usize,
+    height: usize,
+    population: usize,
+    hash: u64,
+}
+
+/// Run every reference pattern and compare the results with the golden values.
+///
+/// With `bless`, print a new golden file with the current results instead.
+pub fn run(options: &VerifyOptions) -> Result<(), String> {
+    let mut failures = 0;
+    let mut entries = 0;
+    for line in GOLDEN.lines() {
+        if line.starts_with('#') || line.trim().is_empty() {
+            if options.bless {
+                println!("{}", line);
+            }
+            continue;
+        }
+        let golden = parse_line(line)?;
+        let (population, hash) = simulate(&golden)?;
+        entries += 1;
+
+        if options.bless {
+            println!(
+                "{} {} {} {} {} {:016x}",
+                golden.name, golden.generations, golden.width, golden.height, population, hash
+            );
+        } else if population == golden.population && hash == golden.hash {
+            println!("ok   {}", golden.name);
+        } else {
+            failures += 1;
+            println!(
+                "FAIL {} after {} generations",
+                golden.name, golden.generations
+            );
+            if population != golden.population {
+                println!(
+                    "       population: expected {}, got {}",
+                    golden.population, population
+                );
+            }
+            if hash != golden.hash {
+                println!(
+                    "       state hash: expected {:016x}, got {:016x}",
+                    golden.hash, hash
+                );
+            }
+        }
+    }
+
+    if failures > 0 {
+        return Err(format!(
+            "{} of {} patterns differ from their golden values",
+            failures, entries
+        ));
+    }
+    Ok(())
+}
+
+fn parse_line(line: &str) -> Result<Golden<'_>, String> {
+    let invalid = || format!("invalid golden line `{}`", line);
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    let [name, generations, width, height, population, hash] = fields[..] else {
+        return Err(invalid());
+    };
+    Ok(Golden {
+        name,
+        generations: generations.parse().map_err(|_| invalid())?,
+        width: width.parse().map_err(|_| invalid())?,
+        height: height.parse().map_err(|_| invalid())?,
+        population: population.parse().map_err(|_| invalid())?,
+        hash: u64::from_str_radix(hash, 16).map_err(|_| invalid())?,
+    })
+}
+
+/// Run a reference pattern and return its final population and state hash.
+fn simulate(golden: &Golden) -> Result<(usize, u64), String> {
+    let text = PATTERNS
+        .iter()
+        .find(|(name, _)| *name == golden.name)
+        .map(|(_, text)| text)
+        .ok_or_else(|| format!("no reference pattern named `{}`", golden.name))?;
+    let pattern = Pattern::parse(text).map_err(|e| format!("{}: {}", golden.name, e))?;
+
+    let mut universe = Universe::new(golden.width, golden.height);
+    let (left, top) = universe.centered(&pattern);
+    universe.place(&pattern, left, top);
+    for _ in 0..golden.generations {
+        universe.step();
+    }
+    Ok((universe.population(), state_hash(universe.cells())))
+}
+
+/// FNV-1a over every cell, stable across platforms and compiler versions.
+fn state_hash(grid: &[Vec<bool>]) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for &alive in grid.iter().flatten() {
+        hash ^= alive as u64;
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    hash
+}
@@ -0,0 +1,87 @@
+use std::path::{Path, PathBuf};
+
+use crate::save::SAVE_DIR;
+
+// Subdirectory of the saves that receives the snapshots.
+const SNAPSHOT_DIR: &str = "snapshots";
+// How much a new high has to beat the last saved one by, so that a growing
+// pattern does not write a file every generation.
+const PEAK_MARGIN: f64 = 0.05;
+
+/// Why a snapshot is taken.
+#[derive(Clone, Copy)]
+pub enum Trigger {
+    /// The population reached a new high.
+    Peak,
+    /// The population rose above the threshold.
+    Threshold,
+}
+
+/// Decides when the population is worth a snapshot of the grid, so that the
+/// record-setting moments of an unattended run are kept.
+pub struct PopulationWatcher {
+    peaks: bool,
+    threshold: Option<usize>,
+    /// Highest population seen since the last reset.
+    high: usize,
+    /// Population of the last peak that was saved.
+    saved_high: usize,
+    /// Whether the population is above the threshold.
+    above: bool,
+}
+
+impl PopulationWatcher {
+    /// Watch for new highs if `peaks` is set, and for the population rising above `threshold`.
+    pub fn new(peaks: bool, threshold: Option<usize>) -> PopulationWatcher {
+        PopulationWatcher {
+            peaks,
+            threshold,
+            high: 0,
+            saved_high: 0,
+            above: false,
+        }
+    }
+
+    /// Whether there is anything to watch for.
+    pub fn is_enabled(&self) -> bool {
+        self.peaks || self.threshold.is_some()
+    }
+
+    /// Start over, e.g. for a new pattern.
+    pub fn reset(&mut self) {
+        self.high = 0;
+        self.saved_high = 0;
+        self.above = false;
+    }
+
+    /// Look at the population of a new generation and tell whether to take a snapshot.
+    pub fn observe(&mut self, population: usize) -> Option<Trigger> {
+        let was_above = self.above;
+        self.above = self
+            .threshold
+            .is_some_and(|threshold| population > threshold);
+        if self.above && !was_above {
+            return Some(Trigger::Threshold);
+        }
+        if population <= self.high {
+            return None;
+        }
+        self.high = population;
+        if self.peaks && population as f64 > self.saved_high as f64 * (1.0 + PEAK_MARGIN) {
+            self.saved_high = population;
+            return Some(Trigger::Peak);
+        }
+        None
+    }
+}
+
+/// Where the snapshot of a generation is saved, e.g. `saves/snapshots/peak_g1200_p834.state.gz`.
+pub fn snapshot_path(trigger: Trigger, generation: u64, population: usize) -> PathBuf {
+    let kind = match trigger {
+        Trigger::Peak => "peak",
+        Trigger::Threshold => "threshold",
+    };
+    Path::new(SAVE_DIR)
+        .join(SNAPSHOT_DIR)
+        .join(format!("{}_g{}_p{}.state.gz", kind, generation, population))
+}
@@ -0,0 +1,81 @@
+use ggez::graphics::Color;
+
+use crate::locale::Message;
+use crate::multistate::{Automaton, State, EMPTY};
+
+pub const CONDUCTOR: State = 1;
+pub const HEAD: State = 2;
+pub const TAIL: State = 3;
+
+// A clock: an electron going round a loop of wire, sending a copy down the
+// wire to the right every lap. `#` is a conductor, `H` an electron head and
+// `t` its tail.
+const DEMO: [&str; 3] = [".tH#.", "#...############", ".###."];
+
+/// Brian Silverman's Wireworld: electrons run along wires of conductor, a
+/// conductor becoming an electron head next to one or two heads, a head
+/// becoming a tail and a tail becoming conductor again.
+pub struct Wireworld;
+
+impl Automaton for Wireworld {
+    fn name(&self) -> &'static str {
+        "Wireworld"
+    }
+
+    fn description(&self) -> Message {
+        Message::RuleWireworld
+    }
+
+    fn drawn(&self) -> State {
+        CONDUCTOR
+    }
+
+    fn cycled(&self, state: State) -> State {
+        match state {
+            CONDUCTOR => HEAD,
+            HEAD => TAIL,
+            TAIL => CONDUCTOR,
+            _ => EMPTY,
+        }
+    }
+
+    fn next(&self, state: State, neighbors: &[State; 8]) -> State {
+        match state {
+            HEAD => TAIL,
+            TAIL => CONDUCTOR,
+            CONDUCTOR => {
+                let heads = neighbors.iter().filter(|&&n| n == HEAD).count();
+                if heads == 1 || heads == 2 {
+                    HEAD
+                } else {
+                    CONDUCTOR
+                }
+            }
+            _ => EMPTY,
+        }
+    }
+
+    fn color(&self, state: State) -> Color {
+        match state {
+            CONDUCTOR => Color::new(0.9, 0.6, 0.1, 1.0),
+            HEAD => Color::new(0.3, 0.6, 1.0, 1.0),
+            TAIL => Color::new(0.9, 0.2, 0.1, 1.0),
+            _ => Color::BLACK,
+        }
+    }
+
+    fn demo(&self) -> Vec<Vec<State>> {
+        DEMO.iter()
+            .map(|row| {
+                row.chars()
+                    .map(|c| match c {
+                        '#' => CONDUCTOR,
+                        'H' => HEAD,
+                        't' => TAIL,
+                        _ => EMPTY,
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,134 @@
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+
+use crate::annotations::Label;
+use crate::camera::Camera;
+use crate::game::ViewMode;
+use crate::placements::Placement;
+use crate::save::SAVE_DIR;
+use crate::universe::Region;
+
+/// Extension of workspace files.
+pub const EXTENSION: &str = "krida";
+/// Version of the format written by this build, the only one it reads.
+pub const VERSION: u32 = 1;
+// Every gzip stream starts with these two bytes.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Everything needed to reopen an experiment exactly as it was left: the grid
+/// with its recent history, the labels, the placed patterns, the rule and the
+/// view, stored as gzip-compressed TOML.
+#[derive(Serialize, Deserialize)]
+pub struct Workspace {
+    pub version: u32,
+    pub rule: String,
+    pub generation: u64,
+    pub toroidal: bool,
+    pub wrap_shift: usize,
+    pub view: ViewMode,
+    /// Missing from workspaces saved before the view could be zoomed and panned.
+    #[serde(default)]
+    pub camera: Camera,
+    /// Whole milliseconds in workspaces saved before delays could have fractions.
+    pub update_delay_ms: f64,
+    pub grid: Rows,
+    /// Earlier generations, oldest first.
+    pub history: Vec<Rows>,
+    pub labels: Vec<Label>,
+    pub placements: Vec<Placement>,
+    /// Missing from workspaces saved before regions could be frozen.
+    #[serde(default)]
+    pub frozen: Vec<Region>,
+}
+
+/// A grid, written as rows of `.` and `O`.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(from = "Vec<String>", into = "Vec<String>")]
+pub struct Rows(pub Vec<Vec<bool>>);
+
+impl From<Vec<String>> for Rows {
+    fn from(rows: Vec<String>) -> Rows {
+        Rows(
+            rows.iter()
+                .map(|row| row.chars().map(|c| c == 'O').collect())
+                .collect(),
+        )
+    }
+}
+
+impl From<Rows> for Vec<String> {
+    fn from(rows: Rows) -> Vec<String> {
+        rows.0
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|&alive| if alive { 'O' } else { '.' })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+impl Workspace {
+    /// Width and height of the saved grid.
+    pub fn size(&self) -> (usize, usize) {
+        (self.grid.0.first().map_or(0, Vec::len), self.grid.0.len())
+    }
+
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let text = toml::to_string(self).map_err(io::Error::other)?;
+        let file = BufWriter::new(File::create(path)?);
+        let mut out = GzEncoder::new(file, Compression::default());
+        out.write_all(text.as_bytes())?;
+        out.finish()?.flush()
+    }
+
+    /// Read a workspace, decompressing it first if it is gzipped.
+    pub fn read(path: &Path) -> io::Result<Workspace> {
+        let bytes = fs::read(path)?;
+        let mut text = String::new();
+        if bytes.starts_with(&GZIP_MAGIC) {
+            GzDecoder::new(&bytes[..]).read_to_string(&mut text)?;
+        } else {
+            text = String::from_utf8(bytes).map_err(|e| invalid(&e.to_string()))?;
+        }
+        let workspace: Workspace = toml::from_str(&text).map_err(|e| invalid(&e.to_string()))?;
+        if workspace.version != VERSION {
+            return Err(invalid(&format!(
+                "unsupported workspace version {}",
+                workspace.version
+            )));
+        }
+        let (width, height) = workspace.size();
+        let rectangular =
+            |rows: &Rows| rows.0.len() == height && rows.0.iter().all(|row| row.len() == width);
+        if width == 0 || !rectangular(&workspace.grid) || !workspace.history.iter().all(rectangular)
+        {
+            return Err(invalid("the grid is not rectangular"));
+        }
+        Ok(workspace)
+    }
+}
+
+/// Where a workspace called `name` is kept: in the saves directory, unless the
+/// name is already a path with an extension.
+pub fn path_for(name: &str) -> PathBuf {
+    let path = Path::new(name);
+    if path.extension().is_some() {
+        path.to_path_buf()
+    } else {
+        Path::new(SAVE_DIR).join(name).with_extension(EXTENSION)
+    }
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}